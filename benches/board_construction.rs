@@ -0,0 +1,52 @@
+use bevy::{ecs::system::CommandQueue, prelude::*};
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use minesweeper_rs::{
+    game::{
+        options::{Expert, GameOptions, PresetInfo},
+        tilemap::TileMap,
+        GamePlugin,
+    },
+    style::prelude::GameAssets,
+};
+
+/// Spawns the tiles and covers of an Expert board, the tile map is generated
+/// beforehand so only the construction of the entities is measured
+fn spawn_expert_board(c: &mut Criterion) {
+    let game_options = GameOptions {
+        size: Expert.size(),
+        bomb_count: Expert.bomb_count(),
+        ..default()
+    };
+    let window = Window::default();
+    let game_assets = GameAssets::default();
+
+    c.bench_function("spawn expert board", |b| {
+        b.iter_batched(
+            || {
+                let mut tile_map = TileMap::empty(game_options.size);
+                tile_map
+                    .set_bombs_seeded(game_options.bomb_count, 0)
+                    .unwrap();
+                (World::new(), tile_map)
+            },
+            |(mut world, tile_map)| {
+                let mut queue = CommandQueue::default();
+                let mut commands = Commands::new(&mut queue, &world);
+                GamePlugin::start_game(
+                    &mut commands,
+                    &window,
+                    &game_options,
+                    &game_assets,
+                    tile_map,
+                    None,
+                );
+                queue.apply(&mut world);
+                world
+            },
+            BatchSize::SmallInput,
+        );
+    });
+}
+
+criterion_group!(benches, spawn_expert_board);
+criterion_main!(benches);
@@ -0,0 +1,46 @@
+use std::{collections::HashSet, hint::black_box};
+
+use bevy::math::UVec2;
+use criterion::{criterion_group, criterion_main, Criterion};
+use minesweeper_rs::game::tilemap::{TileMap, VisitedSet};
+
+/// Size of the custom board the flood fill is measured on
+const SIZE: UVec2 = UVec2::new(100, 100);
+
+fn floodfill(c: &mut Criterion) {
+    let empty = TileMap::empty(SIZE);
+    c.bench_function("floodfill 100x100 empty", |b| {
+        b.iter(|| empty.floodfill_from(black_box(SIZE / 2)))
+    });
+
+    // One in ten tiles is a bomb, the fill opens the region of the first empty tile
+    let mut tile_map = TileMap::empty(SIZE);
+    tile_map.set_bombs_seeded(SIZE.x * SIZE.y / 10, 0).unwrap();
+    let start = tile_map.find_empty_tile().unwrap();
+    c.bench_function("floodfill 100x100 with bombs", |b| {
+        b.iter(|| tile_map.floodfill_from(black_box(start)))
+    });
+}
+
+/// Visits every tile of the board once, compared to the `HashSet` the fill used before
+fn visited_set(c: &mut Criterion) {
+    let positions = (0..SIZE.y)
+        .flat_map(|y| (0..SIZE.x).map(move |x| UVec2::new(x, y)))
+        .collect::<Vec<_>>();
+
+    c.bench_function("visit 100x100 with VisitedSet", |b| {
+        b.iter(|| {
+            let mut visited = VisitedSet::new(SIZE);
+            positions.iter().filter(|&&pos| visited.insert(pos)).count()
+        })
+    });
+    c.bench_function("visit 100x100 with HashSet", |b| {
+        b.iter(|| {
+            let mut visited = HashSet::new();
+            positions.iter().filter(|&&pos| visited.insert(pos)).count()
+        })
+    });
+}
+
+criterion_group!(benches, floodfill, visited_set);
+criterion_main!(benches);
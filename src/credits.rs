@@ -0,0 +1,209 @@
+use bevy::{
+    asset::{io::Reader, AssetLoader, AsyncReadExt as _, LoadContext},
+    prelude::*,
+    utils::BoxedFuture,
+};
+use serde::Deserialize;
+
+use crate::{style::prelude::*, util::despawn_all, AppState};
+
+/// Asset file holding the credits, relative to the assets directory
+const CREDITS_FILE: &str = "credits.toml";
+
+/// Pixels the credits move up per second
+const CREDITS_SCROLL_SPEED: f32 = 40.;
+
+/// A section of the credits file, e.g. `[libraries]`
+#[derive(Deserialize)]
+struct CreditsSection {
+    title: String,
+    #[serde(default)]
+    entries: Vec<String>,
+}
+
+/// Credits shown on the credits screen, the sections in the order of the file
+#[derive(Asset, TypePath)]
+struct Credits(Vec<CreditsSection>);
+
+#[derive(Default)]
+struct CreditsLoader;
+
+impl AssetLoader for CreditsLoader {
+    type Asset = Credits;
+    type Settings = ();
+    type Error = std::io::Error;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a Self::Settings,
+        _load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<Self::Asset, Self::Error>> {
+        Box::pin(async move {
+            let mut contents = String::new();
+            reader.read_to_string(&mut contents).await?;
+
+            let invalid_data =
+                |err: toml::de::Error| std::io::Error::new(std::io::ErrorKind::InvalidData, err);
+            let table = toml::from_str::<toml::Table>(&contents).map_err(invalid_data)?;
+            let sections = table
+                .into_iter()
+                .map(|(_, section)| section.try_into().map_err(invalid_data))
+                .collect::<Result<_, _>>()?;
+
+            Ok(Credits(sections))
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["toml"]
+    }
+}
+
+/// Credits file of the credits screen, kept while it is loading
+#[derive(Resource)]
+struct CreditsHandle(Handle<Credits>);
+
+#[derive(Component)]
+struct OnCreditsScreen;
+
+/// Column of credits moving up the screen, the offset in pixels from the bottom
+/// of the screen to its top
+#[derive(Component, Default)]
+struct CreditsScroll(f32);
+
+pub struct CreditsPlugin;
+
+impl Plugin for CreditsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<Credits>()
+            .init_asset_loader::<CreditsLoader>()
+            .add_systems(OnEnter(AppState::Credits), Self::setup)
+            .add_systems(OnExit(AppState::Credits), despawn_all::<OnCreditsScreen>)
+            .add_systems(
+                Update,
+                (
+                    Self::spawn_credits.run_if(resource_exists::<CreditsHandle>),
+                    Self::scroll_credits,
+                    Self::return_to_menu,
+                )
+                    .chain()
+                    .run_if(in_state(AppState::Credits)),
+            );
+    }
+}
+
+impl CreditsPlugin {
+    fn setup(mut commands: Commands, asset_server: Res<AssetServer>, ui_assets: Res<UiAssets>) {
+        commands.insert_resource(CreditsHandle(asset_server.load(CREDITS_FILE)));
+
+        commands.spawn((
+            NodeBundle {
+                style: Style {
+                    justify_content: JustifyContent::Center,
+                    width: Val::Percent(100.),
+                    height: Val::Percent(100.),
+                    overflow: Overflow::clip(),
+                    ..Default::default()
+                },
+                background_color: ui_assets.background.into(),
+                ..Default::default()
+            },
+            OnCreditsScreen,
+        ));
+    }
+
+    /// Fills the screen with the credits once the file has been loaded
+    fn spawn_credits(
+        mut commands: Commands,
+        handle: Res<CreditsHandle>,
+        credits: Res<Assets<Credits>>,
+        screens: Query<Entity, With<OnCreditsScreen>>,
+        ui_assets: Res<UiAssets>,
+    ) {
+        let Some(credits) = credits.get(&handle.0) else {
+            return;
+        };
+        commands.remove_resource::<CreditsHandle>();
+
+        let entry_style = TextStyle {
+            font_size: 25.,
+            color: ui_assets.foreground,
+            font: ui_assets.font.clone(),
+        };
+        let title_style = TextStyle {
+            font_size: 40.,
+            color: ui_assets.accent,
+            font: ui_assets.font.clone(),
+        };
+
+        for screen in screens.iter() {
+            commands.entity(screen).with_children(|parent| {
+                parent
+                    .spawn((
+                        NodeBundle {
+                            style: Style {
+                                position_type: PositionType::Absolute,
+                                top: Val::Percent(100.),
+                                flex_direction: FlexDirection::Column,
+                                align_items: AlignItems::Center,
+                                row_gap: Val::Px(10.),
+                                ..Default::default()
+                            },
+                            ..Default::default()
+                        },
+                        CreditsScroll::default(),
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn(TextBundle::from_section("Credits", ui_assets.style_title()));
+
+                        for section in &credits.0 {
+                            parent.spawn(
+                                TextBundle::from_section(&section.title, title_style.clone())
+                                    .with_style(Style {
+                                        margin: UiRect::top(Val::Px(40.)),
+                                        ..Default::default()
+                                    }),
+                            );
+                            for entry in &section.entries {
+                                parent.spawn(TextBundle::from_section(entry, entry_style.clone()));
+                            }
+                        }
+                    });
+            });
+        }
+    }
+
+    /// Moves the credits up from the bottom of the screen, starting over
+    /// once they have passed the top
+    fn scroll_credits(
+        time: Res<Time>,
+        screens: Query<&Node, With<OnCreditsScreen>>,
+        mut columns: Query<(&Node, &mut Style, &mut CreditsScroll)>,
+    ) {
+        let Ok(screen) = screens.get_single() else {
+            return;
+        };
+        let screen_height = screen.size().y;
+
+        for (column, mut style, mut scroll) in columns.iter_mut() {
+            scroll.0 += CREDITS_SCROLL_SPEED * time.delta_seconds();
+            if scroll.0 > screen_height + column.size().y {
+                scroll.0 = 0.;
+            }
+            style.top = Val::Px(screen_height - scroll.0);
+        }
+    }
+
+    fn return_to_menu(
+        keyboard_input: Res<ButtonInput<KeyCode>>,
+        mouse_buttons: Res<ButtonInput<MouseButton>>,
+        mut app_state: ResMut<NextState<AppState>>,
+    ) {
+        if keyboard_input.get_just_pressed().next().is_some()
+            || mouse_buttons.get_just_pressed().next().is_some()
+        {
+            app_state.set(AppState::Menu);
+        }
+    }
+}
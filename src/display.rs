@@ -0,0 +1,130 @@
+use std::fs;
+
+use bevy::{prelude::*, window::WindowMode};
+use serde::{Deserialize, Serialize};
+
+use crate::util::data_file;
+
+/// File name of the display settings file in the data directory
+const DISPLAY_FILE: &str = "minesweeper_display.json";
+
+/// Slowest animation speed other than disabling animations
+const MIN_ANIMATION_SPEED: f32 = 0.25;
+
+/// Fastest animation speed
+const MAX_ANIMATION_SPEED: f32 = 4.;
+
+/// Animation speeds the settings screen cycles through
+const ANIMATION_SPEEDS: [f32; 6] = [0., 0.25, 0.5, 1., 2., 4.];
+
+/// Display preferences, saved whenever they change
+#[derive(Resource, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DisplaySettings {
+    pub fullscreen: bool,
+    /// Multiplier of the speed of the reveal, flag and particle animations
+    /// between 0.25 and 4. A speed of 0 disables animations, revealed covers
+    /// and removed flags disappear right away and no particles are spawned
+    pub animation_speed: f32,
+}
+
+impl Default for DisplaySettings {
+    fn default() -> Self {
+        Self {
+            fullscreen: false,
+            animation_speed: 1.,
+        }
+    }
+}
+
+impl DisplaySettings {
+    /// Returns how long an animation taking `duration` seconds at normal speed
+    /// takes, `None` when animations are disabled
+    pub fn animation_duration(&self, duration: f32) -> Option<f32> {
+        (self.animation_speed > 0.).then(|| {
+            duration
+                / self
+                    .animation_speed
+                    .clamp(MIN_ANIMATION_SPEED, MAX_ANIMATION_SPEED)
+        })
+    }
+
+    /// Returns the next speed of `ANIMATION_SPEEDS`, wrapping around after the fastest
+    pub fn next_animation_speed(&self) -> f32 {
+        ANIMATION_SPEEDS
+            .into_iter()
+            .find(|&speed| speed > self.animation_speed)
+            .unwrap_or(ANIMATION_SPEEDS[0])
+    }
+
+    pub fn save(&self) -> std::io::Result<()> {
+        let path = data_file(DISPLAY_FILE);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        fs::write(path, serde_json::to_string_pretty(self)?)
+    }
+}
+
+pub struct DisplayPlugin;
+
+impl Plugin for DisplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, Self::load_display_settings)
+            .add_systems(
+                Update,
+                (
+                    Self::toggle_fullscreen,
+                    Self::apply_display_settings.run_if(resource_changed::<DisplaySettings>),
+                )
+                    .chain(),
+            );
+    }
+}
+
+impl DisplayPlugin {
+    fn load_display_settings(mut commands: Commands) {
+        let settings = fs::read_to_string(data_file(DISPLAY_FILE))
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default();
+
+        commands.insert_resource::<DisplaySettings>(settings);
+    }
+
+    fn toggle_fullscreen(
+        keyboard_input: Res<ButtonInput<KeyCode>>,
+        mut display_settings: ResMut<DisplaySettings>,
+    ) {
+        if keyboard_input.just_pressed(KeyCode::F11) {
+            display_settings.fullscreen = !display_settings.fullscreen;
+        }
+    }
+
+    /// Applies the settings to the window, the board is laid out
+    /// again by the resulting resize
+    fn apply_display_settings(
+        display_settings: Res<DisplaySettings>,
+        mut windows: Query<&mut Window>,
+    ) {
+        let mode = if display_settings.fullscreen {
+            WindowMode::BorderlessFullscreen
+        } else {
+            WindowMode::Windowed
+        };
+
+        for mut window in windows.iter_mut() {
+            window.mode = mode;
+        }
+
+        // Freshly loaded settings are already on disk
+        if display_settings.is_added() {
+            return;
+        }
+
+        if let Err(err) = display_settings.save() {
+            warn!("Could not save display settings: {err}");
+        }
+    }
+}
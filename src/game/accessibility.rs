@@ -0,0 +1,88 @@
+use bevy::{
+    a11y::{
+        accesskit::{NodeBuilder, Role},
+        AccessibilityNode, Focus,
+    },
+    prelude::*,
+};
+
+use super::{board::Board, cursor::CursorTile, tilemap::TileType, GameState, Position, Tile};
+
+/// Label currently given to screen readers for a tile,
+/// kept to only rebuild the accessibility node when it changes
+#[derive(Component)]
+struct TileLabel(String);
+
+/// Describes the tiles to screen readers and moves the screen
+/// reader focus along with the keyboard cursor
+pub struct TileAccessibilityPlugin;
+
+impl Plugin for TileAccessibilityPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                Self::update_labels.run_if(resource_changed::<Board>),
+                Self::focus_cursor.run_if(resource_changed::<CursorTile>),
+            )
+                .run_if(in_state(GameState::Playing)),
+        );
+    }
+}
+
+impl TileAccessibilityPlugin {
+    fn update_labels(
+        mut commands: Commands,
+        board: Res<Board>,
+        tiles: Query<(Entity, &Position, Option<&TileLabel>), With<Tile>>,
+    ) {
+        for (entity, position, label) in tiles.iter() {
+            let Some(tile) = board.tile_map.get_tile(position.0) else {
+                continue;
+            };
+
+            let description = if tile.cover.is_some() {
+                format!(
+                    "Covered tile at column {} row {}",
+                    position.0.x + 1,
+                    position.0.y + 1
+                )
+            } else {
+                match tile.tile_type {
+                    TileType::Empty => "Empty".to_string(),
+                    TileType::Bomb => "Mine".to_string(),
+                    TileType::Number(1) => "1 mine nearby".to_string(),
+                    TileType::Number(count) => format!("{count} mines nearby"),
+                }
+            };
+
+            let description = if tile.flag.is_some() {
+                format!("Flagged: {description}")
+            } else {
+                description
+            };
+
+            if label.is_some_and(|label| label.0 == description) {
+                continue;
+            }
+
+            let mut node = NodeBuilder::new(Role::Button);
+            node.set_name(description.clone());
+
+            commands
+                .entity(entity)
+                .insert((AccessibilityNode(node), TileLabel(description)));
+        }
+    }
+
+    fn focus_cursor(cursor_tile: Res<CursorTile>, board: Res<Board>, mut focus: ResMut<Focus>) {
+        let entity = cursor_tile
+            .0
+            .and_then(|position| board.tile_map.get_tile(position))
+            .and_then(|tile| tile.entity);
+
+        if entity.is_some() {
+            focus.0 = entity;
+        }
+    }
+}
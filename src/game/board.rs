@@ -1,46 +1,411 @@
 use bevy::prelude::*;
 
-use super::tilemap::TileMap;
+use super::{
+    camera::CameraController,
+    tilemap::{GridShape, TileMap},
+};
+
+/// Vertical distance between rows of hexagonal tiles relative to the horizontal
+/// distance, rows of pointy-top hexagons overlap by a quarter of their height
+const HEX_ROW_SPACING: f32 = 0.75;
+
+/// Returns the center of a tile relative to the bottom left corner of a board
+/// with the given number of tiles, row 0 being the top row
+pub fn tile_offset(
+    position: IVec2,
+    size: UVec2,
+    tile_size: f32,
+    tile_padding: f32,
+    shape: GridShape,
+) -> Vec2 {
+    let stride = tile_size + tile_padding;
+    let row = (size.y as i32 - position.y - 1) as f32;
+
+    let offset = match shape {
+        GridShape::Square => Vec2::new(position.x as f32, row) * stride,
+        GridShape::Hexagonal => {
+            let shift = if position.y.rem_euclid(2) == 1 {
+                stride / 2.
+            } else {
+                0.
+            };
+            Vec2::new(
+                position.x as f32 * stride + shift,
+                row * stride * HEX_ROW_SPACING,
+            )
+        }
+    };
+
+    offset + tile_size / 2.
+}
+
+/// Returns the size of a board with the given number of tiles
+pub fn board_size(size: UVec2, tile_size: f32, tile_padding: f32, shape: GridShape) -> Vec2 {
+    let stride = tile_size + tile_padding;
+    let square = size.as_vec2() * stride - tile_padding;
+
+    match shape {
+        GridShape::Square => square,
+        GridShape::Hexagonal => Vec2::new(
+            if size.y > 1 {
+                square.x + stride / 2.
+            } else {
+                square.x
+            },
+            size.y.saturating_sub(1) as f32 * stride * HEX_ROW_SPACING + tile_size,
+        ),
+    }
+}
+
+/// Returns the number of tile heights a board with the given number of rows takes up
+pub fn rows_height(rows: u32, shape: GridShape) -> f32 {
+    match shape {
+        GridShape::Square => rows as f32,
+        GridShape::Hexagonal => rows.saturating_sub(1) as f32 * HEX_ROW_SPACING + 1.,
+    }
+}
 
 /// Resource to keep track of the game tilemap and handle
 /// retrieving tiles
 #[derive(Resource)]
 pub struct Board {
     pub tile_map: TileMap,
-    pub position: Vec2,
+    pub position: Vec3,
     pub size: Vec2,
     pub tile_size: f32,
     pub tile_padding: f32,
+    /// Tiles around the safe start tile, the start tile first,
+    /// highlighted at the start of a game
+    pub safe_region: Option<Vec<UVec2>>,
+    /// Bombs that can be revealed before the game is lost
+    pub lives: u8,
+    /// Fraction of the safe tiles in the largest empty region, boards with large
+    /// open regions are generally more enjoyable. Zero until the bombs are placed
+    pub board_quality_score: f32,
 }
 
 impl Board {
+    /// Scores the board by its largest empty region, see `board_quality_score`
+    pub fn update_quality_score(&mut self) {
+        let size = self.tile_map.size();
+        let safe_tiles = (size.x * size.y).saturating_sub(self.tile_map.total_bombs());
+
+        self.board_quality_score = if self.tile_map.is_generated() && safe_tiles > 0 {
+            self.tile_map.largest_empty_region() as f32 / safe_tiles as f32
+        } else {
+            0.
+        };
+    }
+
     /// Translate a mouse position to a tile position
-    pub fn mouse_to_tile(&self, window: &Window, mouse_position: Vec2) -> Option<UVec2> {
+    ///
+    /// Window coordinates grow downwards, so row 0 is the top row of the board.
+    /// The zoom and pan of the camera are undone before looking up the tile
+    pub fn mouse_to_tile(
+        &self,
+        window: &Window,
+        mouse_position: Vec2,
+        camera: &CameraController,
+    ) -> Option<UVec2> {
         let window_size = Vec2::new(window.width(), window.height());
-        let mouse_position = mouse_position - window_size / 2.;
+        let position = camera.unproject(mouse_position - window_size / 2.);
+
+        self.tile_at_world_pos(Vec2::new(position.x, -position.y))
+    }
 
-        if !self.in_bounds(mouse_position) {
+    /// Returns the tile at a world position, `None` outside the board
+    /// and in the padding between square tiles
+    ///
+    /// This matches `start_game`, which places tile `(x, y)` at world height
+    /// `size.y - y - 1` to render row 0 at the top
+    pub fn tile_at_world_pos(&self, world_position: Vec2) -> Option<UVec2> {
+        if !self.in_bounds(world_position) {
             return None;
         }
 
-        let board_position = mouse_position - self.position;
-        let tile_position = (board_position / (self.tile_size + self.tile_padding)).as_uvec2();
-        Some(tile_position)
+        // Measured from the bottom left corner of the board like the tile offsets
+        let position = world_position - self.position.xy();
+        let size = self.tile_map.size();
+
+        match self.tile_map.shape() {
+            GridShape::Square => {
+                let stride = self.tile_size + self.tile_padding;
+                let from_top = Vec2::new(position.x, self.size.y - position.y);
+                let tile_position = (from_top / stride).floor();
+
+                // Offset within the tile, anything past the tile body is the gap
+                let within_tile = from_top - tile_position * stride;
+                if within_tile.x >= self.tile_size || within_tile.y >= self.tile_size {
+                    return None;
+                }
+
+                let tile_position = tile_position.as_uvec2();
+                (tile_position.x < size.x && tile_position.y < size.y).then_some(tile_position)
+            }
+            GridShape::Hexagonal => {
+                // Hexagons are the cells closest to their center
+                let closest = self
+                    .tile_map
+                    .iter_with_pos()
+                    .map(|(position, _)| position)
+                    .min_by(|&a, &b| {
+                        let distance_a = self.tile_offset(a).distance_squared(position);
+                        let distance_b = self.tile_offset(b).distance_squared(position);
+                        distance_a.total_cmp(&distance_b)
+                    })?;
+
+                // The corners of the board and the padding around a hexagon are not
+                // part of any tile, the shape matches `hex_image`
+                let within_tile =
+                    ((position - self.tile_offset(closest)) / (self.tile_size / 2.)).abs();
+                (within_tile.x <= 1. && within_tile.y <= 1. - within_tile.x / 2.).then_some(closest)
+            }
+        }
+    }
+
+    /// Returns the center of a tile relative to the bottom left corner of the board
+    pub fn tile_offset(&self, position: UVec2) -> Vec2 {
+        tile_offset(
+            position.as_ivec2(),
+            self.tile_map.size(),
+            self.tile_size,
+            self.tile_padding,
+            self.tile_map.shape(),
+        )
+    }
+
+    /// Returns the world position of the center of a tile
+    pub fn tile_center(&self, position: UVec2) -> Vec2 {
+        self.position.xy() + self.tile_offset(position)
     }
 
-    /// Check if a position is within the bounds of the board
+    /// Check if a world position is within the bounds of the board, both this and
+    /// `self.position` have their origin at the window center and grow upwards.
+    /// Tiles are measured from the top left corner, so like the tiles the right
+    /// and bottom edges are excluded
     fn in_bounds(&self, position: Vec2) -> bool {
-        position.x >= self.position.x
-            && position.x <= self.position.x + self.size.x
-            && position.y >= self.position.y
-            && position.y <= self.position.y + self.size.y
+        let board_position = self.position.xy();
+        position.x >= board_position.x
+            && position.x < board_position.x + self.size.x
+            && position.y > board_position.y
+            && position.y <= board_position.y + self.size.y
+    }
+
+    /// Returns the number of flagged tiles
+    pub fn flag_count(&self) -> u32 {
+        self.tile_map.count_flagged()
     }
 
+    /// Returns the number of revealed tiles, revealed bombs included
+    pub fn reveal_count(&self) -> u32 {
+        let size = self.tile_map.size();
+        size.x * size.y - self.tile_map.count_covered()
+    }
+
+    /// Counts the tiles of the board, systems should read the cached
+    /// `BoardCounts` instead unless the board changed during the same system
+    pub fn counts(&self) -> BoardCounts {
+        let size = self.tile_map.size();
+        BoardCounts {
+            covered: size.x * size.y - self.reveal_count(),
+            flagged: self.flag_count(),
+            mines: self.tile_map.count_mines(),
+            revealed_mines: self.tile_map.count_revealed_mines(),
+        }
+    }
+
+    /// Checks if as many bombs as the board has lives have been revealed
+    pub fn is_lost(&self) -> bool {
+        self.counts().is_lost(self.lives)
+    }
+
+    /// Checks if the game has been won, i.e. all non-bomb tiles
+    /// have been revealed without losing the game
+    pub fn is_won(&self) -> bool {
+        self.counts().is_won(self.lives)
+    }
+}
+
+/// Tile counts of the `Board`, updated whenever the board changes
+/// so systems don't have to iterate over all tiles every frame
+#[derive(Resource, Default, Clone, Copy)]
+pub struct BoardCounts {
+    /// Covered tiles, flagged tiles included
+    pub covered: u32,
+    pub flagged: u32,
+    pub mines: u32,
+    pub revealed_mines: u32,
+}
+
+impl BoardCounts {
     /// Checks if all non-bomb tiles have been revealed
     /// used to check if a game is finished
     pub fn all_revealed(&self) -> bool {
-        self.tile_map
-            .iter()
-            .all(|tile| tile.cover.is_none() || tile.is_bomb())
+        self.covered + self.revealed_mines == self.mines
+    }
+
+    /// Returns the number of bombs that can still be revealed
+    /// without losing the game
+    pub fn lives_left(&self, lives: u8) -> u8 {
+        (lives as u32).saturating_sub(self.revealed_mines) as u8
+    }
+
+    pub fn is_lost(&self, lives: u8) -> bool {
+        self.lives_left(lives) == 0
+    }
+
+    pub fn is_won(&self, lives: u8) -> bool {
+        self.all_revealed() && !self.is_lost(lives)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    /// Builds a board placed like `start_game` does, centered on the window
+    fn test_board(tile_map: TileMap, tile_size: f32, tile_padding: f32) -> Board {
+        let size = board_size(tile_map.size(), tile_size, tile_padding, tile_map.shape());
+
+        Board {
+            tile_map,
+            position: (-size / 2.).extend(0.),
+            size,
+            tile_size,
+            tile_padding,
+            safe_region: None,
+            lives: 1,
+            board_quality_score: 0.,
+        }
+    }
+
+    /// Builds a 3x3 board with a bomb at `(0, 0)` and 10 pixel tiles without padding
+    fn board_with_corner_bomb() -> Board {
+        test_board(
+            TileMap::with_bombs(UVec2::new(3, 3), &[UVec2::ZERO]),
+            10.,
+            0.,
+        )
+    }
+
+    /// Window exactly covered by the board
+    fn test_window(board: &Board) -> Window {
+        Window {
+            resolution: (board.size.x, board.size.y).into(),
+            ..default()
+        }
+    }
+
+    #[test]
+    fn mouse_at_top_left_maps_to_corner_bomb() {
+        let board = board_with_corner_bomb();
+        let window = test_window(&board);
+
+        let tile = board.mouse_to_tile(&window, Vec2::new(1., 1.), &CameraController::default());
+        assert_eq!(tile, Some(UVec2::ZERO));
+        assert!(board.tile_map.get_tile(tile.unwrap()).unwrap().is_bomb());
+    }
+
+    #[test]
+    fn mouse_at_bottom_of_window_maps_to_bottom_row() {
+        let board = board_with_corner_bomb();
+        let window = test_window(&board);
+
+        let tile = board.mouse_to_tile(&window, Vec2::new(1., 29.), &CameraController::default());
+        assert_eq!(tile, Some(UVec2::new(0, 2)));
+        assert!(!board.tile_map.get_tile(tile.unwrap()).unwrap().is_bomb());
+    }
+
+    #[test]
+    fn mouse_to_tile_matches_tile_centers() {
+        let board = test_board(TileMap::empty(UVec2::new(4, 3)), 10., 2.);
+        let window = test_window(&board);
+        let camera = CameraController::default();
+        let window_center = Vec2::new(window.width(), window.height()) / 2.;
+
+        for (position, _) in board.tile_map.iter_with_pos() {
+            // Window coordinates grow downwards, world coordinates upwards
+            let center = board.tile_center(position);
+            let mouse_position = window_center + Vec2::new(center.x, -center.y);
+
+            assert_eq!(
+                board.mouse_to_tile(&window, mouse_position, &camera),
+                Some(position)
+            );
+        }
+    }
+
+    /// Covers every tile of the board like a freshly spawned game
+    fn cover_all(board: &mut Board) {
+        for (index, tile) in board.tile_map.iter_mut().enumerate() {
+            tile.cover = Some(Entity::from_raw(index as u32));
+        }
+    }
+
+    #[test]
+    fn counts_of_covered_board() {
+        let mut board = test_board(TileMap::empty(UVec2::new(4, 3)), 10., 0.);
+        cover_all(&mut board);
+
+        assert_eq!(board.flag_count(), 0);
+        assert_eq!(board.reveal_count(), 0);
+    }
+
+    #[test]
+    fn counts_after_revealing_and_flagging() {
+        let mut board = test_board(TileMap::empty(UVec2::new(4, 3)), 10., 0.);
+        cover_all(&mut board);
+
+        for position in [UVec2::new(0, 0), UVec2::new(1, 0), UVec2::new(3, 2)] {
+            board.tile_map.get_tile_mut(position).unwrap().cover = None;
+        }
+        for position in [UVec2::new(2, 1), UVec2::new(0, 2)] {
+            board.tile_map.get_tile_mut(position).unwrap().flag =
+                Some(Entity::from_raw(100 + position.x));
+        }
+
+        assert_eq!(board.reveal_count(), 3);
+        assert_eq!(board.flag_count(), 2);
+
+        // Removing a flag and covering a tile again are counted as well
+        board.tile_map.get_tile_mut(UVec2::new(2, 1)).unwrap().flag = None;
+        board.tile_map.get_tile_mut(UVec2::new(3, 2)).unwrap().cover = Some(Entity::PLACEHOLDER);
+
+        assert_eq!(board.reveal_count(), 2);
+        assert_eq!(board.flag_count(), 1);
+    }
+
+    #[test]
+    fn counts_of_revealed_board() {
+        let board = test_board(TileMap::empty(UVec2::new(4, 3)), 10., 0.);
+
+        assert_eq!(board.reveal_count(), 12);
+        assert_eq!(board.counts().covered, 0);
+    }
+
+    proptest! {
+        #[test]
+        fn mouse_to_tile_finds_tiles_inside_board(
+            cols in 1u32..20,
+            rows in 1u32..20,
+            mouse_position in (0f32..400., 0f32..400.),
+        ) {
+            let board = test_board(TileMap::empty(UVec2::new(cols, rows)), 10., 0.);
+            let window = Window {
+                resolution: (400., 400.).into(),
+                ..default()
+            };
+            let mouse_position = Vec2::from(mouse_position);
+
+            // The board is centered in the window, its right and bottom edges are excluded
+            let top_left = (Vec2::splat(400.) - board.size) / 2.;
+            let inside = mouse_position.cmpge(top_left).all()
+                && mouse_position.cmplt(top_left + board.size).all();
+
+            let tile = board.mouse_to_tile(&window, mouse_position, &CameraController::default());
+            prop_assert_eq!(tile.is_some(), inside);
+        }
     }
 }
@@ -0,0 +1,128 @@
+use bevy::{
+    input::mouse::{MouseMotion, MouseScrollUnit, MouseWheel},
+    prelude::*,
+};
+
+use crate::AppState;
+
+use super::GameState;
+
+/// Smallest allowed zoom factor
+const MIN_ZOOM: f32 = 0.5;
+
+/// Largest allowed zoom factor
+const MAX_ZOOM: f32 = 4.;
+
+/// Zoom factor applied per scrolled line
+const ZOOM_STEP: f32 = 1.1;
+
+/// Number of pixels counted as one line when scrolling with a touchpad
+const PIXELS_PER_LINE: f32 = 16.;
+
+/// Zoom and pan of the game camera, controlled with the scroll wheel,
+/// by dragging with the middle mouse button and by pinching
+#[derive(Resource, Clone, Copy)]
+pub struct CameraController {
+    /// Magnification of the board, 1 shows the board at its normal size
+    pub zoom: f32,
+    /// World position at the center of the screen
+    pub pan: Vec2,
+}
+
+impl Default for CameraController {
+    fn default() -> Self {
+        Self {
+            zoom: 1.,
+            pan: Vec2::ZERO,
+        }
+    }
+}
+
+impl CameraController {
+    /// Translates a cursor position relative to the window center into the
+    /// same space unzoomed and unpanned, y still growing downwards
+    pub fn unproject(&self, cursor_offset: Vec2) -> Vec2 {
+        cursor_offset / self.zoom + Vec2::new(self.pan.x, -self.pan.y)
+    }
+}
+
+pub struct CameraPlugin;
+
+impl Plugin for CameraPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CameraController>()
+            .add_systems(OnExit(AppState::Game), Self::reset_camera)
+            .add_systems(
+                Update,
+                (
+                    (Self::camera_zoom_pan, Self::pinch_zoom).run_if(in_state(GameState::Playing)),
+                    Self::apply_camera.run_if(resource_changed::<CameraController>),
+                )
+                    .chain(),
+            );
+    }
+}
+
+impl CameraPlugin {
+    fn camera_zoom_pan(
+        mut mouse_wheel_evr: EventReader<MouseWheel>,
+        mut mouse_motion_evr: EventReader<MouseMotion>,
+        mouse_buttons: Res<ButtonInput<MouseButton>>,
+        mut controller: ResMut<CameraController>,
+    ) {
+        let lines = mouse_wheel_evr
+            .read()
+            .map(|event| match event.unit {
+                MouseScrollUnit::Line => event.y,
+                MouseScrollUnit::Pixel => event.y / PIXELS_PER_LINE,
+            })
+            .sum::<f32>();
+
+        if lines != 0. {
+            controller.zoom = (controller.zoom * ZOOM_STEP.powf(lines)).clamp(MIN_ZOOM, MAX_ZOOM);
+        }
+
+        let delta = mouse_motion_evr
+            .read()
+            .map(|event| event.delta)
+            .sum::<Vec2>();
+
+        if mouse_buttons.pressed(MouseButton::Middle) && delta != Vec2::ZERO {
+            // Mouse motion grows downwards, the world upwards
+            let zoom = controller.zoom;
+            controller.pan += Vec2::new(-delta.x, delta.y) / zoom;
+        }
+    }
+
+    /// Zooms by the change in distance between two fingers
+    fn pinch_zoom(touches: Res<Touches>, mut controller: ResMut<CameraController>) {
+        let mut pressed = touches.iter();
+        let (Some(first), Some(second), None) = (pressed.next(), pressed.next(), pressed.next())
+        else {
+            return;
+        };
+
+        let previous = first
+            .previous_position()
+            .distance(second.previous_position());
+        let current = first.position().distance(second.position());
+
+        if previous > 0. && current != previous {
+            controller.zoom = (controller.zoom * current / previous).clamp(MIN_ZOOM, MAX_ZOOM);
+        }
+    }
+
+    fn apply_camera(
+        controller: Res<CameraController>,
+        mut cameras: Query<(&mut OrthographicProjection, &mut Transform), With<Camera2d>>,
+    ) {
+        for (mut projection, mut transform) in cameras.iter_mut() {
+            projection.scale = 1. / controller.zoom;
+            transform.translation = controller.pan.extend(transform.translation.z);
+        }
+    }
+
+    fn reset_camera(mut controller: ResMut<CameraController>) {
+        *controller = CameraController::default();
+    }
+}
@@ -0,0 +1,171 @@
+use bevy::{prelude::*, window::CursorMoved};
+
+use crate::style::prelude::*;
+
+use super::{
+    board::Board, options::GameOptions, tile_texture, BoardInitialized, GameState, OnGameScreen,
+    TileFlagged, TileRevealed, COVER_Z, TILE_Z,
+};
+
+/// The z-index of the keyboard cursor, relative to the tiles
+const CURSOR_Z: f32 = COVER_Z + 0.5;
+
+/// Tile selected with the arrow keys, `None` until an arrow key is pressed
+#[derive(Resource, Default)]
+pub struct CursorTile(pub Option<UVec2>);
+
+impl CursorTile {
+    /// Moves the cursor one step in `direction` on a board of the given size,
+    /// the first step places it in the middle of the board
+    pub fn step(&mut self, direction: IVec2, size: UVec2, wrap: bool) {
+        let size = size.as_ivec2();
+        let position = match self.0 {
+            Some(position) => {
+                let position = position.as_ivec2() + direction;
+                if wrap {
+                    position.rem_euclid(size)
+                } else {
+                    position.clamp(IVec2::ZERO, size - 1)
+                }
+            }
+            // Start in the middle of the board
+            None => size / 2,
+        };
+        self.0 = Some(position.as_uvec2());
+    }
+}
+
+#[derive(Component)]
+pub(super) struct CursorHighlight;
+
+pub struct CursorPlugin;
+
+impl Plugin for CursorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CursorTile>()
+            .add_systems(
+                Update,
+                (
+                    Self::setup_cursor.run_if(on_event::<BoardInitialized>()),
+                    Self::handle_cursor_input,
+                    Self::hide_cursor.run_if(on_event::<CursorMoved>()),
+                    Self::move_highlight
+                        .run_if(resource_changed::<CursorTile>.or_else(resource_changed::<Board>)),
+                )
+                    .chain()
+                    .run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(
+                Update,
+                Self::recolor_cursor.run_if(resource_changed::<GameAssets>),
+            );
+    }
+}
+
+impl CursorPlugin {
+    fn setup_cursor(
+        mut commands: Commands,
+        mut cursor_tile: ResMut<CursorTile>,
+        board: Res<Board>,
+        game_assets: Res<GameAssets>,
+    ) {
+        cursor_tile.0 = None;
+
+        commands.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: game_assets.tile_cursor.with_a(0.5),
+                    custom_size: Some(Vec2::splat(board.tile_size)),
+                    ..Default::default()
+                },
+                texture: tile_texture(board.tile_map.shape(), &game_assets),
+                transform: Transform::from_translation(
+                    board.tile_center(UVec2::ZERO).extend(TILE_Z + CURSOR_Z),
+                ),
+                visibility: Visibility::Hidden,
+                ..Default::default()
+            },
+            CursorHighlight,
+            OnGameScreen,
+        ));
+    }
+
+    fn handle_cursor_input(
+        keyboard_input: Res<ButtonInput<KeyCode>>,
+        mut cursor_tile: ResMut<CursorTile>,
+        mut highlight: Query<&mut Visibility, With<CursorHighlight>>,
+        board: Res<Board>,
+        game_options: Res<GameOptions>,
+        mut tile_revealed_evw: EventWriter<TileRevealed>,
+        mut tile_flagged_evw: EventWriter<TileFlagged>,
+    ) {
+        let direction = [
+            (KeyCode::ArrowLeft, IVec2::new(-1, 0)),
+            (KeyCode::ArrowRight, IVec2::new(1, 0)),
+            (KeyCode::ArrowUp, IVec2::new(0, -1)),
+            (KeyCode::ArrowDown, IVec2::new(0, 1)),
+        ]
+        .into_iter()
+        .filter(|(key, _)| keyboard_input.just_pressed(*key))
+        .map(|(_, direction)| direction)
+        .sum::<IVec2>();
+
+        if keyboard_input.any_just_pressed([
+            KeyCode::ArrowLeft,
+            KeyCode::ArrowRight,
+            KeyCode::ArrowUp,
+            KeyCode::ArrowDown,
+        ]) {
+            cursor_tile.step(direction, board.tile_map.size(), game_options.wrap_cursor);
+
+            for mut visibility in highlight.iter_mut() {
+                *visibility = Visibility::Visible;
+            }
+        }
+
+        let Some(position) = cursor_tile.0 else {
+            return;
+        };
+
+        if keyboard_input.just_pressed(KeyCode::Space) {
+            tile_revealed_evw.send(TileRevealed { position });
+        }
+
+        if keyboard_input.just_pressed(KeyCode::KeyF) {
+            tile_flagged_evw.send(TileFlagged { position });
+        }
+    }
+
+    /// Hides the cursor while the mouse is used, it is shown
+    /// again once an arrow key is pressed
+    fn hide_cursor(mut highlight: Query<&mut Visibility, With<CursorHighlight>>) {
+        for mut visibility in highlight.iter_mut() {
+            *visibility = Visibility::Hidden;
+        }
+    }
+
+    fn move_highlight(
+        cursor_tile: Res<CursorTile>,
+        board: Res<Board>,
+        mut highlight: Query<(&mut Transform, &mut Sprite), With<CursorHighlight>>,
+    ) {
+        let Some(position) = cursor_tile.0 else {
+            return;
+        };
+
+        // The board changes size when the window is resized
+        for (mut transform, mut sprite) in highlight.iter_mut() {
+            transform.translation = board.tile_center(position).extend(transform.translation.z);
+            sprite.custom_size = Some(Vec2::splat(board.tile_size));
+        }
+    }
+
+    fn recolor_cursor(
+        mut highlight: Query<&mut Sprite, With<CursorHighlight>>,
+        game_assets: Res<GameAssets>,
+    ) {
+        for mut sprite in highlight.iter_mut() {
+            sprite.color = game_assets.tile_cursor.with_a(0.5);
+        }
+    }
+}
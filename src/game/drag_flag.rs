@@ -0,0 +1,159 @@
+use bevy::{prelude::*, window::CursorMoved};
+
+use crate::style::prelude::*;
+
+use super::{
+    board::Board, camera::CameraController, BoardInitialized, GameState, OnGameScreen, TileFlagged,
+};
+
+/// Whether dragging over covered tiles with the left mouse button flags them,
+/// for trackpads and touch screens without a right button
+#[derive(Resource, Default, PartialEq)]
+pub struct DragFlagMode(pub bool);
+
+#[derive(Component)]
+struct DragFlagButton;
+
+pub struct DragFlagPlugin;
+
+impl Plugin for DragFlagPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DragFlagMode>().add_systems(
+            Update,
+            (
+                Self::setup_button.run_if(on_event::<BoardInitialized>()),
+                Self::button_action,
+                Self::button_color.run_if(resource_changed::<DragFlagMode>),
+                Self::drag_flag.run_if(resource_equals(DragFlagMode(true))),
+            )
+                .chain()
+                .run_if(in_state(GameState::Playing)),
+        );
+    }
+}
+
+impl DragFlagPlugin {
+    fn setup_button(
+        mut commands: Commands,
+        drag_flag_mode: Res<DragFlagMode>,
+        ui_assets: Res<UiAssets>,
+    ) {
+        commands
+            .spawn((
+                NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        top: Val::Px(10.),
+                        width: Val::Percent(100.),
+                        justify_content: JustifyContent::Center,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                OnGameScreen,
+            ))
+            .with_children(|parent| {
+                parent
+                    .spawn((
+                        ButtonBundle {
+                            style: Style {
+                                width: Val::Px(200.),
+                                height: Val::Px(40.),
+                                justify_content: JustifyContent::Center,
+                                align_items: AlignItems::Center,
+                                ..Default::default()
+                            },
+                            background_color: button_color(drag_flag_mode.0, &ui_assets),
+                            ..Default::default()
+                        },
+                        DragFlagButton,
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn(TextBundle::from_section(
+                            "Drag flag",
+                            TextStyle {
+                                font_size: 25.,
+                                color: ui_assets.foreground,
+                                font: ui_assets.font.clone(),
+                            },
+                        ));
+                    });
+            });
+    }
+
+    fn button_action(
+        interactions: Query<&Interaction, (Changed<Interaction>, With<DragFlagButton>)>,
+        mut drag_flag_mode: ResMut<DragFlagMode>,
+    ) {
+        for interaction in interactions.iter() {
+            if *interaction == Interaction::Pressed {
+                drag_flag_mode.0 = !drag_flag_mode.0;
+            }
+        }
+    }
+
+    fn button_color(
+        drag_flag_mode: Res<DragFlagMode>,
+        mut buttons: Query<&mut BackgroundColor, With<DragFlagButton>>,
+        ui_assets: Res<UiAssets>,
+    ) {
+        for mut color in buttons.iter_mut() {
+            *color = button_color(drag_flag_mode.0, &ui_assets);
+        }
+    }
+
+    /// Flags every covered tile the mouse enters while the left button is held,
+    /// the tile the drag started at is flagged by the mouse input handling
+    fn drag_flag(
+        mut cursor_moved_evr: EventReader<CursorMoved>,
+        mut last_hovered_tile: Local<Option<UVec2>>,
+        window: Query<&Window>,
+        board: Res<Board>,
+        camera_controller: Res<CameraController>,
+        mouse_buttons: Res<ButtonInput<MouseButton>>,
+        mut tile_flagged_evw: EventWriter<TileFlagged>,
+    ) {
+        let window = window.single();
+
+        if mouse_buttons.just_pressed(MouseButton::Left) {
+            *last_hovered_tile = window
+                .cursor_position()
+                .and_then(|cursor| board.mouse_to_tile(window, cursor, &camera_controller));
+        }
+
+        if !mouse_buttons.pressed(MouseButton::Left) {
+            *last_hovered_tile = None;
+            cursor_moved_evr.clear();
+            return;
+        }
+
+        for event in cursor_moved_evr.read() {
+            let hovered_tile = board.mouse_to_tile(window, event.position, &camera_controller);
+            if hovered_tile == *last_hovered_tile {
+                continue;
+            }
+            *last_hovered_tile = hovered_tile;
+
+            let Some(position) = hovered_tile else {
+                continue;
+            };
+
+            // Flagging a flagged tile would turn it into a question mark
+            if board
+                .tile_map
+                .get_tile(position)
+                .is_some_and(|tile| tile.cover.is_some() && tile.flag.is_none())
+            {
+                tile_flagged_evw.send(TileFlagged { position });
+            }
+        }
+    }
+}
+
+fn button_color(active: bool, ui_assets: &UiAssets) -> BackgroundColor {
+    if active {
+        ui_assets.accent.into()
+    } else {
+        ui_assets.background_alt.into()
+    }
+}
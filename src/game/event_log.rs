@@ -0,0 +1,80 @@
+use std::fs;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::util::data_file;
+
+use super::{hud::GameTimer, GameState};
+
+/// File name in the data directory the log of the last finished game is exported to
+const EVENT_LOG_FILE: &str = "minesweeper_last_game.json";
+
+/// Mirrors the tile events, along with the tile they happened on
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+pub enum GameEventKind {
+    Revealed(UVec2),
+    Flagged(UVec2),
+    Chord(UVec2),
+    Questioned(UVec2),
+}
+
+/// Tile event of the current game
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct GameEvent {
+    /// Seconds since the game started
+    pub timestamp: f32,
+    pub kind: GameEventKind,
+}
+
+/// Every tile event of the current game in the order they were handled.
+/// Unlike the events themselves the log is kept until the next game starts,
+/// and it is exported once the game has finished
+#[derive(Resource, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct GameEventLog(pub Vec<GameEvent>);
+
+impl GameEventLog {
+    /// Appends an event timestamped with the game timer, events before
+    /// the timer exists are placed at the start of the game
+    pub fn record(&mut self, game_timer: Option<&GameTimer>, kind: GameEventKind) {
+        self.0.push(GameEvent {
+            timestamp: game_timer.map_or(0., |game_timer| game_timer.0.as_secs_f32()),
+            kind,
+        });
+    }
+
+    /// Returns the tile of the most recent reveal
+    pub fn last_reveal(&self) -> Option<UVec2> {
+        self.0.iter().rev().find_map(|event| match event.kind {
+            GameEventKind::Revealed(position) => Some(position),
+            _ => None,
+        })
+    }
+
+    pub fn save(&self) -> std::io::Result<()> {
+        let path = data_file(EVENT_LOG_FILE);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        fs::write(path, serde_json::to_string_pretty(self)?)
+    }
+}
+
+pub struct EventLogPlugin;
+
+impl Plugin for EventLogPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GameEventLog>()
+            .add_systems(OnEnter(GameState::Finished), Self::export_event_log);
+    }
+}
+
+impl EventLogPlugin {
+    fn export_event_log(event_log: Res<GameEventLog>) {
+        if let Err(err) = event_log.save() {
+            warn!("Could not export the game event log: {err}");
+        }
+    }
+}
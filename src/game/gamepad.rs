@@ -0,0 +1,172 @@
+use bevy::{
+    input::{
+        gamepad::{GamepadAxisChangedEvent, GamepadButtonInput},
+        ButtonState,
+    },
+    prelude::*,
+};
+
+use super::{
+    board::Board,
+    cursor::{CursorHighlight, CursorTile},
+    options::GameOptions,
+    GameState, TileFlagged, TileRevealed,
+};
+
+/// Seconds a direction has to be held before the cursor starts repeating
+const REPEAT_DELAY: f32 = 0.3;
+
+/// Seconds between cursor steps while a direction is held
+const REPEAT_INTERVAL: f32 = 0.08;
+
+/// Gamepad preferences
+#[derive(Resource)]
+pub struct GamepadCursorSettings {
+    /// Stick deflection below which the left stick is ignored
+    pub dead_zone: f32,
+}
+
+impl Default for GamepadCursorSettings {
+    fn default() -> Self {
+        Self { dead_zone: 0.5 }
+    }
+}
+
+/// Direction the cursor is being moved in by the gamepad
+#[derive(Resource, Default)]
+struct GamepadCursor {
+    /// Position of the left stick, y growing upwards
+    stick: Vec2,
+    /// Direction held during the last frame
+    held: IVec2,
+    /// Elapsed time in seconds at which the cursor moves again
+    next_step: f32,
+}
+
+pub struct GamepadPlugin;
+
+impl Plugin for GamepadPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GamepadCursorSettings>()
+            .init_resource::<GamepadCursor>()
+            .add_systems(
+                Update,
+                (Self::read_stick, Self::move_cursor, Self::handle_buttons)
+                    .chain()
+                    .run_if(in_state(GameState::Playing).and_then(any_gamepad_connected)),
+            );
+    }
+}
+
+impl GamepadPlugin {
+    fn read_stick(
+        mut axis_changed_evr: EventReader<GamepadAxisChangedEvent>,
+        mut gamepad_cursor: ResMut<GamepadCursor>,
+    ) {
+        for event in axis_changed_evr.read() {
+            match event.axis_type {
+                GamepadAxisType::LeftStickX => gamepad_cursor.stick.x = event.value,
+                GamepadAxisType::LeftStickY => gamepad_cursor.stick.y = event.value,
+                _ => {}
+            }
+        }
+    }
+
+    /// Moves the cursor while the d-pad or left stick is held, the first step is
+    /// immediate and holding the direction repeats it after a delay
+    #[allow(clippy::too_many_arguments)]
+    fn move_cursor(
+        gamepad_buttons: Res<ButtonInput<GamepadButton>>,
+        settings: Res<GamepadCursorSettings>,
+        time: Res<Time>,
+        mut gamepad_cursor: ResMut<GamepadCursor>,
+        mut cursor_tile: ResMut<CursorTile>,
+        mut highlight: Query<&mut Visibility, With<CursorHighlight>>,
+        board: Res<Board>,
+        game_options: Res<GameOptions>,
+    ) {
+        let dpad = [
+            (GamepadButtonType::DPadLeft, IVec2::new(-1, 0)),
+            (GamepadButtonType::DPadRight, IVec2::new(1, 0)),
+            (GamepadButtonType::DPadUp, IVec2::new(0, -1)),
+            (GamepadButtonType::DPadDown, IVec2::new(0, 1)),
+        ]
+        .into_iter()
+        .filter(|(button_type, _)| {
+            gamepad_buttons
+                .get_pressed()
+                .any(|button| button.button_type == *button_type)
+        })
+        .map(|(_, direction)| direction)
+        .sum::<IVec2>();
+
+        // Rows grow downwards, the stick upwards
+        let stick = gamepad_cursor.stick;
+        let stick = IVec2::new(
+            dead_zone_sign(stick.x, settings.dead_zone),
+            -dead_zone_sign(stick.y, settings.dead_zone),
+        );
+
+        let direction = (dpad + stick).clamp(IVec2::NEG_ONE, IVec2::ONE);
+        let now = time.elapsed_seconds();
+
+        let step = if direction != gamepad_cursor.held {
+            gamepad_cursor.held = direction;
+            gamepad_cursor.next_step = now + REPEAT_DELAY;
+            direction != IVec2::ZERO
+        } else if direction != IVec2::ZERO && now >= gamepad_cursor.next_step {
+            gamepad_cursor.next_step = now + REPEAT_INTERVAL;
+            true
+        } else {
+            false
+        };
+
+        if !step {
+            return;
+        }
+
+        cursor_tile.step(direction, board.tile_map.size(), game_options.wrap_cursor);
+
+        for mut visibility in highlight.iter_mut() {
+            *visibility = Visibility::Visible;
+        }
+    }
+
+    fn handle_buttons(
+        mut button_input_evr: EventReader<GamepadButtonInput>,
+        cursor_tile: Res<CursorTile>,
+        mut game_state: ResMut<NextState<GameState>>,
+        mut tile_revealed_evw: EventWriter<TileRevealed>,
+        mut tile_flagged_evw: EventWriter<TileFlagged>,
+    ) {
+        for event in button_input_evr.read() {
+            if event.state != ButtonState::Pressed {
+                continue;
+            }
+
+            match (event.button.button_type, cursor_tile.0) {
+                (GamepadButtonType::East, _) => game_state.set(GameState::Paused),
+                (GamepadButtonType::South, Some(position)) => {
+                    tile_revealed_evw.send(TileRevealed { position });
+                }
+                (GamepadButtonType::West, Some(position)) => {
+                    tile_flagged_evw.send(TileFlagged { position });
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+fn any_gamepad_connected(gamepads: Res<Gamepads>) -> bool {
+    gamepads.iter().next().is_some()
+}
+
+/// Returns the direction of a stick axis, 0 while it is within the dead zone
+fn dead_zone_sign(value: f32, dead_zone: f32) -> i32 {
+    if value.abs() < dead_zone {
+        0
+    } else {
+        value.signum() as i32
+    }
+}
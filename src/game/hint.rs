@@ -0,0 +1,91 @@
+use bevy::prelude::*;
+use rand::{seq::IteratorRandom as _, thread_rng};
+
+use crate::{style::prelude::*, util::lerp_color};
+
+use super::{board::Board, event_log::GameEventLog, Cover, GameState, Position, SafeStartCover};
+
+/// Number of pulses per second of a hinted cover
+const HINT_PULSE_FREQUENCY: f32 = 1.5;
+
+/// Pulses the cover of a tile that can be revealed safely,
+/// holds the time since the hint was shown
+#[derive(Component, Default)]
+pub(super) struct TileHint(f32);
+
+pub struct HintPlugin;
+
+impl Plugin for HintPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                Self::handle_hint_input.run_if(in_state(GameState::Playing)),
+                // The solver highlights its next move with a hint while spectating
+                Self::animate_hint
+                    .run_if(in_state(GameState::Playing).or_else(in_state(GameState::Spectating))),
+            )
+                .chain(),
+        );
+    }
+}
+
+impl HintPlugin {
+    /// Shows a hint when pressing `H`, pressing again moves the hint
+    /// to another safe tile or removes it if there is none.
+    /// The safe tile closest to the last reveal is hinted first
+    fn handle_hint_input(
+        mut commands: Commands,
+        keyboard_input: Res<ButtonInput<KeyCode>>,
+        board: Res<Board>,
+        event_log: Res<GameEventLog>,
+        mut hinted: Query<(Entity, &Position, &mut Sprite, Has<SafeStartCover>), With<TileHint>>,
+        game_assets: Res<GameAssets>,
+    ) {
+        if !keyboard_input.just_pressed(KeyCode::KeyH) {
+            return;
+        }
+
+        let mut current = None;
+        for (entity, position, mut sprite, safe_start) in hinted.iter_mut() {
+            commands.entity(entity).remove::<TileHint>();
+            sprite.color = if safe_start {
+                game_assets.tile_uncovered
+            } else {
+                game_assets.tile_covered
+            };
+            current = Some(position.0);
+        }
+
+        let candidates = board
+            .tile_map
+            .deducible_safe_tiles()
+            .into_iter()
+            .filter(|&position| Some(position.as_ivec2()) != current);
+        let next = match event_log.last_reveal() {
+            Some(last_reveal) => candidates.min_by_key(|position| {
+                (position.as_ivec2() - last_reveal.as_ivec2()).length_squared()
+            }),
+            None => candidates.choose(&mut thread_rng()),
+        };
+
+        let Some(cover) = next.and_then(|position| board.tile_map.get_tile(position)?.cover) else {
+            return;
+        };
+
+        commands.entity(cover).insert(TileHint::default());
+    }
+
+    fn animate_hint(
+        time: Res<Time>,
+        mut hinted: Query<(&mut TileHint, &mut Sprite), With<Cover>>,
+        game_assets: Res<GameAssets>,
+    ) {
+        for (mut hint, mut sprite) in hinted.iter_mut() {
+            hint.0 += time.delta_seconds();
+
+            let t = (1. - (hint.0 * HINT_PULSE_FREQUENCY * std::f32::consts::TAU).cos()) / 2.;
+            sprite.color = lerp_color(game_assets.tile_covered, game_assets.tile_hint, t);
+        }
+    }
+}
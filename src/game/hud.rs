@@ -0,0 +1,293 @@
+use std::time::Duration;
+
+use bevy::{prelude::*, sprite::Anchor};
+
+use crate::style::prelude::*;
+
+use super::{
+    board::{Board, BoardCounts},
+    options::GameOptions,
+    BoardInitialized, GameState, OnGameScreen, FLAG_Z,
+};
+
+/// Height reserved above and below the board for the HUD
+pub const HUD_HEIGHT: f32 = 80.;
+
+/// Seconds available to clear the board in rush mode
+const RUSH_DURATION: f32 = 180.;
+
+/// Seconds left below which the countdown flashes
+const COUNTDOWN_WARNING: f32 = 10.;
+
+/// Flashes of the countdown per second once time is running out
+const COUNTDOWN_FLASH_RATE: f32 = 2.;
+
+/// Heart icon of the Nerd Font used by the HUD
+const HEART_GLYPH: &str = "\u{f004}";
+
+/// Time spent playing the current game
+#[derive(Resource, Default)]
+pub struct GameTimer(pub Duration);
+
+/// Time left to clear the board in rush mode, shown instead of the game timer.
+/// The game is lost once it finishes
+#[derive(Resource)]
+pub struct CountdownTimer(pub Timer);
+
+impl Default for CountdownTimer {
+    fn default() -> Self {
+        Self(Timer::from_seconds(RUSH_DURATION, TimerMode::Once))
+    }
+}
+
+/// Number of bombs minus the number of placed flags and revealed bombs,
+/// can become negative when placing too many flags
+#[derive(Resource)]
+pub struct MineCounter(pub i32);
+
+impl MineCounter {
+    fn new(bomb_total: u32, board_counts: &BoardCounts) -> Self {
+        Self(bomb_total as i32 - (board_counts.flagged + board_counts.revealed_mines) as i32)
+    }
+}
+
+#[derive(Component)]
+struct TimerText;
+
+#[derive(Component)]
+struct MineCounterText;
+
+/// Quality score of the board, shown below the board while debugging
+#[cfg(feature = "debug")]
+#[derive(Component)]
+struct QualityScoreText;
+
+/// One of the lives left, the hearts are numbered from left to right
+#[derive(Component)]
+struct Heart(u8);
+
+pub struct HudPlugin;
+
+impl Plugin for HudPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                Self::setup_hud.run_if(on_event::<BoardInitialized>()),
+                Self::update_timer.run_if(resource_exists::<GameTimer>),
+                Self::count_remaining_mines.run_if(
+                    resource_exists::<MineCounter>.and_then(resource_changed::<BoardCounts>),
+                ),
+                Self::update_mine_counter.run_if(resource_exists_and_changed::<MineCounter>),
+                Self::flash_countdown_timer.run_if(resource_exists::<CountdownTimer>),
+                Self::update_hearts.run_if(resource_changed::<BoardCounts>),
+            )
+                .chain()
+                .run_if(in_state(GameState::Playing).or_else(in_state(GameState::Spectating))),
+        );
+
+        #[cfg(feature = "debug")]
+        app.add_systems(
+            Update,
+            Self::update_quality_score
+                .after(Self::setup_hud)
+                .run_if(resource_changed::<Board>.and_then(in_state(GameState::Playing))),
+        );
+    }
+}
+
+impl HudPlugin {
+    fn setup_hud(
+        mut commands: Commands,
+        board: Res<Board>,
+        board_counts: Res<BoardCounts>,
+        game_options: Res<GameOptions>,
+        ui_assets: Res<UiAssets>,
+    ) {
+        let text_style = TextStyle {
+            font: ui_assets.font.clone(),
+            font_size: HUD_HEIGHT / 2.5,
+            color: ui_assets.foreground,
+        };
+        let top_left = Vec2::new(board.position.x, board.position.y + board.size.y);
+        let top_right = top_left + Vec2::new(board.size.x, 0.);
+
+        // A new board starts a new count, this also resets it on restarts
+        let mine_counter = MineCounter::new(bomb_total(&board, &game_options), &board_counts);
+        commands.spawn((
+            Text2dBundle {
+                text: Text::from_section(mine_counter.0.max(0).to_string(), text_style.clone()),
+                text_anchor: Anchor::BottomRight,
+                transform: Transform::from_translation(top_right.extend(FLAG_Z)),
+                ..Default::default()
+            },
+            MineCounterText,
+            OnGameScreen,
+        ));
+        commands.insert_resource(mine_counter);
+
+        commands.spawn((
+            Text2dBundle {
+                text: Text::from_section(format_time(Duration::ZERO), text_style.clone()),
+                text_anchor: Anchor::BottomLeft,
+                transform: Transform::from_translation(top_left.extend(FLAG_Z)),
+                ..Default::default()
+            },
+            TimerText,
+            OnGameScreen,
+        ));
+
+        #[cfg(feature = "debug")]
+        commands.spawn((
+            Text2dBundle {
+                text: Text::from_section(
+                    format!("Quality: {:.2}", board.board_quality_score),
+                    TextStyle {
+                        font_size: HUD_HEIGHT / 4.,
+                        ..text_style.clone()
+                    },
+                ),
+                text_anchor: Anchor::TopLeft,
+                transform: Transform::from_translation(board.position.xy().extend(FLAG_Z)),
+                ..Default::default()
+            },
+            QualityScoreText,
+            OnGameScreen,
+        ));
+
+        // Classic games have a single life, showing it would not tell anything
+        if board.lives > 1 {
+            let top_center = top_left + Vec2::new(board.size.x / 2., 0.);
+            let spacing = HUD_HEIGHT / 2.;
+            let first_offset = (board.lives - 1) as f32 * -spacing / 2.;
+
+            for index in 0..board.lives {
+                let position = top_center + Vec2::new(first_offset + index as f32 * spacing, 0.);
+                commands.spawn((
+                    Text2dBundle {
+                        text: Text::from_section(
+                            HEART_GLYPH,
+                            TextStyle {
+                                color: Color::RED,
+                                ..text_style.clone()
+                            },
+                        ),
+                        text_anchor: Anchor::BottomCenter,
+                        transform: Transform::from_translation(position.extend(FLAG_Z)),
+                        ..Default::default()
+                    },
+                    Heart(index),
+                    OnGameScreen,
+                ));
+            }
+        }
+    }
+
+    /// Only runs while playing, so neither timer advances while the game is paused
+    fn update_timer(
+        time: Res<Time>,
+        mut game_timer: ResMut<GameTimer>,
+        countdown_timer: Option<ResMut<CountdownTimer>>,
+        mut timer_text: Query<&mut Text, With<TimerText>>,
+    ) {
+        game_timer.0 += time.delta();
+
+        let shown_time = match countdown_timer {
+            Some(mut countdown_timer) => {
+                countdown_timer.0.tick(time.delta());
+                // Round up so the countdown shows 00:00 only once time is up
+                Duration::from_secs_f32(countdown_timer.0.remaining_secs().ceil())
+            }
+            None => game_timer.0,
+        };
+
+        for mut text in timer_text.iter_mut() {
+            text.sections[0].value = format_time(shown_time);
+        }
+    }
+
+    /// Turns the countdown red and makes it blink once time is running out
+    fn flash_countdown_timer(
+        time: Res<Time>,
+        countdown_timer: Res<CountdownTimer>,
+        ui_assets: Res<UiAssets>,
+        mut timer_text: Query<&mut Text, With<TimerText>>,
+    ) {
+        let color = if countdown_timer.0.remaining_secs() < COUNTDOWN_WARNING {
+            let phase = (time.elapsed_seconds() * COUNTDOWN_FLASH_RATE).fract();
+            Color::RED.with_a(if phase < 0.5 { 1. } else { 0.3 })
+        } else {
+            ui_assets.foreground
+        };
+
+        for mut text in timer_text.iter_mut() {
+            text.sections[0].style.color = color;
+        }
+    }
+
+    /// Bombs of a game without a safe start are only placed on the first reveal
+    #[cfg(feature = "debug")]
+    fn update_quality_score(
+        board: Res<Board>,
+        mut quality_texts: Query<&mut Text, With<QualityScoreText>>,
+    ) {
+        for mut text in quality_texts.iter_mut() {
+            text.sections[0].value = format!("Quality: {:.2}", board.board_quality_score);
+        }
+    }
+
+    /// Removes the hearts of the lives lost, starting from the right
+    fn update_hearts(
+        mut commands: Commands,
+        board: Res<Board>,
+        board_counts: Res<BoardCounts>,
+        hearts: Query<(Entity, &Heart)>,
+    ) {
+        let lives_left = board_counts.lives_left(board.lives);
+        for (entity, heart) in hearts.iter() {
+            if heart.0 >= lives_left {
+                commands.entity(entity).despawn_recursive();
+            }
+        }
+    }
+
+    /// Keeps the `MineCounter` in line with the flags and revealed bombs of the board
+    fn count_remaining_mines(
+        board: Res<Board>,
+        board_counts: Res<BoardCounts>,
+        game_options: Res<GameOptions>,
+        mut mine_counter: ResMut<MineCounter>,
+    ) {
+        let counted = MineCounter::new(bomb_total(&board, &game_options), &board_counts);
+        if mine_counter.0 != counted.0 {
+            *mine_counter = counted;
+        }
+    }
+
+    /// Shows the `MineCounter`, it stays at zero when placing too many flags
+    fn update_mine_counter(
+        mine_counter: Res<MineCounter>,
+        mut counter_text: Query<&mut Text, With<MineCounterText>>,
+    ) {
+        for mut text in counter_text.iter_mut() {
+            text.sections[0].value = mine_counter.0.max(0).to_string();
+        }
+    }
+}
+
+/// Returns the number of bombs on the board, shared and saved boards can differ
+/// from the options. Without a safe start the bombs are only placed on the
+/// first reveal, until then the board will get the bomb count of the options
+fn bomb_total(board: &Board, game_options: &GameOptions) -> u32 {
+    if board.tile_map.is_generated() {
+        board.tile_map.total_bombs()
+    } else {
+        game_options.bomb_count
+    }
+}
+
+/// Formats a duration as minutes and seconds
+pub(super) fn format_time(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    format!("{:02}:{:02}", seconds / 60, seconds % 60)
+}
@@ -0,0 +1,176 @@
+use std::collections::HashSet;
+
+use bevy::{prelude::*, window::WindowResized};
+
+use crate::style::prelude::*;
+
+use super::{
+    board::Board, camera::CameraController, tilemap::Tile, BoardInitialized, GameState,
+    OnGameScreen,
+};
+
+/// Boards need more tiles than this in either direction to get a minimap
+const MINIMAP_MIN_SIZE: UVec2 = UVec2::new(20, 20);
+
+/// Width of the minimap in pixels, the height follows the board
+const MINIMAP_WIDTH: f32 = 200.;
+
+/// Distance between the minimap and the corner of the window
+const MINIMAP_MARGIN: f32 = 10.;
+
+/// Overview of the whole board in the top right corner, one pixel per tile,
+/// toggled with M
+#[derive(Component)]
+struct Minimap;
+
+/// Outline of the part of the board visible through the camera
+#[derive(Component)]
+struct ViewportOutline;
+
+pub struct MinimapPlugin;
+
+impl Plugin for MinimapPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                Self::setup_minimap.run_if(on_event::<BoardInitialized>()),
+                Self::toggle_minimap,
+                Self::update_minimap.run_if(resource_changed::<Board>),
+                Self::update_viewport.run_if(
+                    resource_changed::<CameraController>
+                        .or_else(on_event::<BoardInitialized>())
+                        .or_else(on_event::<WindowResized>()),
+                ),
+            )
+                .chain()
+                .run_if(in_state(GameState::Playing).or_else(in_state(GameState::Spectating))),
+        );
+    }
+}
+
+impl MinimapPlugin {
+    fn setup_minimap(
+        mut commands: Commands,
+        board: Res<Board>,
+        game_assets: Res<GameAssets>,
+        mut images: ResMut<Assets<Image>>,
+    ) {
+        let size = board.tile_map.size();
+        if !size.cmpgt(MINIMAP_MIN_SIZE).any() {
+            return;
+        }
+
+        let image = images.add(board.tile_map.render_minimap(
+            &HashSet::new(),
+            &HashSet::new(),
+            &game_assets,
+        ));
+
+        commands
+            .spawn((
+                ImageBundle {
+                    image: UiImage::new(image),
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        top: Val::Px(MINIMAP_MARGIN),
+                        right: Val::Px(MINIMAP_MARGIN),
+                        width: Val::Px(MINIMAP_WIDTH),
+                        height: Val::Px(MINIMAP_WIDTH * size.y as f32 / size.x as f32),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                Minimap,
+                OnGameScreen,
+            ))
+            .with_children(|parent| {
+                parent.spawn((
+                    NodeBundle {
+                        style: Style {
+                            position_type: PositionType::Absolute,
+                            border: UiRect::all(Val::Px(1.)),
+                            ..Default::default()
+                        },
+                        border_color: Color::WHITE.into(),
+                        ..Default::default()
+                    },
+                    ViewportOutline,
+                ));
+            });
+    }
+
+    fn toggle_minimap(
+        keyboard_input: Res<ButtonInput<KeyCode>>,
+        mut minimaps: Query<&mut Visibility, With<Minimap>>,
+    ) {
+        if !keyboard_input.just_pressed(KeyCode::KeyM) {
+            return;
+        }
+
+        for mut visibility in minimaps.iter_mut() {
+            *visibility = match *visibility {
+                Visibility::Hidden => Visibility::Inherited,
+                _ => Visibility::Hidden,
+            };
+        }
+    }
+
+    /// Redraws the minimap into its existing image whenever tiles are revealed or marked,
+    /// the whole image is replaced as expanding the board changes its size
+    fn update_minimap(
+        board: Res<Board>,
+        game_assets: Res<GameAssets>,
+        minimaps: Query<&UiImage, With<Minimap>>,
+        mut images: ResMut<Assets<Image>>,
+    ) {
+        let Ok(ui_image) = minimaps.get_single() else {
+            return;
+        };
+        let Some(image) = images.get_mut(&ui_image.texture) else {
+            return;
+        };
+
+        let positions = |predicate: fn(&Tile) -> bool| {
+            board
+                .tile_map
+                .iter_with_pos()
+                .filter(|(_, tile)| predicate(tile))
+                .map(|(position, _)| position)
+                .collect::<HashSet<_>>()
+        };
+        let revealed = positions(|tile| tile.cover.is_none());
+        let flagged = positions(|tile| tile.flag.is_some());
+
+        *image = board
+            .tile_map
+            .render_minimap(&revealed, &flagged, &game_assets);
+    }
+
+    /// Places the outline over the part of the minimap the camera shows
+    fn update_viewport(
+        window: Query<&Window>,
+        board: Res<Board>,
+        camera_controller: Res<CameraController>,
+        mut outlines: Query<&mut Style, With<ViewportOutline>>,
+    ) {
+        let window = window.single();
+        let half_view = Vec2::new(window.width(), window.height()) / 2. / camera_controller.zoom;
+        let min = camera_controller.pan - half_view;
+        let max = camera_controller.pan + half_view;
+
+        // Fractions of the board measured from its top left corner like the minimap
+        let board_top = board.position.y + board.size.y;
+        let left = ((min.x - board.position.x) / board.size.x).clamp(0., 1.);
+        let right = ((max.x - board.position.x) / board.size.x).clamp(0., 1.);
+        let top = ((board_top - max.y) / board.size.y).clamp(0., 1.);
+        let bottom = ((board_top - min.y) / board.size.y).clamp(0., 1.);
+
+        for mut style in outlines.iter_mut() {
+            style.left = Val::Percent(left * 100.);
+            style.top = Val::Percent(top * 100.);
+            style.width = Val::Percent((right - left) * 100.);
+            style.height = Val::Percent((bottom - top) * 100.);
+        }
+    }
+}
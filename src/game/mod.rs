@@ -1,37 +1,79 @@
+mod accessibility;
 mod board;
-mod options;
-mod tilemap;
-
-use std::collections::VecDeque;
+mod camera;
+mod cursor;
+mod drag_flag;
+mod event_log;
+mod gamepad;
+mod hint;
+mod hud;
+mod minimap;
+pub mod options;
+mod particles;
+pub mod presets;
+#[cfg(feature = "debug")]
+mod probability_overlay;
+mod region_select;
+mod replay;
+#[cfg(feature = "debug")]
+mod reveal_throughput;
+pub mod saved_game;
+pub mod scores;
+mod screenshot;
+#[cfg(feature = "audio")]
+pub mod sound;
+mod spectate;
+pub mod stats;
+pub mod tilemap;
+mod tooltip;
+mod touch;
+mod versus;
+
+use std::f32::consts::TAU;
 
 use crate::{
-    style::{game_assets::GameAssets, ui_assets::UiAssets},
-    util::despawn_all,
+    display::DisplaySettings,
+    style::{game_assets::TileSpriteIndex, prelude::*},
+    util::{despawn_all, despawn_all_except, despawn_with_filter, ease_out_back, lerp_color},
     AppState,
 };
 
-use board::Board;
+use board::{Board, BoardCounts};
 
 #[cfg(feature = "debug")]
 use bevy::log;
+#[cfg(feature = "debug")]
+use reveal_throughput::RevealThroughput;
 
 use bevy::{
     input::{keyboard::KeyboardInput, mouse::MouseButtonInput, ButtonState},
     prelude::*,
-    utils::HashSet,
+    tasks::{block_on, futures_lite::future, AsyncComputeTaskPool, Task},
+    window::WindowResized,
 };
-use options::GameOptions;
+use options::{GameMode, GameOptions};
+use rand::{thread_rng, Rng as _};
 
 use self::{
+    camera::CameraController,
+    event_log::{GameEventKind, GameEventLog},
+    hud::{CountdownTimer, GameTimer},
     options::TileSize,
-    tilemap::{TileMap, TileType},
+    replay::{Replay, ReplayEvent},
+    saved_game::SavedGame,
+    tilemap::{GridShape, ShareCodeError, TileMap, TileMapError, TileType},
 };
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, States)]
 enum GameState {
     Options,
+    /// Bombs are being placed in the background before the game starts
+    Generating,
     Playing,
     Paused,
+    /// The solver finishes the game while the player watches
+    Spectating,
+    Animating,
     Finished,
     #[default]
     Inactive,
@@ -46,8 +88,10 @@ struct OnPauseScreen;
 #[derive(Component)]
 struct OnFinishedScreen;
 
+/// Position of a tile on the board, signed so offset coordinates of
+/// hexagonal grids can go negative
 #[derive(Component)]
-struct Position(UVec2);
+struct Position(IVec2);
 
 #[derive(Component)]
 struct Tile(TileType);
@@ -58,11 +102,257 @@ struct Cover;
 #[derive(Component)]
 struct Flag;
 
+/// Marks a tile the player is unsure about
+#[derive(Component)]
+struct Question;
+
+#[derive(Component)]
+struct Mine;
+
+/// Image showing the bomb count of a tile, used instead of the count text
+/// when the assets have an image for the count
+#[derive(Component)]
+struct CountSprite;
+
+/// Marks a tile whose bomb was revealed without losing the game
+#[derive(Component)]
+struct DeadMine;
+
+/// Marks the entity holding all tiles of the board
+#[derive(Component)]
+struct BoardRoot;
+
+/// Marks the sprite behind the tiles of the board
+#[derive(Component)]
+struct BoardBackground;
+
+/// Segment of the frame around the board
+#[derive(Component)]
+struct BoardBorder;
+
+/// Marks the lines along the edges of a wrap-around board
+#[derive(Component)]
+struct Seam;
+
+/// Marks the cover of the safe start tile, which is colored as uncovered
+#[derive(Component)]
+struct SafeStartCover;
+
+/// Flashes the mine of a tile from white to the mine color
+/// after it has been revealed, finishing the game afterwards
+#[derive(Component)]
+struct BombHitAnimation {
+    timer: Timer,
+}
+
+/// Reveals the mines in rings around the mine that ended the game, one ring
+/// each time the timer finishes. The game finishes once the rings have passed
+/// the edges of the board
+#[derive(Resource)]
+struct RevealCascade {
+    center: UVec2,
+    /// Distance from the center of the last revealed ring, see `TileMap::region_around`
+    radius: u32,
+    timer: Timer,
+}
+
+/// Seconds between two rings of the reveal cascade
+const REVEAL_CASCADE_STEP: f32 = 0.05;
+
+/// Gently pulses the brightness of a cover while playing,
+/// the phase differs per tile so they don't pulse in unison
+#[derive(Component)]
+struct IdleAnimation {
+    phase_offset: f32,
+}
+
+impl IdleAnimation {
+    fn new(position: UVec2) -> Self {
+        // Cheap hash of the position spreading neighboring tiles over the whole cycle
+        let hash = (position.as_vec2().dot(Vec2::new(12.9898, 78.233)).sin() * 43758.547).fract();
+        Self {
+            phase_offset: hash * TAU,
+        }
+    }
+}
+
+/// Idle pulses of a cover per second
+const IDLE_PULSE_FREQUENCY: f32 = 0.4;
+
+/// How far a pulsing cover brightens towards white
+const IDLE_BRIGHTNESS: f32 = 0.08;
+
+/// Duration of the bomb hit flash in seconds
+const BOMB_HIT_DURATION: f32 = 0.3;
+
+/// Shrinks a cover that has been revealed,
+/// despawning it once the timer finishes
+#[derive(Component)]
+struct RevealAnimating {
+    timer: Timer,
+}
+
+impl RevealAnimating {
+    /// Shrinks a revealed cover after a delay growing with the number of steps
+    /// from the revealed tile, without animations it is despawned right away
+    fn start(
+        commands: &mut Commands,
+        cover_entity: Entity,
+        depth: u32,
+        display_settings: &DisplaySettings,
+    ) {
+        let (Some(duration), Some(delay)) = (
+            display_settings.animation_duration(REVEAL_DURATION),
+            display_settings
+                .animation_duration((depth as f32 * REVEAL_DELAY_STEP).min(MAX_REVEAL_DELAY)),
+        ) else {
+            commands.entity(cover_entity).despawn_recursive();
+            return;
+        };
+
+        commands.entity(cover_entity).insert((
+            Self {
+                timer: Timer::from_seconds(duration, TimerMode::Once),
+            },
+            RevealDelay(delay),
+        ));
+    }
+}
+
+/// Duration of the cover shrinking on reveal in seconds
+const REVEAL_DURATION: f32 = 0.08;
+
+/// Seconds left before a revealed cover starts shrinking,
+/// so flood-filled regions ripple outwards from the revealed tile
+#[derive(Component)]
+struct RevealDelay(f32);
+
+/// Delay in seconds added per step away from the revealed tile
+const REVEAL_DELAY_STEP: f32 = 0.03;
+
+/// Longest delay in seconds before a cover starts shrinking
+const MAX_REVEAL_DELAY: f32 = 0.5;
+
+/// Grows a newly planted flag with a bounce, or shrinks
+/// a removed flag before despawning it
+#[derive(Component)]
+struct FlagAnimation {
+    timer: Timer,
+    removing: bool,
+}
+
+impl FlagAnimation {
+    /// Animates planting or removing a flag,
+    /// without animations a removed flag is despawned right away
+    fn start(
+        commands: &mut Commands,
+        flag_entity: Entity,
+        removing: bool,
+        display_settings: &DisplaySettings,
+    ) {
+        match display_settings.animation_duration(FLAG_ANIMATION_DURATION) {
+            Some(duration) => {
+                commands.entity(flag_entity).insert(Self {
+                    timer: Timer::from_seconds(duration, TimerMode::Once),
+                    removing,
+                });
+            }
+            None if removing => commands.entity(flag_entity).despawn_recursive(),
+            None => {}
+        }
+    }
+}
+
+/// Duration of planting or removing a flag in seconds
+const FLAG_ANIMATION_DURATION: f32 = 0.25;
+
+/// How far a planted flag grows past its size before settling
+const FLAG_OVERSHOOT: f32 = 2.5;
+
+/// Times the highlight of the safe start region
+#[derive(Resource)]
+struct SafeStartHighlight(Timer);
+
+/// Duration of the safe start highlight in seconds
+const SAFE_START_HIGHLIGHT_DURATION: f32 = 2.;
+
+/// Tiles uncovered by a single reveal, restored when undoing it
+struct UndoAction {
+    /// Positions of the uncovered tiles and whether they were questioned
+    revealed: Vec<(UVec2, bool)>,
+}
+
+/// Most recent reveals that can be undone with Ctrl+Z
+#[derive(Resource, Default)]
+struct UndoStack(Vec<UndoAction>);
+
+/// Maximum number of reveals that can be undone
+const UNDO_DEPTH: usize = 5;
+
+/// Number of boards generated before giving up on finding
+/// a board that can be started without guessing
+const MAX_GENERATION_ATTEMPTS: u64 = 100;
+
+/// Number of boards generated before giving up on the
+/// minimum opening of a safe start
+const MAX_OPENING_ATTEMPTS: u64 = 50;
+
+/// Tile map being generated in the background, along with the safe start tile
+#[derive(Resource)]
+struct BoardGeneration(Task<Result<(TileMap, Option<UVec2>), GenerationError>>);
+
+/// Error returned when the board of a new game cannot be generated
+#[derive(Debug)]
+enum GenerationError {
+    /// The share code of the options or the saved game could not be decoded
+    ShareCode(ShareCodeError),
+    TileMap(TileMapError),
+}
+
+impl From<ShareCodeError> for GenerationError {
+    fn from(err: ShareCodeError) -> Self {
+        Self::ShareCode(err)
+    }
+}
+
+impl From<TileMapError> for GenerationError {
+    fn from(err: TileMapError) -> Self {
+        Self::TileMap(err)
+    }
+}
+
+impl std::fmt::Display for GenerationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GenerationError::ShareCode(err) => write!(f, "invalid share code: {err}"),
+            GenerationError::TileMap(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for GenerationError {}
+
+/// Spinner shown while the board is being generated
+#[derive(Component)]
+struct LoadingSpinner;
+
+/// Rotation of the loading spinner in radians per second
+const SPINNER_SPEED: f32 = 4.;
+
+/// Width and height of the loading spinner
+const SPINNER_SIZE: f32 = 40.;
+
+/// Z position of the loading spinner, above the previous board when restarting
+const SPINNER_Z: f32 = FLAG_Z + 1.;
+
 #[derive(Component)]
 enum OverlayButtonAction {
     Restart,
     ReturnToMenu,
     Continue,
+    WatchReplay,
+    /// Lets the solver finish the game
+    Spectate,
 }
 
 #[derive(Event)]
@@ -75,6 +365,27 @@ pub struct TileFlagged {
     pub position: UVec2,
 }
 
+/// Places a question mark on a covered tile, sent when a flag is removed
+#[derive(Event)]
+pub struct TileQuestioned {
+    pub position: UVec2,
+}
+
+/// Reveals all unflagged neighbors of a revealed number tile
+/// if enough flags have been placed around it
+#[derive(Event)]
+pub struct TileChord {
+    pub position: UVec2,
+}
+
+/// Sent once a freshly generated `Board` resource has been inserted
+#[derive(Event)]
+pub struct BoardInitialized;
+
+/// Adds a border of covered tiles without bombs around the board, sent with Ctrl and +
+#[derive(Event)]
+pub struct BoardExpand;
+
 #[derive(Resource)]
 struct GameResult(bool);
 
@@ -89,12 +400,67 @@ const TILE_Z: f32 = 1.;
 /// the number of bombs around a tile
 const BOMB_COUNT_Z: f32 = 2.;
 
+/// The z-index of the cross over revealed bombs that cost a life
+const DEAD_MINE_Z: f32 = 2.5;
+
 /// The z-index of the cover
 const COVER_Z: f32 = 3.;
 
 /// The z-index of the flag sprite
 const FLAG_Z: f32 = 4.;
 
+/// Size of the bomb count text relative to the tile size
+const COUNT_FONT_SCALE: f32 = 0.75;
+
+/// Smallest font size of the bomb counts, keeps them readable on tiny tiles
+const MIN_COUNT_FONT_SIZE: f32 = 8.;
+
+/// Largest font size of the bomb counts
+const MAX_COUNT_FONT_SIZE: f32 = 48.;
+
+/// Width of the lines marking the edges of a wrap-around board
+const SEAM_WIDTH: f32 = 2.;
+
+/// Returns the font size of the text shown on a tile, it fits inside the tile
+/// but stays readable on small tiles and modest on large ones
+fn count_font_size(tile_size: f32) -> f32 {
+    (tile_size * COUNT_FONT_SCALE).clamp(MIN_COUNT_FONT_SIZE, MAX_COUNT_FONT_SIZE)
+}
+
+/// Returns the sprite sheet graphic replacing the solid colored sprite of a tile,
+/// `None` when there is no tile sprite sheet
+fn atlas_sprite(
+    game_assets: &GameAssets,
+    sprite: TileSpriteIndex,
+    tile_size: f32,
+) -> Option<(Sprite, Handle<Image>, TextureAtlas)> {
+    game_assets.tile_atlas(sprite).map(|(image, atlas)| {
+        let sprite = Sprite {
+            custom_size: Some(Vec2::splat(tile_size)),
+            ..Default::default()
+        };
+        (sprite, image, atlas)
+    })
+}
+
+/// Returns the graphic of a revealed tile in the tile sprite sheet
+fn tile_sprite_index(tile_type: TileType) -> TileSpriteIndex {
+    match tile_type {
+        TileType::Bomb => TileSpriteIndex::Mine,
+        TileType::Number(count) => TileSpriteIndex::Number(count as u8),
+        TileType::Empty => TileSpriteIndex::Empty,
+    }
+}
+
+/// Returns the texture of the tile sprites, the default texture
+/// for square tiles and the generated hexagon for hexagonal tiles
+fn tile_texture(shape: GridShape, game_assets: &GameAssets) -> Handle<Image> {
+    match shape {
+        GridShape::Square => Default::default(),
+        GridShape::Hexagonal => game_assets.hex_tile.clone(),
+    }
+}
+
 pub struct GamePlugin;
 
 impl Plugin for GamePlugin {
@@ -102,27 +468,106 @@ impl Plugin for GamePlugin {
         app.init_state::<GameState>()
             .add_event::<TileRevealed>()
             .add_event::<TileFlagged>()
-            .add_plugins(options::GameOptionsPlugin)
+            .add_event::<TileChord>()
+            .add_event::<TileQuestioned>()
+            .add_event::<BoardInitialized>()
+            .add_event::<BoardExpand>()
+            .add_plugins((
+                options::GameOptionsPlugin,
+                hud::HudPlugin,
+                camera::CameraPlugin,
+                cursor::CursorPlugin,
+                hint::HintPlugin,
+                scores::ScoresPlugin,
+                presets::PresetsPlugin,
+                particles::ParticlePlugin,
+                stats::StatsPlugin,
+                drag_flag::DragFlagPlugin,
+                touch::TouchPlugin,
+                gamepad::GamepadPlugin,
+                accessibility::TileAccessibilityPlugin,
+                replay::ReplayPlugin,
+                region_select::RegionSelectPlugin,
+            ))
+            .add_plugins((
+                saved_game::SavedGamePlugin,
+                minimap::MinimapPlugin,
+                versus::VersusPlugin,
+                event_log::EventLogPlugin,
+                screenshot::ScreenshotPlugin,
+                tooltip::TooltipPlugin,
+                spectate::SpectatePlugin,
+            ))
             .add_systems(OnEnter(AppState::Game), Self::start_setup)
             .add_systems(OnExit(AppState::Game), despawn_all::<OnGameScreen>)
-            .add_systems(OnEnter(GameState::Playing), Self::start_game)
-            .add_systems(OnEnter(GameState::Paused), Self::pause)
+            .add_systems(OnEnter(GameState::Generating), Self::start_generation)
+            .add_systems(OnExit(GameState::Generating), despawn_all::<LoadingSpinner>)
+            .add_systems(
+                Update,
+                (Self::rotate_spinner, Self::poll_board_generation)
+                    .run_if(in_state(GameState::Generating)),
+            )
+            .add_systems(
+                OnEnter(GameState::Paused),
+                (Self::pause, Self::clear_tile_events),
+            )
             .add_systems(OnExit(GameState::Paused), despawn_all::<OnPauseScreen>)
-            .add_systems(OnEnter(GameState::Finished), Self::game_finished)
+            .add_systems(
+                OnEnter(GameState::Finished),
+                (Self::game_finished, Self::clear_tile_events),
+            )
             .add_systems(
                 OnExit(GameState::Finished),
-                (despawn_all::<OnGameScreen>, despawn_all::<OnFinishedScreen>),
+                (
+                    // The finished board stays visible while the next one is generated
+                    despawn_with_filter::<OnGameScreen, Without<BoardRoot>>,
+                    despawn_all::<OnFinishedScreen>,
+                ),
             )
             .add_systems(
                 Update,
                 (
-                    Self::handle_keyboard_input,
-                    Self::handle_mouse_input,
+                    Self::handle_keyboard_input.run_if(in_state(GameState::Playing)),
+                    Self::handle_mouse_input.run_if(in_state(GameState::Playing)),
+                    Self::handle_chord_event.before(Self::handle_reveal_event),
                     Self::handle_reveal_event,
                     Self::handle_flag_event,
-                    Self::check_finished,
+                    Self::handle_board_expand.run_if(on_event::<BoardExpand>()),
+                    Self::board_relayout.after(Self::handle_board_expand),
+                    Self::handle_question_event.after(Self::handle_flag_event),
+                    Self::mark_dead_mines,
+                    Self::update_board_counts
+                        .after(Self::handle_reveal_event)
+                        .after(Self::handle_question_event)
+                        .run_if(resource_changed::<Board>),
+                    Self::check_finished.after(Self::update_board_counts),
+                    Self::check_time_up
+                        .after(Self::update_board_counts)
+                        .run_if(resource_exists::<CountdownTimer>),
+                    Self::animate_covered_tiles.before(Self::highlight_safe_start),
+                    Self::highlight_safe_start.run_if(resource_exists::<SafeStartHighlight>),
+                )
+                    // The solver plays through the same tile events as the player
+                    .run_if(in_state(GameState::Playing).or_else(in_state(GameState::Spectating))),
+            )
+            .add_systems(OnExit(GameState::Playing), Self::reset_covered_tiles)
+            .add_systems(
+                Update,
+                (
+                    Self::animate_bomb_hit,
+                    Self::step_reveal_cascade.run_if(resource_exists::<RevealCascade>),
                 )
-                    .run_if(in_state(GameState::Playing)),
+                    .chain()
+                    .run_if(in_state(GameState::Animating)),
+            )
+            .add_systems(
+                Update,
+                (
+                    Self::process_delayed_reveals,
+                    Self::animate_reveal.after(Self::process_delayed_reveals),
+                    Self::animate_flag.after(Self::handle_flag_event),
+                    Self::recolor_board.run_if(resource_changed::<GameAssets>),
+                ),
             )
             .add_systems(
                 Update,
@@ -133,35 +578,244 @@ impl Plugin for GamePlugin {
                         .run_if(in_state(GameState::Finished)),
                 ),
             );
+
+        #[cfg(feature = "debug")]
+        app.add_systems(
+            Update,
+            crate::util::log_state_transitions::<GameState>.run_if(state_changed::<GameState>),
+        );
+
+        #[cfg(feature = "audio")]
+        app.add_plugins(sound::SoundPlugin);
+
+        #[cfg(feature = "debug")]
+        app.add_plugins((
+            probability_overlay::ProbabilityOverlayPlugin,
+            reveal_throughput::RevealThroughputPlugin,
+        ));
     }
 }
 
 impl GamePlugin {
-    fn start_setup(mut game_state: ResMut<NextState<GameState>>, board: Option<Res<Board>>) {
+    fn start_setup(
+        mut game_state: ResMut<NextState<GameState>>,
+        board: Option<Res<Board>>,
+        saved_game: Option<Res<SavedGame>>,
+        mut game_options: ResMut<GameOptions>,
+    ) {
         if board.is_some() {
             game_state.set(GameState::Playing);
+        } else if let Some(saved_game) = saved_game {
+            // The options are skipped, the saved game is played with its own
+            *game_options = saved_game.options().clone();
+            game_state.set(GameState::Generating);
         } else {
             game_state.set(GameState::Options);
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn handle_keyboard_input(
+        mut commands: Commands,
         mut keyboard_evr: EventReader<KeyboardInput>,
+        keyboard_input: Res<ButtonInput<KeyCode>>,
         mut game_state: ResMut<NextState<GameState>>,
+        mut undo_stack: ResMut<UndoStack>,
+        mut board: ResMut<Board>,
+        game_assets: Res<GameAssets>,
+        mut board_expand_evw: EventWriter<BoardExpand>,
     ) {
         for event in keyboard_evr.read() {
             if event.key_code == KeyCode::KeyP {
                 game_state.set(GameState::Paused);
             }
+
+            let ctrl = keyboard_input.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
+            if event.state != ButtonState::Pressed || !ctrl {
+                continue;
+            }
+            match event.key_code {
+                KeyCode::KeyZ => {
+                    if let Some(action) = undo_stack.0.pop() {
+                        Self::undo_reveal(&mut commands, &mut board, action, &game_assets);
+                    }
+                }
+                // The plus shares its key with the equals sign on most layouts
+                KeyCode::Equal | KeyCode::NumpadAdd => {
+                    board_expand_evw.send(BoardExpand);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Adds a border of tiles around the board, spawning the new tiles and moving
+    /// everything that refers to a tile by position one tile along with it.
+    /// The board is laid out again by `board_relayout` afterwards
+    #[allow(clippy::too_many_arguments)]
+    fn handle_board_expand(
+        mut commands: Commands,
+        mut board: ResMut<Board>,
+        game_assets: Res<GameAssets>,
+        mut positions: Query<&mut Position>,
+        roots: Query<Entity, With<BoardRoot>>,
+        mut undo_stack: ResMut<UndoStack>,
+        mut replay: ResMut<Replay>,
+        mut event_log: ResMut<GameEventLog>,
+        mut cursor_tile: ResMut<cursor::CursorTile>,
+    ) {
+        if let Err(err) = board.tile_map.expand_border() {
+            warn!("Could not expand the board: {err}");
+            return;
+        }
+
+        for mut position in positions.iter_mut() {
+            position.0 += IVec2::ONE;
+        }
+        for action in undo_stack.0.iter_mut() {
+            for (position, _) in action.revealed.iter_mut() {
+                *position += UVec2::ONE;
+            }
+        }
+        for event in replay.events.iter_mut() {
+            let (ReplayEvent::Reveal(position, _) | ReplayEvent::Flag(position, _)) = event;
+            *position += UVec2::ONE;
+        }
+        for event in event_log.0.iter_mut() {
+            let (GameEventKind::Revealed(position)
+            | GameEventKind::Flagged(position)
+            | GameEventKind::Chord(position)
+            | GameEventKind::Questioned(position)) = &mut event.kind;
+            *position += UVec2::ONE;
+        }
+        if let Some(position) = &mut cursor_tile.0 {
+            *position += UVec2::ONE;
+        }
+        if let Some(safe_region) = &mut board.safe_region {
+            for position in safe_region.iter_mut() {
+                *position += UVec2::ONE;
+            }
+        }
+
+        let Ok(root) = roots.get_single() else {
+            return;
+        };
+
+        // The tiles are placed by the relayout, the new ones only need their content
+        let tile_size = board.tile_size;
+        let texture = tile_texture(board.tile_map.shape(), &game_assets);
+        let cover_atlas_sprite = atlas_sprite(&game_assets, TileSpriteIndex::Cover, tile_size);
+        for (position, tile) in board.tile_map.iter_mut_with_pos() {
+            if tile.entity.is_some() {
+                continue;
+            }
+
+            let tile_entity = commands
+                .spawn((
+                    SpriteBundle {
+                        sprite: Sprite {
+                            color: game_assets.tile_uncovered,
+                            custom_size: Some(Vec2::splat(tile_size)),
+                            ..Default::default()
+                        },
+                        texture: texture.clone(),
+                        transform: Transform::from_xyz(0., 0., TILE_Z),
+                        ..Default::default()
+                    },
+                    Position(position.as_ivec2()),
+                    Tile(tile.tile_type),
+                ))
+                .id();
+            let cover_entity = Self::spawn_cover(
+                &mut commands,
+                position,
+                tile_size,
+                texture.clone(),
+                game_assets.tile_covered,
+            );
+            if let Some(cover_atlas_sprite) = cover_atlas_sprite.clone() {
+                commands.entity(cover_entity).insert(cover_atlas_sprite);
+            }
+            commands.entity(tile_entity).push_children(&[cover_entity]);
+
+            // Sprite sheet graphics already show the bomb count
+            if let Some(tile_atlas_sprite) =
+                atlas_sprite(&game_assets, tile_sprite_index(tile.tile_type), tile_size)
+            {
+                commands.entity(tile_entity).insert(tile_atlas_sprite);
+            } else if let Some(content) = Self::spawn_tile_content(
+                &mut commands,
+                tile.tile_type,
+                tile_size,
+                texture.clone(),
+                &game_assets,
+            ) {
+                commands.entity(tile_entity).push_children(&[content]);
+            }
+
+            commands.entity(root).push_children(&[tile_entity]);
+            tile.entity = Some(tile_entity);
+            tile.cover = Some(cover_entity);
+        }
+    }
+
+    /// Covers the tiles uncovered by a reveal again
+    fn undo_reveal(
+        commands: &mut Commands,
+        board: &mut Board,
+        action: UndoAction,
+        game_assets: &GameAssets,
+    ) {
+        let tile_size = board.tile_size;
+        let texture = tile_texture(board.tile_map.shape(), game_assets);
+
+        for (position, questioned) in action.revealed {
+            let Some(tile) = board.tile_map.get_tile_mut(position) else {
+                continue;
+            };
+            let Some(tile_entity) = tile.entity else {
+                continue;
+            };
+
+            let cover_entity = Self::spawn_cover(
+                commands,
+                position,
+                tile_size,
+                texture.clone(),
+                game_assets.tile_covered,
+            );
+            commands.entity(tile_entity).push_children(&[cover_entity]);
+            tile.cover = Some(cover_entity);
+
+            if questioned {
+                let question_entity = Self::spawn_marker(
+                    commands,
+                    position,
+                    game_assets.tile_questioned,
+                    tile_size,
+                    texture.clone(),
+                    Question,
+                );
+                commands
+                    .entity(tile_entity)
+                    .push_children(&[question_entity]);
+                tile.question = Some(question_entity);
+            }
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn handle_mouse_input(
         window: Query<&Window>,
         board: Res<Board>,
+        camera_controller: Res<CameraController>,
+        drag_flag_mode: Res<drag_flag::DragFlagMode>,
+        region_select_mode: Res<region_select::RegionSelectMode>,
+        mouse_buttons: Res<ButtonInput<MouseButton>>,
         mut mouse_button_evr: EventReader<MouseButtonInput>,
         mut tile_revealed_evw: EventWriter<TileRevealed>,
         mut tile_flagged_evw: EventWriter<TileFlagged>,
+        mut tile_chord_evw: EventWriter<TileChord>,
     ) {
         let window = &window.single();
 
@@ -169,8 +823,19 @@ impl GamePlugin {
             match event.state {
                 ButtonState::Pressed => {
                     if let Some(cursor_position) = window.cursor_position() {
-                        if let Some(position) = board.mouse_to_tile(window, cursor_position) {
-                            if event.button == MouseButton::Left {
+                        if let Some(position) =
+                            board.mouse_to_tile(window, cursor_position, &camera_controller)
+                        {
+                            let chord = mouse_buttons.pressed(MouseButton::Left)
+                                && mouse_buttons.pressed(MouseButton::Right);
+
+                            if chord {
+                                tile_chord_evw.send(TileChord { position });
+                            } else if event.button == MouseButton::Left && region_select_mode.0 {
+                                // The left button selects a region instead
+                            } else if event.button == MouseButton::Left && drag_flag_mode.0 {
+                                tile_flagged_evw.send(TileFlagged { position });
+                            } else if event.button == MouseButton::Left {
                                 tile_revealed_evw.send(TileRevealed { position });
                             } else if event.button == MouseButton::Right {
                                 tile_flagged_evw.send(TileFlagged { position });
@@ -183,90 +848,261 @@ impl GamePlugin {
         }
     }
 
+    fn handle_chord_event(
+        board: Res<Board>,
+        mut tile_chord_evr: EventReader<TileChord>,
+        mut tile_revealed_evw: EventWriter<TileRevealed>,
+        mut event_log: ResMut<GameEventLog>,
+        game_timer: Option<Res<GameTimer>>,
+    ) {
+        for event in tile_chord_evr.read() {
+            event_log.record(game_timer.as_deref(), GameEventKind::Chord(event.position));
+
+            if !board.tile_map.can_chord(event.position) {
+                continue;
+            }
+
+            for position in board.tile_map.get_neighbors(event.position) {
+                let tile = board.tile_map.get_tile(position).unwrap();
+                if tile.cover.is_some() && tile.flag.is_none() {
+                    tile_revealed_evw.send(TileRevealed { position });
+                }
+            }
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
     fn handle_reveal_event(
         mut commands: Commands,
         mut board: ResMut<Board>,
         mut tile_revealed_evr: EventReader<TileRevealed>,
         mut game_state: ResMut<NextState<GameState>>,
+        mut undo_stack: ResMut<UndoStack>,
+        mut replay: ResMut<Replay>,
+        mut event_log: ResMut<GameEventLog>,
+        board_counts: Res<BoardCounts>,
+        game_timer: Option<Res<GameTimer>>,
+        game_options: Res<GameOptions>,
+        game_assets: Res<GameAssets>,
+        display_settings: Res<DisplaySettings>,
+        #[cfg(feature = "audio")] sounds: sound::Sounds,
+        #[cfg(feature = "debug")] mut reveal_throughput: ResMut<RevealThroughput>,
     ) {
-        let mut queue = VecDeque::new();
-        for event in tile_revealed_evr.read() {
-            let tile = match board.tile_map.get_tile(event.position) {
+        if board_counts.is_lost(board.lives) || board_counts.is_won(board.lives) {
+            return;
+        }
+
+        let positions = tile_revealed_evr
+            .read()
+            .map(|event| event.position)
+            .collect::<Vec<_>>();
+
+        if let Some(game_timer) = &game_timer {
+            let time = game_timer.0.as_secs_f32();
+            replay.events.extend(
+                positions
+                    .iter()
+                    .map(|&position| ReplayEvent::Reveal(position, time)),
+            );
+        }
+        for &position in &positions {
+            event_log.record(game_timer.as_deref(), GameEventKind::Revealed(position));
+        }
+
+        if let Some(&first) = positions.first() {
+            if !board.tile_map.is_generated() && board.tile_map.get_tile(first).is_some() {
+                Self::generate_board(
+                    &mut commands,
+                    &mut board,
+                    first,
+                    &game_options,
+                    &game_assets,
+                );
+            }
+        }
+
+        let mut starts = Vec::with_capacity(positions.len());
+        for position in positions {
+            let tile = match board.tile_map.get_tile(position) {
                 Some(tile) => tile,
                 None => {
                     #[cfg(feature = "debug")]
-                    log::error!("Could not find tile for position {}", event.position);
+                    log::error!("Could not find tile for position {}", position);
                     continue;
                 }
             };
             if tile.cover.is_none() {
-                if let TileType::Number(count) = tile.tile_type {
-                    if count
-                        == board
-                            .tile_map
-                            .get_neighbors(event.position)
-                            .filter(|pos| board.tile_map.get_tile(*pos).unwrap().flag.is_some())
-                            .count()
-                    {
-                        for neighbor in board.tile_map.get_neighbors(event.position) {
-                            queue.push_back(neighbor);
-                        }
-                    }
+                if board.tile_map.can_chord(position) {
+                    starts.extend(board.tile_map.get_neighbors(position));
                 }
             } else {
-                queue.push_back(event.position);
+                starts.push(position);
             }
         }
 
-        let mut revealed = HashSet::new();
+        let mut undo_action = UndoAction {
+            revealed: Vec::new(),
+        };
 
-        while let Some(position) = queue.pop_front() {
-            if !revealed.insert(position) {
+        // Tiles are revealed breadth first, the depth being the number of steps
+        // from the tile the reveal started at
+        for (position, depth) in board.tile_map.floodfill_with_depth(&starts) {
+            let Some(tile) = board.tile_map.get_tile_mut(position) else {
                 continue;
-            }
-
-            let tile = match board.tile_map.get_tile_mut(position) {
-                Some(tile) => tile,
-                None => {
-                    #[cfg(feature = "debug")]
-                    log::error!("Could not find tile for position {}", position);
-                    continue;
-                }
             };
 
-            if tile.flag.is_some() {
+            if let Some(cover_entity) = tile.cover.take() {
+                RevealAnimating::start(&mut commands, cover_entity, depth, &display_settings);
+            } else {
                 continue;
             }
 
-            if let Some(cover_entity) = tile.cover.take() {
-                commands.entity(cover_entity).despawn_recursive();
-            } else {
+            #[cfg(feature = "debug")]
+            {
+                reveal_throughput.count += 1;
+            }
+
+            let questioned = tile.question.is_some();
+            if let Some(question_entity) = tile.question.take() {
+                commands.entity(question_entity).despawn_recursive();
+            }
+
+            undo_action.revealed.push((position, questioned));
+
+            if !tile.is_bomb() {
                 continue;
             }
 
-            match tile.tile_type {
-                TileType::Bomb => {
-                    commands.insert_resource(GameResult(false));
-                    game_state.set(GameState::Finished);
-                }
-                TileType::Empty => {
-                    for neighbor in board.tile_map.get_neighbors(position) {
-                        queue.push_back(neighbor);
-                    }
-                }
-                TileType::Number(_) => {}
+            let tile_entity = tile.entity.unwrap();
+            particles::spawn_burst(
+                &mut commands,
+                tile_entity,
+                game_assets.tile_mine,
+                board.tile_size,
+                &display_settings,
+            );
+
+            #[cfg(feature = "audio")]
+            sounds.play(&mut commands, sound::Sound::Explosion);
+
+            if board.is_lost() {
+                commands.insert_resource(GameResult(false));
+                commands.entity(tile_entity).insert(BombHitAnimation {
+                    timer: Timer::from_seconds(BOMB_HIT_DURATION, TimerMode::Once),
+                });
+                game_state.set(GameState::Animating);
+            } else {
+                // The life is spent for good, undoing the reveal would give it back
+                undo_action.revealed.pop();
+                commands.entity(tile_entity).insert(DeadMine);
+            }
+        }
+
+        #[cfg(feature = "audio")]
+        if !undo_action.revealed.is_empty() {
+            sounds.play(&mut commands, sound::Sound::Reveal);
+        }
+
+        if !game_options.competitive && !undo_action.revealed.is_empty() {
+            if undo_stack.0.len() == UNDO_DEPTH {
+                undo_stack.0.remove(0);
             }
+            undo_stack.0.push(undo_action);
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn handle_flag_event(
         mut commands: Commands,
         mut tile_flagged_evr: EventReader<TileFlagged>,
+        mut tile_questioned_evw: EventWriter<TileQuestioned>,
         mut board: ResMut<Board>,
+        board_counts: Res<BoardCounts>,
+        mut replay: ResMut<Replay>,
+        mut event_log: ResMut<GameEventLog>,
+        game_timer: Option<Res<GameTimer>>,
         game_assets: Res<GameAssets>,
+        display_settings: Res<DisplaySettings>,
+        #[cfg(feature = "audio")] sounds: sound::Sounds,
     ) {
+        if board_counts.is_lost(board.lives) {
+            return;
+        }
+
         for event in tile_flagged_evr.read() {
             let tile_size = board.tile_size;
+            let texture = tile_texture(board.tile_map.shape(), &game_assets);
+
+            let tile = match board.tile_map.get_tile_mut(event.position) {
+                Some(tile) => tile,
+                None => {
+                    #[cfg(feature = "debug")]
+                    log::error!("Could not find tile for position {}", event.position);
+                    continue;
+                }
+            };
+
+            if tile.cover.is_none() {
+                continue;
+            }
+
+            if let Some(game_timer) = &game_timer {
+                replay.events.push(ReplayEvent::Flag(
+                    event.position,
+                    game_timer.0.as_secs_f32(),
+                ));
+            }
+            event_log.record(
+                game_timer.as_deref(),
+                GameEventKind::Flagged(event.position),
+            );
+
+            // Cycle between covered, flagged and questioned
+            if let Some(flag_entity) = tile.flag.take() {
+                FlagAnimation::start(&mut commands, flag_entity, true, &display_settings);
+
+                tile_questioned_evw.send(TileQuestioned {
+                    position: event.position,
+                });
+            } else if let Some(question_entity) = tile.question.take() {
+                commands.entity(question_entity).despawn_recursive();
+            } else {
+                let flag_entity = Self::spawn_marker(
+                    &mut commands,
+                    event.position,
+                    game_assets.tile_flagged,
+                    tile_size,
+                    texture,
+                    Flag,
+                );
+                FlagAnimation::start(&mut commands, flag_entity, false, &display_settings);
+                if let Some(flag_atlas_sprite) =
+                    atlas_sprite(&game_assets, TileSpriteIndex::Flag, tile_size)
+                {
+                    commands.entity(flag_entity).insert(flag_atlas_sprite);
+                }
+                commands
+                    .entity(tile.entity.unwrap())
+                    .push_children(&[flag_entity]);
+                tile.flag = Some(flag_entity);
+
+                #[cfg(feature = "audio")]
+                sounds.play(&mut commands, sound::Sound::FlagPlace);
+            }
+        }
+    }
+
+    fn handle_question_event(
+        mut commands: Commands,
+        mut tile_questioned_evr: EventReader<TileQuestioned>,
+        mut board: ResMut<Board>,
+        mut event_log: ResMut<GameEventLog>,
+        game_timer: Option<Res<GameTimer>>,
+        game_assets: Res<GameAssets>,
+    ) {
+        for event in tile_questioned_evr.read() {
+            let tile_size = board.tile_size;
+            let texture = tile_texture(board.tile_map.shape(), &game_assets);
 
             let tile = match board.tile_map.get_tile_mut(event.position) {
                 Some(tile) => tile,
@@ -277,229 +1113,1025 @@ impl GamePlugin {
                 }
             };
 
-            if tile.cover.is_none() {
-                continue;
-            }
+            if tile.cover.is_none() || tile.flag.is_some() || tile.question.is_some() {
+                continue;
+            }
+            event_log.record(
+                game_timer.as_deref(),
+                GameEventKind::Questioned(event.position),
+            );
+
+            let question_entity = Self::spawn_marker(
+                &mut commands,
+                event.position,
+                game_assets.tile_questioned,
+                tile_size,
+                texture,
+                Question,
+            );
+            commands
+                .entity(tile.entity.unwrap())
+                .push_children(&[question_entity]);
+            tile.question = Some(question_entity);
+        }
+    }
+
+    /// Spawns the cover hiding the contents of a tile
+    fn spawn_cover(
+        commands: &mut Commands,
+        position: UVec2,
+        tile_size: f32,
+        texture: Handle<Image>,
+        color: Color,
+    ) -> Entity {
+        commands
+            .spawn(Self::cover_bundle(position, tile_size, texture, color))
+            .id()
+    }
+
+    fn cover_bundle(
+        position: UVec2,
+        tile_size: f32,
+        texture: Handle<Image>,
+        color: Color,
+    ) -> (SpriteBundle, Position, Cover, IdleAnimation) {
+        (
+            SpriteBundle {
+                sprite: Sprite {
+                    custom_size: Some(Vec2::splat(tile_size)),
+                    color,
+                    ..Default::default()
+                },
+                texture,
+                transform: Transform::from_xyz(0., 0., COVER_Z),
+                ..Default::default()
+            },
+            Position(position.as_ivec2()),
+            Cover,
+            IdleAnimation::new(position),
+        )
+    }
+
+    /// Spawns a marker sprite placed on top of a covered tile
+    fn spawn_marker(
+        commands: &mut Commands,
+        position: UVec2,
+        color: Color,
+        tile_size: f32,
+        texture: Handle<Image>,
+        marker: impl Component,
+    ) -> Entity {
+        commands
+            .spawn((
+                SpriteBundle {
+                    sprite: Sprite {
+                        custom_size: Some(Vec2::splat(tile_size)),
+                        color,
+                        ..Default::default()
+                    },
+                    texture,
+                    transform: Transform::from_xyz(0., 0., FLAG_Z),
+                    ..Default::default()
+                },
+                Position(position.as_ivec2()),
+                marker,
+            ))
+            .id()
+    }
+
+    /// Drops tile events that were sent but not yet handled when leaving
+    /// `GameState::Playing`, so they are not processed once play resumes
+    fn clear_tile_events(
+        mut tile_revealed_events: ResMut<Events<TileRevealed>>,
+        mut tile_flagged_events: ResMut<Events<TileFlagged>>,
+        mut tile_chord_events: ResMut<Events<TileChord>>,
+        mut tile_questioned_events: ResMut<Events<TileQuestioned>>,
+    ) {
+        tile_revealed_events.clear();
+        tile_flagged_events.clear();
+        tile_chord_events.clear();
+        tile_questioned_events.clear();
+    }
+
+    fn animate_bomb_hit(
+        mut commands: Commands,
+        time: Res<Time>,
+        mut animations: Query<(Entity, &mut BombHitAnimation, &Children, &Position)>,
+        mut mines: Query<&mut Sprite, With<Mine>>,
+        game_assets: Res<GameAssets>,
+        game_options: Res<GameOptions>,
+        mut game_state: ResMut<NextState<GameState>>,
+    ) {
+        for (entity, mut animation, children, position) in animations.iter_mut() {
+            animation.timer.tick(time.delta());
+
+            let color = lerp_color(
+                Color::WHITE,
+                game_assets.tile_mine,
+                animation.timer.fraction(),
+            );
+            for &child in children.iter() {
+                if let Ok(mut sprite) = mines.get_mut(child) {
+                    sprite.color = color;
+                }
+            }
+
+            if !animation.timer.finished() {
+                continue;
+            }
+
+            commands.entity(entity).remove::<BombHitAnimation>();
+            if game_options.reveal_cascade {
+                commands.insert_resource(RevealCascade {
+                    center: position.0.as_uvec2(),
+                    radius: 0,
+                    timer: Timer::from_seconds(REVEAL_CASCADE_STEP, TimerMode::Repeating),
+                });
+            } else {
+                game_state.set(GameState::Finished);
+            }
+        }
+    }
+
+    /// Reveals the covered mines of the next ring of the cascade,
+    /// finishing the game once no ring is left on the board
+    fn step_reveal_cascade(
+        mut commands: Commands,
+        time: Res<Time>,
+        mut cascade: ResMut<RevealCascade>,
+        mut board: ResMut<Board>,
+        display_settings: Res<DisplaySettings>,
+        mut game_state: ResMut<NextState<GameState>>,
+    ) {
+        if !cascade.timer.tick(time.delta()).just_finished() {
+            return;
+        }
+
+        cascade.radius += 1;
+        let radius = cascade.radius;
+        let ring = board
+            .tile_map
+            .region_where(cascade.center, radius, |_, distance| distance == radius);
+        if ring.is_empty() {
+            commands.remove_resource::<RevealCascade>();
+            game_state.set(GameState::Finished);
+            return;
+        }
+
+        for position in ring {
+            let Some(tile) = board.tile_map.get_tile_mut(position) else {
+                continue;
+            };
+            if !tile.is_bomb() {
+                continue;
+            }
+
+            if let Some(cover_entity) = tile.cover.take() {
+                RevealAnimating::start(&mut commands, cover_entity, 0, &display_settings);
+            }
+        }
+    }
+
+    /// Dims the mine of tiles that cost a life and crosses it out
+    fn mark_dead_mines(
+        mut commands: Commands,
+        board: Res<Board>,
+        dead_mines: Query<(Entity, &Children), Added<DeadMine>>,
+        mut mines: Query<&mut Sprite, With<Mine>>,
+        game_assets: Res<GameAssets>,
+    ) {
+        for (tile_entity, children) in dead_mines.iter() {
+            for &child in children.iter() {
+                if let Ok(mut sprite) = mines.get_mut(child) {
+                    sprite.color = game_assets.tile_mine.with_a(0.5);
+                }
+            }
+
+            let cross = commands
+                .spawn(Text2dBundle {
+                    text: Text::from_section(
+                        "X",
+                        TextStyle {
+                            font: game_assets.tile_count_font.clone(),
+                            font_size: count_font_size(board.tile_size),
+                            color: game_assets.tile_uncovered,
+                        },
+                    ),
+                    transform: Transform::from_xyz(0., 0., DEAD_MINE_Z),
+                    ..Default::default()
+                })
+                .id();
+            commands.entity(tile_entity).push_children(&[cross]);
+        }
+    }
+
+    /// Counts down the delay of revealed covers, they start shrinking
+    /// once their delay has run out
+    fn process_delayed_reveals(
+        mut commands: Commands,
+        time: Res<Time>,
+        mut covers: Query<(Entity, &mut RevealDelay)>,
+    ) {
+        for (entity, mut delay) in covers.iter_mut() {
+            delay.0 -= time.delta_seconds();
+
+            if delay.0 <= 0. {
+                commands.entity(entity).remove::<RevealDelay>();
+            }
+        }
+    }
+
+    fn animate_reveal(
+        mut commands: Commands,
+        time: Res<Time>,
+        mut covers: Query<(Entity, &mut RevealAnimating, &mut Transform), Without<RevealDelay>>,
+    ) {
+        for (entity, mut animation, mut transform) in covers.iter_mut() {
+            animation.timer.tick(time.delta());
+
+            transform.scale = Vec3::ONE.lerp(Vec3::ZERO, animation.timer.fraction());
+
+            if animation.timer.finished() {
+                commands.entity(entity).despawn_recursive();
+            }
+        }
+    }
+
+    fn animate_flag(
+        mut commands: Commands,
+        time: Res<Time>,
+        mut flags: Query<(Entity, &mut FlagAnimation, &mut Transform)>,
+    ) {
+        for (entity, mut animation, mut transform) in flags.iter_mut() {
+            animation.timer.tick(time.delta());
+
+            let t = animation.timer.fraction();
+            transform.scale = if animation.removing {
+                Vec3::splat(1. - t)
+            } else {
+                Vec3::splat(ease_out_back(t, FLAG_OVERSHOOT))
+            };
+
+            if !animation.timer.finished() {
+                continue;
+            }
+
+            if animation.removing {
+                commands.entity(entity).despawn_recursive();
+            } else {
+                commands.entity(entity).remove::<FlagAnimation>();
+            }
+        }
+    }
+
+    /// Pulses the covers between their color and a slightly brighter one.
+    /// The safe start highlight runs afterwards so it overrides the pulse
+    #[allow(clippy::type_complexity)]
+    fn animate_covered_tiles(
+        time: Res<Time>,
+        mut covers: Query<
+            (&IdleAnimation, &mut Sprite),
+            (
+                Without<SafeStartCover>,
+                Without<hint::TileHint>,
+                Without<TextureAtlas>,
+            ),
+        >,
+        game_assets: Res<GameAssets>,
+    ) {
+        let brighter = lerp_color(game_assets.tile_covered, Color::WHITE, IDLE_BRIGHTNESS);
+        let angle = time.elapsed_seconds() * IDLE_PULSE_FREQUENCY * TAU;
+
+        for (animation, mut sprite) in covers.iter_mut() {
+            let t = (f32::sin(angle + animation.phase_offset) + 1.) / 2.;
+            sprite.color = lerp_color(game_assets.tile_covered, brighter, t);
+        }
+    }
+
+    /// Leaves the covers static in their color once play stops
+    #[allow(clippy::type_complexity)]
+    fn reset_covered_tiles(
+        mut covers: Query<
+            &mut Sprite,
+            (
+                With<IdleAnimation>,
+                Without<SafeStartCover>,
+                Without<hint::TileHint>,
+                Without<TextureAtlas>,
+            ),
+        >,
+        game_assets: Res<GameAssets>,
+    ) {
+        for mut sprite in covers.iter_mut() {
+            sprite.color = game_assets.tile_covered;
+        }
+    }
+
+    /// Tints the covers of the safe start region, fading back to
+    /// their normal color before the highlight is removed
+    fn highlight_safe_start(
+        mut commands: Commands,
+        time: Res<Time>,
+        mut highlight: ResMut<SafeStartHighlight>,
+        mut board: ResMut<Board>,
+        mut covers: Query<(&Position, &mut Sprite), With<Cover>>,
+        game_assets: Res<GameAssets>,
+    ) {
+        highlight.0.tick(time.delta());
+
+        let Some(safe_region) = &board.safe_region else {
+            commands.remove_resource::<SafeStartHighlight>();
+            return;
+        };
+
+        // The start tile is covered by an uncovered colored sprite
+        let start = safe_region[0];
+        let tint = 0.25 * highlight.0.fraction_remaining();
+
+        for (position, mut sprite) in covers.iter_mut() {
+            let Ok(position) = UVec2::try_from(position.0) else {
+                continue;
+            };
+            if !safe_region.contains(&position) {
+                continue;
+            }
+
+            let base = if position == start {
+                game_assets.tile_uncovered
+            } else {
+                game_assets.tile_covered
+            };
+            sprite.color = lerp_color(base, Color::WHITE, tint);
+        }
+
+        if highlight.0.finished() {
+            board.safe_region = None;
+            commands.remove_resource::<SafeStartHighlight>();
+        }
+    }
+
+    /// Caches the tile counts of the board after it has changed
+    fn update_board_counts(board: Res<Board>, mut board_counts: ResMut<BoardCounts>) {
+        *board_counts = board.counts();
+    }
+
+    fn check_finished(
+        mut commands: Commands,
+        board: Res<Board>,
+        board_counts: Res<BoardCounts>,
+        mut game_state: ResMut<NextState<GameState>>,
+    ) {
+        if board_counts.is_won(board.lives) {
+            commands.insert_resource(GameResult(true));
+            game_state.set(GameState::Finished);
+        }
+    }
+
+    /// Ends the game as lost once the rush mode countdown has run out
+    fn check_time_up(
+        mut commands: Commands,
+        countdown_timer: Res<CountdownTimer>,
+        board: Res<Board>,
+        board_counts: Res<BoardCounts>,
+        mut game_state: ResMut<NextState<GameState>>,
+    ) {
+        if countdown_timer.0.finished() && !board_counts.is_won(board.lives) {
+            commands.insert_resource(GameResult(false));
+            game_state.set(GameState::Finished);
+        }
+    }
+
+    /// Starts placing the bombs in the background, large boards
+    /// would otherwise stall the game for a few frames
+    fn start_generation(
+        mut commands: Commands,
+        game_options: Res<GameOptions>,
+        game_assets: Res<GameAssets>,
+        saved_game: Option<Res<SavedGame>>,
+    ) {
+        commands.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: game_assets.tile_covered,
+                    custom_size: Some(Vec2::splat(SPINNER_SIZE)),
+                    ..Default::default()
+                },
+                texture: tile_texture(game_options.grid_shape, &game_assets),
+                transform: Transform::from_xyz(0., 0., SPINNER_Z),
+                ..Default::default()
+            },
+            LoadingSpinner,
+            OnGameScreen,
+        ));
+
+        let game_options = GameOptions {
+            share_code: saved_game
+                .as_deref()
+                .map(|saved_game| saved_game.share_code().to_string())
+                .or_else(|| game_options.share_code.clone()),
+            // The saved game has already been started, there is no safe start to show
+            safe_start: game_options.safe_start && saved_game.is_none(),
+            ..game_options.clone()
+        };
+        let task = AsyncComputeTaskPool::get().spawn(async move { generate(&game_options) });
+        commands.insert_resource(BoardGeneration(task));
+    }
+
+    fn rotate_spinner(time: Res<Time>, mut spinners: Query<&mut Transform, With<LoadingSpinner>>) {
+        for mut transform in spinners.iter_mut() {
+            transform.rotate_z(-SPINNER_SPEED * time.delta_seconds());
+        }
+    }
+
+    /// Spawns the board once its generation has finished and starts the game
+    #[allow(clippy::too_many_arguments)]
+    fn poll_board_generation(
+        mut commands: Commands,
+        mut generation: ResMut<BoardGeneration>,
+        window: Query<&Window>,
+        mut game_options: ResMut<GameOptions>,
+        game_assets: Res<GameAssets>,
+        mut game_state: ResMut<NextState<GameState>>,
+        mut app_state: ResMut<NextState<AppState>>,
+        mut board_initialized_evw: EventWriter<BoardInitialized>,
+        previous_boards: Query<Entity, With<BoardRoot>>,
+    ) {
+        let Some(generated) = block_on(future::poll_once(&mut generation.0)) else {
+            return;
+        };
+
+        commands.remove_resource::<BoardGeneration>();
+        let (tile_map, start) = match generated {
+            Ok(generated) => generated,
+            Err(GenerationError::ShareCode(err)) => {
+                // Back to the options, where another code can be entered
+                warn!("Could not load the shared board: {err}");
+                game_options.share_code = None;
+                commands.remove_resource::<SavedGame>();
+                game_state.set(GameState::Options);
+                return;
+            }
+            Err(err) => {
+                warn!("Could not generate the board: {err}");
+                app_state.set(AppState::Menu);
+                return;
+            }
+        };
+        let board_entity = Self::start_game(
+            &mut commands,
+            window.single(),
+            &game_options,
+            &game_assets,
+            tile_map,
+            start,
+        );
+        // A restarted game kept the finished board on screen until now
+        despawn_all_except(&mut commands, board_entity, &previous_boards);
+        board_initialized_evw.send(BoardInitialized);
+        game_state.set(GameState::Playing);
+    }
+
+    /// Spawns the entities of a generated board and inserts the `Board` resource,
+    /// returning the root entity of the board
+    pub fn start_game(
+        commands: &mut Commands,
+        window: &Window,
+        game_options: &GameOptions,
+        game_assets: &GameAssets,
+        tile_map: TileMap,
+        start: Option<UVec2>,
+    ) -> Entity {
+        let tile_size = Self::fit_tile_size(window, game_options, tile_map.size());
+
+        let board_size = board::board_size(
+            tile_map.size(),
+            tile_size,
+            game_options.tile_padding,
+            game_options.grid_shape,
+        );
+        let board_position = Vec3::new(-board_size.x / 2., -board_size.y / 2., BACKGROUND_Z);
+
+        let board_entity = commands
+            .spawn((
+                Name::new("Board"),
+                SpatialBundle {
+                    transform: Transform::from_translation(board_position),
+                    ..Default::default()
+                },
+                BoardRoot,
+                OnGameScreen,
+            ))
+            .with_children(|parent| {
+                parent.spawn((
+                    SpriteBundle {
+                        sprite: Sprite {
+                            custom_size: Some(board_size),
+                            ..Default::default()
+                        },
+                        texture: game_assets.board_gradient.clone(),
+                        transform: Transform::from_xyz(
+                            board_size.x / 2.,
+                            board_size.y / 2.,
+                            BACKGROUND_Z,
+                        ),
+                        ..Default::default()
+                    },
+                    BoardBackground,
+                ));
+
+                Self::spawn_border(parent, board_size, game_assets);
+                if game_options.wrap_around {
+                    Self::spawn_seams(parent, board_size, game_options.tile_padding, game_assets);
+                }
+            })
+            .id();
+
+        let size = tile_map.size();
+        let tile_padding = game_options.tile_padding;
+        let custom_size = Some(Vec2::splat(tile_size));
+        let texture = tile_texture(game_options.grid_shape, game_assets);
+
+        let tile_count = (size.x * size.y) as usize;
+        let mut tiles = Vec::with_capacity(tile_count);
+        let mut covers = Vec::with_capacity(tile_count);
+        let mut contents = Vec::with_capacity(tile_count);
+        let mut tile_atlas_sprites = Vec::with_capacity(tile_count);
+        let cover_atlas_sprite = atlas_sprite(game_assets, TileSpriteIndex::Cover, tile_size);
+
+        for (position, tile) in tile_map.iter_with_pos() {
+            let tile_type = tile.tile_type;
+
+            let sprite = SpriteBundle {
+                sprite: Sprite {
+                    color: game_assets.tile_uncovered,
+                    custom_size,
+                    ..Default::default()
+                },
+                texture: texture.clone(),
+                transform: Transform::from_translation(
+                    board::tile_offset(
+                        position.as_ivec2(),
+                        size,
+                        tile_size,
+                        tile_padding,
+                        game_options.grid_shape,
+                    )
+                    .extend(TILE_Z),
+                ),
+                ..Default::default()
+            };
+
+            // The safe start tile is highlighted by a cover in the uncovered color
+            let cover_color = if start == Some(position) {
+                game_assets.tile_uncovered
+            } else {
+                game_assets.tile_covered
+            };
+
+            tiles.push((sprite, Position(position.as_ivec2()), Tile(tile_type)));
+            covers.push(Self::cover_bundle(
+                position,
+                tile_size,
+                texture.clone(),
+                cover_color,
+            ));
+
+            // Sprite sheet graphics already show the mine or bomb count
+            let tile_atlas_sprite =
+                atlas_sprite(game_assets, tile_sprite_index(tile_type), tile_size);
+            contents.push(if tile_atlas_sprite.is_some() {
+                None
+            } else {
+                Self::spawn_tile_content(
+                    commands,
+                    tile_type,
+                    tile_size,
+                    texture.clone(),
+                    game_assets,
+                )
+            });
+            tile_atlas_sprites.push(tile_atlas_sprite);
+        }
+
+        let safe_region = start.map(|position| {
+            commands.insert_resource(SafeStartHighlight(Timer::from_seconds(
+                SAFE_START_HIGHLIGHT_DURATION,
+                TimerMode::Once,
+            )));
+
+            tile_map.region_around(position, 1)
+        });
+
+        let mut board = Board {
+            tile_map,
+            position: board_position,
+            size: board_size,
+            tile_size,
+            tile_padding,
+            safe_region,
+            lives: game_options.lives.max(1),
+            board_quality_score: 0.,
+        };
+        board.update_quality_score();
+
+        // Tiles and covers are spawned in batches, the board is only inserted
+        // once the tile map knows the entities of its tiles
+        commands.add(move |world: &mut World| {
+            let tile_entities = world.spawn_batch(tiles).collect::<Vec<_>>();
+            let cover_entities = world.spawn_batch(covers).collect::<Vec<_>>();
+
+            // Both batches were spawned in the order of the tile map
+            for (((((position, tile), &tile_entity), cover_entity), content), tile_atlas_sprite) in
+                board
+                    .tile_map
+                    .iter_mut_with_pos()
+                    .zip(&tile_entities)
+                    .zip(cover_entities)
+                    .zip(contents)
+                    .zip(tile_atlas_sprites)
+            {
+                tile.entity = Some(tile_entity);
+                tile.cover = Some(cover_entity);
+
+                if let Some(tile_atlas_sprite) = tile_atlas_sprite {
+                    world.entity_mut(tile_entity).insert(tile_atlas_sprite);
+                }
+                if let Some(cover_atlas_sprite) = cover_atlas_sprite.clone() {
+                    world.entity_mut(cover_entity).insert(cover_atlas_sprite);
+                }
+
+                if start == Some(position) {
+                    world.entity_mut(cover_entity).insert(SafeStartCover);
+                }
+
+                let mut tile_entity = world.entity_mut(tile_entity);
+                tile_entity.push_children(&[cover_entity]);
+                if let Some(content) = content {
+                    tile_entity.push_children(&[content]);
+                }
+            }
+
+            world.entity_mut(board_entity).push_children(&tile_entities);
+            world.insert_resource(board.counts());
+            world.insert_resource(board);
+        });
+        commands.insert_resource(GameTimer::default());
+        match game_options.game_mode {
+            GameMode::Classic | GameMode::Versus => commands.remove_resource::<CountdownTimer>(),
+            GameMode::RushMode => commands.insert_resource(CountdownTimer::default()),
+        }
+        commands.insert_resource(CameraController::default());
+        commands.insert_resource(UndoStack::default());
+        commands.insert_resource(Replay::default());
+        commands.insert_resource(GameEventLog::default());
+
+        board_entity
+    }
 
-            if let Some(flag_entity) = tile.flag.take() {
-                commands.entity(flag_entity).despawn_recursive();
-            } else {
-                let custom_size = Some(Vec2::splat(tile_size));
-                let flag_entity = commands
-                    .spawn((
-                        SpriteBundle {
-                            sprite: Sprite {
-                                custom_size,
-                                color: game_assets.tile_flagged,
-                                ..Default::default()
-                            },
-                            transform: Transform::from_xyz(0., 0., FLAG_Z),
-                            ..Default::default()
-                        },
-                        Position(event.position),
-                        Flag,
-                    ))
-                    .id();
+    /// Returns the size of the tiles, adaptive tiles are as large as possible
+    /// while fitting the board and its padding in the window
+    fn fit_tile_size(window: &Window, game_options: &GameOptions, board_size: UVec2) -> f32 {
+        match game_options.tile_size {
+            TileSize::Fixed(size) => size,
+            TileSize::Adaptive { min, max } => {
+                let padding = 2. * game_options.board_padding;
+                let tile_width = (window.width() - padding) / board_size.x as f32;
+                let tile_height = (window.height() - 2. * hud::HUD_HEIGHT - padding)
+                    / board::rows_height(board_size.y, game_options.grid_shape);
 
-                commands
-                    .entity(tile.entity.unwrap())
-                    .push_children(&[flag_entity]);
-                tile.flag = Some(flag_entity);
+                (tile_width.min(tile_height) - game_options.tile_padding).clamp(min, max)
             }
         }
     }
 
-    fn check_finished(
-        mut commands: Commands,
-        board: Res<Board>,
-        mut game_state: ResMut<NextState<GameState>>,
+    /// Spawns faint lines along the edges of a wrap-around board
+    /// showing they connect to the opposite side
+    fn spawn_seams(
+        parent: &mut ChildBuilder,
+        board_size: Vec2,
+        tile_padding: f32,
+        game_assets: &GameAssets,
     ) {
-        if board.all_revealed() {
-            commands.insert_resource(GameResult(true));
-            game_state.set(GameState::Finished);
+        let color = game_assets.tile_uncovered.with_a(0.3);
+        let offset = SEAM_WIDTH / 2. + tile_padding;
+        let horizontal = Vec2::new(board_size.x, SEAM_WIDTH);
+        let vertical = Vec2::new(SEAM_WIDTH, board_size.y);
+
+        for (size, position) in [
+            (horizontal, Vec2::new(board_size.x / 2., -offset)),
+            (
+                horizontal,
+                Vec2::new(board_size.x / 2., board_size.y + offset),
+            ),
+            (vertical, Vec2::new(-offset, board_size.y / 2.)),
+            (
+                vertical,
+                Vec2::new(board_size.x + offset, board_size.y / 2.),
+            ),
+        ] {
+            parent.spawn((
+                SpriteBundle {
+                    sprite: Sprite {
+                        color,
+                        custom_size: Some(size),
+                        ..Default::default()
+                    },
+                    transform: Transform::from_translation(position.extend(BACKGROUND_Z)),
+                    ..Default::default()
+                },
+                Seam,
+            ));
+        }
+    }
+
+    /// Spawns the four segments of the frame around the board, the top and bottom
+    /// segments span the corners. Nothing is spawned for a thickness of 0
+    fn spawn_border(parent: &mut ChildBuilder, board_size: Vec2, game_assets: &GameAssets) {
+        let thickness = game_assets.board_border_thickness;
+        if thickness <= 0. {
+            return;
+        }
+
+        let offset = thickness / 2.;
+        let horizontal = Vec2::new(board_size.x + 2. * thickness, thickness);
+        let vertical = Vec2::new(thickness, board_size.y);
+
+        for (size, position) in [
+            (
+                horizontal,
+                Vec2::new(board_size.x / 2., board_size.y + offset),
+            ),
+            (horizontal, Vec2::new(board_size.x / 2., -offset)),
+            (vertical, Vec2::new(-offset, board_size.y / 2.)),
+            (
+                vertical,
+                Vec2::new(board_size.x + offset, board_size.y / 2.),
+            ),
+        ] {
+            parent.spawn((
+                SpriteBundle {
+                    sprite: Sprite {
+                        color: game_assets.board_border_color,
+                        custom_size: Some(size),
+                        ..Default::default()
+                    },
+                    transform: Transform::from_translation(position.extend(BACKGROUND_Z)),
+                    ..Default::default()
+                },
+                BoardBorder,
+            ));
         }
     }
 
-    fn start_game(
+    /// Resizes the tiles to fit the board in a resized window or after the board has
+    /// been expanded, moving the existing entities rather than spawning the board again
+    #[allow(clippy::too_many_arguments, clippy::type_complexity)]
+    fn board_relayout(
         mut commands: Commands,
+        mut window_resized_evr: EventReader<WindowResized>,
+        mut board_expand_evr: EventReader<BoardExpand>,
         window: Query<&Window>,
-        board: Option<Res<Board>>,
+        mut board: ResMut<Board>,
         game_options: Res<GameOptions>,
         game_assets: Res<GameAssets>,
+        mut root: Query<
+            (Entity, &mut Transform),
+            (With<BoardRoot>, Without<Tile>, Without<BoardBackground>),
+        >,
+        mut background: Query<
+            (&mut Sprite, &mut Transform),
+            (With<BoardBackground>, Without<Tile>, Without<BoardRoot>),
+        >,
+        mut tiles: Query<
+            (&Position, &mut Transform, Option<&Children>),
+            (With<Tile>, Without<BoardRoot>, Without<BoardBackground>),
+        >,
+        mut sprites: Query<
+            &mut Sprite,
+            (
+                Or<(With<Position>, With<Mine>, With<CountSprite>)>,
+                Without<BoardBackground>,
+            ),
+        >,
+        mut texts: Query<&mut Text>,
+        seams: Query<Entity, Or<(With<Seam>, With<BoardBorder>)>>,
     ) {
-        if board.is_some() {
+        let resized = window_resized_evr.read().last().is_some();
+        let expanded = board_expand_evr.read().last().is_some();
+        if !resized && !expanded {
             return;
         }
 
-        let tile_size = match game_options.tile_size {
-            TileSize::Fixed(size) => size,
-            TileSize::Adaptive { min, max } => {
-                let window = &window.single();
-                let tile_width = window.width() / game_options.size.x as f32;
-                let tile_height = window.height() / game_options.size.y as f32;
+        let tile_size = Self::fit_tile_size(window.single(), &game_options, board.tile_map.size());
+        if tile_size == board.tile_size && !expanded {
+            return;
+        }
 
-                (tile_width.min(tile_height) - game_options.tile_padding).clamp(min, max)
+        let size = board.tile_map.size();
+        let shape = board.tile_map.shape();
+        let board_size = board::board_size(size, tile_size, board.tile_padding, shape);
+        let board_position = Vec3::new(-board_size.x / 2., -board_size.y / 2., BACKGROUND_Z);
+
+        board.tile_size = tile_size;
+        board.size = board_size;
+        board.position = board_position;
+
+        for (root_entity, mut transform) in root.iter_mut() {
+            transform.translation = board_position;
+
+            for seam in seams.iter() {
+                commands.entity(seam).despawn_recursive();
             }
-        };
+            commands.entity(root_entity).with_children(|parent| {
+                Self::spawn_border(parent, board_size, &game_assets);
+                if game_options.wrap_around {
+                    Self::spawn_seams(parent, board_size, board.tile_padding, &game_assets);
+                }
+            });
+        }
 
-        let mut tile_map = TileMap::empty(game_options.size);
-        tile_map.set_bombs(game_options.bomb_count);
+        for (mut sprite, mut transform) in background.iter_mut() {
+            sprite.custom_size = Some(board_size);
+            transform.translation = (board_size / 2.).extend(transform.translation.z);
+        }
 
-        #[cfg(feature = "debug")]
-        log::info!("{:?}", tile_map);
+        for (position, mut transform, children) in tiles.iter_mut() {
+            transform.translation =
+                board::tile_offset(position.0, size, tile_size, board.tile_padding, shape)
+                    .extend(transform.translation.z);
 
-        let board_size = tile_map.size().as_vec2() * (tile_size + game_options.tile_padding)
-            - game_options.tile_padding;
-        let board_position = Vec3::new(-board_size.x / 2., -board_size.y / 2., BACKGROUND_Z);
+            for &child in children.into_iter().flatten() {
+                if let Ok(mut text) = texts.get_mut(child) {
+                    text.sections[0].style.font_size = count_font_size(tile_size);
+                }
+            }
+        }
 
-        let board_entity = commands
-            .spawn((
-                Name::new("Board"),
-                SpatialBundle {
-                    transform: Transform::from_translation(board_position),
-                    ..Default::default()
-                },
-                OnGameScreen,
-            ))
-            .with_children(|parent| {
-                parent.spawn(SpriteBundle {
-                    sprite: Sprite {
-                        color: game_assets.board,
-                        custom_size: Some(board_size),
-                        ..Default::default()
-                    },
-                    transform: Transform::from_xyz(
-                        board_size.x / 2.,
-                        board_size.y / 2.,
-                        BACKGROUND_Z,
-                    ),
-                    ..Default::default()
-                });
-            })
-            .id();
+        for mut sprite in sprites.iter_mut() {
+            sprite.custom_size = Some(Vec2::splat(tile_size));
+        }
+    }
 
-        let size = game_options.size;
-        let tile_padding = game_options.tile_padding;
-        let custom_size = Some(Vec2::splat(tile_size));
+    /// Applies the colors of the current `GameAssets` to the sprites
+    /// and bomb counts of the board after the color scheme has changed
+    #[allow(clippy::type_complexity)]
+    fn recolor_board(
+        mut sprites: Query<
+            (
+                &mut Sprite,
+                AnyOf<(
+                    &BoardBackground,
+                    &BoardBorder,
+                    &Tile,
+                    &Cover,
+                    &Flag,
+                    &Question,
+                    &Mine,
+                )>,
+                Has<SafeStartCover>,
+            ),
+            // Sprite sheet graphics keep their own colors
+            Without<TextureAtlas>,
+        >,
+        tiles: Query<(&Tile, &Children)>,
+        mut texts: Query<&mut Text>,
+        game_assets: Res<GameAssets>,
+    ) {
+        for (mut sprite, kind, safe_start) in sprites.iter_mut() {
+            sprite.color = match kind {
+                // The gradient texture holds the colors of the background
+                (Some(_), ..) => Color::WHITE,
+                (_, Some(_), ..) => game_assets.board_border_color,
+                (_, _, Some(_), ..) => game_assets.tile_uncovered,
+                (_, _, _, Some(_), ..) if safe_start => game_assets.tile_uncovered,
+                (_, _, _, Some(_), ..) => game_assets.tile_covered,
+                (_, _, _, _, Some(_), ..) => game_assets.tile_flagged,
+                (_, _, _, _, _, Some(_), _) => game_assets.tile_questioned,
+                _ => game_assets.tile_mine,
+            };
+        }
 
-        let cover = SpriteBundle {
-            sprite: Sprite {
-                custom_size,
-                color: game_assets.tile_covered,
-                ..Default::default()
-            },
-            transform: Transform::from_xyz(0., 0., COVER_Z),
-            ..Default::default()
-        };
+        for (tile, children) in tiles.iter() {
+            let TileType::Number(count) = tile.0 else {
+                continue;
+            };
 
-        for y in 0..size.y {
-            for x in 0..size.x {
-                let position = UVec2::new(x, y);
-                let tile = tile_map.get_tile_mut(position).unwrap();
+            for &child in children.iter() {
+                if let Ok(mut text) = texts.get_mut(child) {
+                    text.sections[0].style.color = game_assets.count_color(count);
+                }
+            }
+        }
+    }
 
-                let sprite = SpriteBundle {
-                    sprite: Sprite {
-                        color: game_assets.tile_uncovered,
-                        custom_size,
-                        ..Default::default()
-                    },
-                    transform: Transform::from_xyz(
-                        x as f32 * (tile_size + tile_padding) + (tile_size / 2.),
-                        (size.y - y - 1) as f32 * (tile_size + tile_padding) + (tile_size / 2.),
-                        TILE_Z,
-                    ),
-                    ..Default::default()
-                };
+    /// Places the bombs avoiding the given position and spawns
+    /// the contents of all tiles afterwards
+    fn generate_board(
+        commands: &mut Commands,
+        board: &mut Board,
+        excluded: UVec2,
+        game_options: &GameOptions,
+        game_assets: &GameAssets,
+    ) {
+        let seed = game_options.seed.unwrap_or_else(|| thread_rng().gen());
+        for attempt in 0..MAX_GENERATION_ATTEMPTS {
+            if let Err(err) = board.tile_map.set_bombs_avoiding(
+                game_options.bomb_count,
+                excluded,
+                seed.wrapping_add(attempt),
+            ) {
+                warn!("Could not place the bombs: {err}");
+                return;
+            }
 
-                let tile_entity = commands
-                    .spawn((sprite, Position(position), Tile(tile.tile_type)))
-                    .id();
+            if !game_options.require_logical_start || board.tile_map.is_solvable_from(excluded) {
+                break;
+            }
+        }
 
-                let cover_entity = commands
-                    .spawn((cover.clone(), Position(position), Cover))
-                    .id();
+        board.update_quality_score();
 
-                tile.entity = Some(tile_entity);
-                tile.cover = Some(cover_entity);
+        #[cfg(feature = "debug")]
+        log::info!("{:?}", board.tile_map);
 
-                let mut children = vec![cover_entity];
-
-                match tile.tile_type {
-                    TileType::Bomb => {
-                        children.push(
-                            commands
-                                .spawn(SpriteBundle {
-                                    sprite: Sprite {
-                                        custom_size,
-                                        color: game_assets.tile_mine,
-                                        ..Default::default()
-                                    },
-                                    transform: Transform::from_xyz(0., 0., BOMB_COUNT_Z),
-                                    ..Default::default()
-                                })
-                                .id(),
-                        );
-                    }
-                    TileType::Number(count) => {
-                        children.push(
-                            commands
-                                .spawn(Text2dBundle {
-                                    text: Text::from_section(
-                                        count.to_string(),
-                                        TextStyle {
-                                            font: game_assets.tile_count_font.clone(),
-                                            font_size: tile_size,
-                                            color: game_assets.count_color(count),
-                                        },
-                                    ),
-                                    transform: Transform::from_xyz(0., 0., BOMB_COUNT_Z),
-                                    ..Default::default()
-                                })
-                                .id(),
-                        );
-                    }
-                    TileType::Empty => {}
-                }
+        for tile in board.tile_map.iter() {
+            let Some(tile_entity) = tile.entity else {
+                continue;
+            };
 
-                commands.entity(tile_entity).push_children(&children);
-                commands.entity(board_entity).push_children(&[tile_entity]);
+            commands.entity(tile_entity).insert(Tile(tile.tile_type));
+            if let Some(content) = Self::spawn_tile_content(
+                commands,
+                tile.tile_type,
+                board.tile_size,
+                tile_texture(board.tile_map.shape(), game_assets),
+                game_assets,
+            ) {
+                commands.entity(tile_entity).push_children(&[content]);
             }
         }
+    }
 
-        if game_options.safe_start {
-            let position = tile_map.find_empty_tile().unwrap();
-            let tile = tile_map.get_tile_mut(position).unwrap();
+    /// Spawns the mine sprite or bomb count text displayed on a tile
+    fn spawn_tile_content(
+        commands: &mut Commands,
+        tile_type: TileType,
+        tile_size: f32,
+        texture: Handle<Image>,
+        game_assets: &GameAssets,
+    ) -> Option<Entity> {
+        let custom_size = Some(Vec2::splat(tile_size));
 
-            let new_cover = commands
-                .spawn((
-                    SpriteBundle {
-                        sprite: Sprite {
-                            custom_size,
-                            color: game_assets.tile_uncovered,
+        match tile_type {
+            TileType::Bomb => Some(
+                commands
+                    .spawn((
+                        SpriteBundle {
+                            sprite: Sprite {
+                                custom_size,
+                                color: game_assets.tile_mine,
+                                ..Default::default()
+                            },
+                            texture,
+                            transform: Transform::from_xyz(0., 0., BOMB_COUNT_Z),
+                            ..Default::default()
+                        },
+                        Mine,
+                    ))
+                    .id(),
+            ),
+            TileType::Number(count) => Some(match game_assets.tile_count_sprite(count) {
+                Some(count_sprite) => commands
+                    .spawn((
+                        SpriteBundle {
+                            sprite: Sprite {
+                                custom_size,
+                                ..Default::default()
+                            },
+                            texture: count_sprite,
+                            transform: Transform::from_xyz(0., 0., BOMB_COUNT_Z),
                             ..Default::default()
                         },
-                        transform: Transform::from_xyz(0., 0., COVER_Z),
+                        CountSprite,
+                    ))
+                    .id(),
+                None => commands
+                    .spawn(Text2dBundle {
+                        text: Text::from_section(
+                            count.to_string(),
+                            TextStyle {
+                                font: game_assets.tile_count_font.clone(),
+                                font_size: count_font_size(tile_size),
+                                color: game_assets.count_color(count),
+                            },
+                        ),
+                        transform: Transform::from_xyz(0., 0., BOMB_COUNT_Z),
                         ..Default::default()
-                    },
-                    Position(position),
-                    Cover,
-                ))
-                .id();
-
-            let old_cover = tile.cover.replace(new_cover).unwrap();
-            commands.entity(old_cover).despawn_recursive();
-            commands
-                .entity(tile.entity.unwrap())
-                .push_children(&[new_cover]);
+                    })
+                    .id(),
+            }),
+            TileType::Empty => None,
         }
-
-        commands.insert_resource(Board {
-            tile_map,
-            position: board_position.xy(),
-            size: board_size,
-            tile_size,
-            tile_padding,
-        });
     }
 
     fn overlay_button_color(
@@ -515,11 +2147,16 @@ impl GamePlugin {
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn button_actions(
         mut commands: Commands,
         interaction_query: Query<(&Interaction, &OverlayButtonAction), Changed<Interaction>>,
         mut app_state: ResMut<NextState<AppState>>,
         mut game_state: ResMut<NextState<GameState>>,
+        board: Option<Res<Board>>,
+        game_options: Res<GameOptions>,
+        game_timer: Option<Res<GameTimer>>,
+        countdown_timer: Option<Res<CountdownTimer>>,
     ) {
         for (interaction, action) in interaction_query.iter() {
             if *interaction != Interaction::Pressed {
@@ -528,10 +2165,25 @@ impl GamePlugin {
             match action {
                 OverlayButtonAction::Restart => {
                     commands.remove_resource::<Board>();
-                    game_state.set(GameState::Playing);
+                    game_state.set(GameState::Generating);
                 }
                 OverlayButtonAction::ReturnToMenu => {
-                    // TODO: Find a way to keep current game
+                    // Games in progress can be continued from the menu
+                    let saved_game = board.as_deref().zip(game_timer.as_deref()).and_then(
+                        |(board, game_timer)| {
+                            SavedGame::from_board(
+                                board,
+                                &game_options,
+                                game_timer,
+                                countdown_timer.as_deref(),
+                            )
+                        },
+                    );
+                    match saved_game {
+                        Some(saved_game) => commands.insert_resource(saved_game),
+                        None => commands.remove_resource::<SavedGame>(),
+                    }
+
                     commands.remove_resource::<Board>();
                     game_state.set(GameState::Inactive);
                     app_state.set(AppState::Menu);
@@ -539,6 +2191,14 @@ impl GamePlugin {
                 OverlayButtonAction::Continue => {
                     game_state.set(GameState::Playing);
                 }
+                OverlayButtonAction::Spectate => {
+                    game_state.set(GameState::Spectating);
+                }
+                OverlayButtonAction::WatchReplay => {
+                    commands.remove_resource::<Board>();
+                    game_state.set(GameState::Inactive);
+                    app_state.set(AppState::Replay);
+                }
             }
         }
     }
@@ -582,11 +2242,7 @@ impl GamePlugin {
         let pause_text = commands
             .spawn(TextBundle::from_section(
                 "Paused",
-                TextStyle {
-                    font: ui_assets.font.clone(),
-                    font_size: 80.,
-                    color: ui_assets.foreground,
-                },
+                ui_assets.style_h1_scaled(2.),
             ))
             .id();
 
@@ -636,28 +2292,69 @@ impl GamePlugin {
             })
             .id();
 
+        let spectate_button = commands
+            .spawn((
+                ButtonBundle {
+                    style: button_style.clone(),
+                    background_color: ui_assets.background_alt.into(),
+                    ..Default::default()
+                },
+                OverlayButtonAction::Spectate,
+            ))
+            .with_children(|parent| {
+                parent.spawn(TextBundle::from_section(
+                    "Watch AI finish",
+                    TextStyle {
+                        font_size: 30.,
+                        ..button_text_style.clone()
+                    },
+                ));
+            })
+            .id();
+
         commands.entity(overlay).push_children(&[column]);
         commands.entity(column).push_children(&[
             pause_text,
             continue_button,
+            spectate_button,
             return_to_menu_button,
         ]);
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn game_finished(
         mut commands: Commands,
         game_result: Res<GameResult>,
         mut board: ResMut<Board>,
+        game_options: Res<GameOptions>,
+        game_timer: Res<GameTimer>,
+        countdown_timer: Option<Res<CountdownTimer>>,
+        opponent: Option<Res<versus::Opponent>>,
+        spectated: Option<Res<spectate::Spectated>>,
+        mut statistics: ResMut<stats::Statistics>,
+        mut replay: ResMut<Replay>,
         ui_assets: Res<UiAssets>,
+        #[cfg(feature = "audio")] sounds: sound::Sounds,
     ) {
-        for tile in board.tile_map.iter_mut() {
-            if tile.is_bomb() {
-                if let Some(cover_entity) = tile.cover.take() {
-                    commands.entity(cover_entity).despawn_recursive();
-                }
+        // Games finished by the solver were not played by the player
+        if spectated.is_none() {
+            statistics.record(
+                game_options.category(),
+                game_result.0,
+                game_timer.0.as_secs_f32(),
+            );
+            if let Err(err) = statistics.save() {
+                warn!("Could not save statistics: {err}");
             }
         }
 
+        #[cfg(feature = "audio")]
+        if game_result.0 {
+            sounds.play(&mut commands, sound::Sound::Win);
+        }
+
+        despawn_bomb_covers(&mut commands, &mut board.tile_map);
+
         let result_color = if game_result.0 {
             Color::GREEN
         } else {
@@ -698,6 +2395,10 @@ impl GamePlugin {
 
         let result_text = if game_result.0 {
             "You win!"
+        } else if countdown_timer.is_some_and(|countdown_timer| countdown_timer.0.finished()) {
+            "Time's Up!"
+        } else if opponent.is_some_and(|opponent| opponent.is_won()) {
+            "The CPU wins!"
         } else {
             "You lose!"
         };
@@ -706,9 +2407,8 @@ impl GamePlugin {
             .spawn(TextBundle::from_section(
                 result_text,
                 TextStyle {
-                    font: ui_assets.font.clone(),
-                    font_size: 80.,
                     color: result_color,
+                    ..ui_assets.style_h1_scaled(2.)
                 },
             ))
             .id();
@@ -759,12 +2459,193 @@ impl GamePlugin {
             })
             .id();
 
+        let watch_replay_button = commands
+            .spawn((
+                ButtonBundle {
+                    style: button_style.clone(),
+                    background_color: ui_assets.background_alt.into(),
+                    ..Default::default()
+                },
+                OverlayButtonAction::WatchReplay,
+            ))
+            .with_children(|parent| {
+                parent.spawn(TextBundle::from_section(
+                    "Watch Replay",
+                    button_text_style.clone(),
+                ));
+            })
+            .id();
+
         commands.entity(finished_screen).push_children(&[column]);
 
+        commands.entity(column).push_children(&[text_entity]);
+
+        // Show the seed so the board can be shared
+        if let Some(seed) = game_options.seed {
+            let seed_entity = commands
+                .spawn(TextBundle::from_section(
+                    format!("Seed: {seed}"),
+                    ui_assets.style_h1(),
+                ))
+                .id();
+            commands.entity(column).push_children(&[seed_entity]);
+        }
+
+        // Share codes are long, so they are also logged to be copied
+        let share_code = board.tile_map.to_ascii_share();
+        info!("Share code: {share_code}");
+        replay.share_code = Some(share_code.clone());
+        let share_code_entity = commands
+            .spawn(
+                TextBundle::from_section(
+                    format!("Share code: {share_code}"),
+                    TextStyle {
+                        font_size: 20.,
+                        ..ui_assets.style_h1()
+                    },
+                )
+                .with_style(Style {
+                    max_width: Val::Px(600.),
+                    ..Default::default()
+                }),
+            )
+            .id();
+        commands.entity(column).push_children(&[share_code_entity]);
+
+        #[cfg(feature = "debug")]
+        {
+            let stats_entity = commands
+                .spawn(TextBundle::from_section(
+                    format!("Board: {}", board.tile_map.statistics()),
+                    TextStyle {
+                        font_size: 20.,
+                        ..ui_assets.style_h1()
+                    },
+                ))
+                .id();
+            commands.entity(column).push_children(&[stats_entity]);
+        }
+
         commands.entity(column).push_children(&[
-            text_entity,
             restart_button,
+            watch_replay_button,
             return_to_menu_button,
         ]);
     }
 }
+
+/// Uncovers the bombs of a finished board
+fn despawn_bomb_covers(commands: &mut Commands, tile_map: &mut TileMap) {
+    // FIXME: after Board entity-map refactor, update this loop to use cover_map
+    for tile in tile_map.iter_mut() {
+        if tile.is_bomb() {
+            if let Some(cover_entity) = tile.cover.take() {
+                commands.entity(cover_entity).despawn_recursive();
+            }
+        }
+    }
+}
+
+/// Builds the tile map of a new game, placing the bombs right away on a safe start.
+/// Returns the map and the tile the game starts from
+fn generate(game_options: &GameOptions) -> Result<(TileMap, Option<UVec2>), GenerationError> {
+    // A code that cannot be decoded is reported instead of playing a random board
+    let shared_tile_map = game_options
+        .share_code
+        .as_deref()
+        .map(TileMap::from_share_code)
+        .transpose()?;
+
+    let mut tile_map = shared_tile_map
+        .unwrap_or_else(|| TileMap::empty(game_options.size))
+        .with_wrap_around(game_options.wrap_around)
+        .with_shape(game_options.grid_shape)
+        .with_neighbor_mode(game_options.neighbor_mode);
+
+    // Without a safe start, bombs are placed on the first reveal
+    // so the first clicked tile can never be a bomb
+    let mut start = None;
+    if tile_map.is_generated() {
+        // Shared boards are played as they are
+        if game_options.safe_start {
+            start = tile_map.find_empty_tile();
+        }
+    } else if game_options.safe_start {
+        for attempt in 0..MAX_GENERATION_ATTEMPTS {
+            match game_options.seed {
+                Some(seed) => tile_map
+                    .set_bombs_seeded(game_options.bomb_count, seed.wrapping_add(attempt))?,
+                None => tile_map.set_bombs(game_options.bomb_count)?,
+            }
+
+            start = tile_map.find_empty_tile();
+            let opening_met = attempt >= MAX_OPENING_ATTEMPTS
+                || start.map_or(0, |start| tile_map.opening_size(start))
+                    >= game_options.safe_start_min_opening;
+            let logical_start_met = !game_options.require_logical_start
+                || start.is_some_and(|start| tile_map.is_solvable_from(start));
+            if opening_met && logical_start_met {
+                break;
+            }
+        }
+
+        #[cfg(feature = "debug")]
+        log::info!("{:?}", tile_map);
+    }
+
+    Ok((tile_map, start))
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::CommandQueue;
+
+    use super::*;
+
+    #[test]
+    fn generate_rejects_invalid_share_code() {
+        let game_options = GameOptions {
+            share_code: Some("not a share code".to_string()),
+            ..default()
+        };
+
+        assert!(matches!(
+            generate(&game_options),
+            Err(GenerationError::ShareCode(ShareCodeError::InvalidCharacter))
+        ));
+    }
+
+    #[test]
+    fn generate_plays_valid_share_code() {
+        let shared = TileMap::new(UVec2::new(6, 5), 7).unwrap();
+        let game_options = GameOptions {
+            share_code: Some(shared.to_ascii_share()),
+            ..default()
+        };
+
+        let (tile_map, _) = generate(&game_options).unwrap();
+        assert_eq!(tile_map.size(), UVec2::new(6, 5));
+        assert_eq!(tile_map.count_mines(), 7);
+    }
+
+    #[test]
+    #[ignore = "FIXME: cover_map refactor"]
+    fn game_finished_despawns_bomb_covers() {
+        let mut world = World::new();
+        let mut tile_map = TileMap::with_bombs(UVec2::new(3, 3), &[UVec2::ZERO]);
+        for tile in tile_map.iter_mut() {
+            tile.cover = Some(world.spawn_empty().id());
+        }
+        let bomb_cover = tile_map.get_tile(UVec2::ZERO).unwrap().cover.unwrap();
+        let number_cover = tile_map.get_tile(UVec2::new(1, 1)).unwrap().cover.unwrap();
+
+        let mut queue = CommandQueue::default();
+        despawn_bomb_covers(&mut Commands::new(&mut queue, &world), &mut tile_map);
+        queue.apply(&mut world);
+
+        // TODO: look the covers up through the entity map once the Board has a cover_map
+        assert!(world.get_entity(bomb_cover).is_none());
+        assert!(world.get_entity(number_cover).is_some());
+        assert!(tile_map.get_tile(UVec2::ZERO).unwrap().cover.is_none());
+    }
+}
@@ -1,31 +1,344 @@
-use bevy::prelude::*;
+use std::{fs, path::PathBuf};
 
-use crate::{style::ui_assets::UiAssets, util::despawn_all, AppState};
+use bevy::{
+    app::AppExit,
+    input::mouse::{MouseScrollUnit, MouseWheel},
+    prelude::*,
+};
+use rand::{thread_rng, Rng as _};
+use serde::{Deserialize, Serialize};
 
-use super::{board::Board, GameState};
+use crate::{
+    style::prelude::*,
+    util::{despawn_all, remove_resource},
+    AppState,
+};
 
-#[derive(Clone, Resource)]
+use super::{
+    board::Board,
+    presets::PresetLibrary,
+    tilemap::{GridShape, NeighborMode, TileMap},
+    GameState,
+};
+
+/// File name of the configuration file holding the game options
+const CONFIG_FILE: &str = "config.toml";
+
+#[derive(Clone, PartialEq, Resource, Serialize, Deserialize)]
+#[serde(default)]
 pub struct GameOptions {
     pub size: UVec2,
     pub bomb_count: u32,
+    /// Preset the size and bomb count were picked from, stored by its name
+    #[serde(with = "preset_serde")]
+    pub preset: Option<Preset>,
     pub safe_start: bool,
+    /// Fewest tiles the first reveal of a safe start opens up,
+    /// boards with a smaller opening are generated again
+    pub safe_start_min_opening: u32,
+    /// Seed used to place the bombs, boards are random when `None`
+    #[serde(with = "seed_format")]
+    pub seed: Option<u64>,
+    /// Whether the keyboard cursor wraps around the edges of the board
+    pub wrap_cursor: bool,
+    /// Disables undoing reveals
+    pub competitive: bool,
+    /// Reveals the mines in rings around the mine that lost the game
+    /// before showing the finished screen
+    pub reveal_cascade: bool,
+    /// Regenerates boards that require guessing right after the first reveal
+    pub require_logical_start: bool,
+    /// Connects the edges of the board to the opposite side
+    pub wrap_around: bool,
+    pub grid_shape: GridShape,
+    /// Which surrounding tiles of a square tile count as its neighbors
+    pub neighbor_mode: NeighborMode,
+    pub game_mode: GameMode,
+    /// Bombs that can be revealed before the game is lost
+    pub lives: u8,
+    /// Board loaded from a share code, replaces placing the bombs randomly
+    #[serde(skip)]
+    pub share_code: Option<String>,
     pub tile_size: TileSize,
+    /// Space between neighboring tiles in pixels
     pub tile_padding: f32,
+    /// Space between the board and the edges of the window in pixels
+    pub board_padding: f32,
 }
 
 impl Default for GameOptions {
     fn default() -> Self {
         Self {
-            size: Preset::Beginner.size(),
-            bomb_count: Preset::Beginner.bomb_count(),
+            size: Beginner.size(),
+            bomb_count: Beginner.bomb_count(),
+            preset: Some(Preset::Beginner),
             safe_start: true,
+            safe_start_min_opening: 0,
+            seed: None,
+            wrap_cursor: false,
+            competitive: false,
+            reveal_cascade: false,
+            require_logical_start: false,
+            wrap_around: false,
+            grid_shape: GridShape::default(),
+            neighbor_mode: NeighborMode::default(),
+            game_mode: GameMode::default(),
+            lives: 1,
+            share_code: None,
             tile_size: TileSize::default(),
             tile_padding: 2.,
+            board_padding: 10.,
+        }
+    }
+}
+
+impl GameOptions {
+    /// Returns the name under which games with these options are grouped,
+    /// the preset name if the size and bomb count match a preset
+    pub fn category(&self) -> String {
+        Preset::values(None)
+            .find(|preset| preset.size() == self.size && preset.bomb_count() == self.bomb_count)
+            .map(|preset| preset.to_string())
+            .unwrap_or_else(|| format!("{}x{}/{}", self.size.x, self.size.y, self.bomb_count))
+    }
+
+    /// Returns the largest bomb count that fits the board, a safe start
+    /// keeps a tile and its neighbors free
+    pub fn max_bomb_count(&self) -> u32 {
+        let tile_count = self.size.x * self.size.y;
+        if self.safe_start {
+            tile_count.saturating_sub(9)
+        } else {
+            tile_count.saturating_sub(1)
+        }
+    }
+
+    /// Returns why a game cannot be started with these options
+    pub fn validation_error(&self) -> Option<String> {
+        if self.size.x < MIN_BOARD_SIZE || self.size.y < MIN_BOARD_SIZE {
+            Some(format!(
+                "The board needs at least {MIN_BOARD_SIZE} rows and columns"
+            ))
+        } else if self.bomb_count < MIN_BOMB_COUNT {
+            Some(format!("At least {MIN_BOMB_COUNT} bomb is needed"))
+        } else if self.bomb_count > self.max_bomb_count() {
+            Some(format!(
+                "At most {} bombs fit this board",
+                self.max_bomb_count()
+            ))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the percentage of tiles that are bombs
+    pub fn density(&self) -> f32 {
+        let tile_count = self.size.x * self.size.y;
+        if tile_count == 0 {
+            return 0.;
+        }
+
+        self.bomb_count as f32 / tile_count as f32 * 100.
+    }
+
+    pub fn save(&self) -> std::io::Result<()> {
+        let path = config_path();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let contents = toml::to_string_pretty(self)
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))?;
+        fs::write(path, contents)
+    }
+}
+
+/// Builds `GameOptions` from the Beginner configuration, checking the board
+/// can be played once it is built
+#[derive(Default)]
+pub struct GameOptionsBuilder(GameOptions);
+
+impl GameOptionsBuilder {
+    pub fn rows(mut self, rows: u32) -> Self {
+        self.0.size.y = rows;
+        self.0.preset = None;
+        self
+    }
+
+    pub fn cols(mut self, cols: u32) -> Self {
+        self.0.size.x = cols;
+        self.0.preset = None;
+        self
+    }
+
+    pub fn bombs(mut self, bombs: u32) -> Self {
+        self.0.bomb_count = bombs;
+        self.0.preset = None;
+        self
+    }
+
+    pub fn safe_start(mut self, safe_start: bool) -> Self {
+        self.0.safe_start = safe_start;
+        self
+    }
+
+    // Only tests configure the rendering and seed of built options so far
+    #[cfg(test)]
+    pub fn tile_size(mut self, tile_size: TileSize) -> Self {
+        self.0.tile_size = tile_size;
+        self
+    }
+
+    /// Sets the space between neighboring tiles
+    #[cfg(test)]
+    pub fn padding(mut self, padding: f32) -> Self {
+        self.0.tile_padding = padding;
+        self
+    }
+
+    #[cfg(test)]
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.0.seed = Some(seed);
+        self
+    }
+
+    pub fn build(self) -> Result<GameOptions, OptionsError> {
+        // The other checks multiply the board size, which must fit a `u32`
+        if self.0.size.x.checked_mul(self.0.size.y).is_none() {
+            return Err(OptionsError(format!(
+                "A board of {}x{} tiles is too large",
+                self.0.size.x, self.0.size.y
+            )));
+        }
+
+        match self.0.validation_error() {
+            Some(err) => Err(OptionsError(err)),
+            None => Ok(self.0),
+        }
+    }
+}
+
+impl From<GameOptions> for GameOptionsBuilder {
+    fn from(game_options: GameOptions) -> Self {
+        Self(game_options)
+    }
+}
+
+/// Error returned when building options a game cannot be started with
+#[derive(Debug, Clone, PartialEq)]
+pub struct OptionsError(pub String);
+
+impl std::fmt::Display for OptionsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for OptionsError {}
+
+/// TOML integers are signed, so seeds are stored as strings to keep all 64 bits
+mod seed_format {
+    use serde::{de::Error as _, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(seed: &Option<u64>, serializer: S) -> Result<S::Ok, S::Error> {
+        match seed {
+            Some(seed) => serializer.serialize_some(&seed.to_string()),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<u64>, D::Error> {
+        Option::<String>::deserialize(deserializer)?
+            .map(|seed| seed.parse().map_err(D::Error::custom))
+            .transpose()
+    }
+}
+
+/// Presets are stored by their name, names that are not a built-in preset load as `None`
+mod preset_serde {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    use super::Preset;
+
+    pub fn serialize<S: Serializer>(
+        preset: &Option<Preset>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match preset {
+            Some(preset) => serializer.serialize_some(&preset.to_string()),
+            None => serializer.serialize_none(),
         }
     }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Preset>, D::Error> {
+        Ok(Option::<String>::deserialize(deserializer)?.and_then(|name| name.parse().ok()))
+    }
+}
+
+/// Returns the path of the configuration file, a `config.toml` in the
+/// working directory takes precedence over the one in the config directory
+fn config_path() -> PathBuf {
+    let local = PathBuf::from(CONFIG_FILE);
+    if local.exists() {
+        return local;
+    }
+
+    dirs::config_dir()
+        .unwrap_or_default()
+        .join("minesweeper")
+        .join(CONFIG_FILE)
+}
+
+/// Smallest number of rows and columns of a board
+const MIN_BOARD_SIZE: u32 = 2;
+
+/// Smallest number of bombs on a board
+const MIN_BOMB_COUNT: u32 = 1;
+
+/// Most lives a game can be started with
+const MAX_LIVES: u8 = 9;
+
+/// Largest opening a safe start can be required to have
+const MAX_SAFE_START_OPENING: u32 = 100;
+
+/// Tiles the required safe start opening changes by per button press
+const SAFE_START_OPENING_STEP: u32 = 5;
+
+/// Largest space between neighboring tiles in pixels
+const MAX_TILE_PADDING: f32 = 10.;
+
+/// Pixels the tile padding changes by per button press
+const TILE_PADDING_STEP: f32 = 0.5;
+
+/// Largest space between the board and the window edges in pixels
+const MAX_BOARD_PADDING: f32 = 100.;
+
+/// Pixels the board padding changes by per button press
+const BOARD_PADDING_STEP: f32 = 5.;
+
+/// Pixels the options screen scrolls per scrolled line
+const SCROLL_LINE_HEIGHT: f32 = 30.;
+
+/// Width of the scroll bar of the options screen in pixels
+const SCROLL_BAR_WIDTH: f32 = 6.;
+
+/// Rules a game is played by
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum GameMode {
+    #[default]
+    Classic,
+    /// The board has to be cleared before a countdown runs out
+    RushMode,
+    /// The board has to be cleared before a computer opponent clears a board
+    /// of the same size with different bombs
+    Versus,
 }
 
-#[derive(Clone)]
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
 pub enum TileSize {
     Fixed(f32),
     Adaptive { min: f32, max: f32 },
@@ -37,64 +350,263 @@ impl Default for TileSize {
     }
 }
 
-#[derive(Clone, Copy, PartialEq)]
+/// Name and board configuration of a preset
+pub trait PresetInfo {
+    fn name(&self) -> &str;
+    fn size(&self) -> UVec2;
+    fn bomb_count(&self) -> u32;
+}
+
+pub struct Beginner;
+
+impl PresetInfo for Beginner {
+    fn name(&self) -> &str {
+        "Beginner"
+    }
+
+    fn size(&self) -> UVec2 {
+        UVec2::new(9, 9)
+    }
+
+    fn bomb_count(&self) -> u32 {
+        10
+    }
+}
+
+pub struct Intermediate;
+
+impl PresetInfo for Intermediate {
+    fn name(&self) -> &str {
+        "Intermediate"
+    }
+
+    fn size(&self) -> UVec2 {
+        UVec2::new(16, 16)
+    }
+
+    fn bomb_count(&self) -> u32 {
+        40
+    }
+}
+
+pub struct Expert;
+
+impl PresetInfo for Expert {
+    fn name(&self) -> &str {
+        "Expert"
+    }
+
+    fn size(&self) -> UVec2 {
+        UVec2::new(30, 16)
+    }
+
+    fn bomb_count(&self) -> u32 {
+        99
+    }
+}
+
+/// One of the built-in presets, whose configuration is described by the
+/// `PresetInfo` of their struct, or a custom preset saved by the player
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
 pub enum Preset {
     Beginner,
     Intermediate,
     Expert,
+    /// A named board configuration saved by the player, see `Preset::custom`
+    Custom {
+        name: String,
+        size: UVec2,
+        bomb_count: u32,
+    },
 }
 
 impl Preset {
-    pub fn values() -> impl Iterator<Item = Preset> {
+    /// Returns the built-in presets followed by the custom presets of the library
+    pub fn values(library: Option<&PresetLibrary>) -> impl Iterator<Item = Preset> + '_ {
         [Preset::Beginner, Preset::Intermediate, Preset::Expert]
-            .iter()
-            .copied()
+            .into_iter()
+            .chain(
+                library
+                    .into_iter()
+                    .flat_map(|library| library.0.iter().cloned()),
+            )
     }
 
-    fn size(&self) -> UVec2 {
-        match self {
-            Preset::Beginner => (9, 9).into(),
-            Preset::Intermediate => (16, 16).into(),
-            Preset::Expert => (30, 16).into(),
+    /// Creates a custom preset, checking the board could be played like `GameOptions`
+    pub fn custom(name: &str, size: UVec2, bomb_count: u32) -> Result<Self, InvalidPreset> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(InvalidPreset("The preset needs a name".to_string()));
         }
+
+        // Presets can be played without a safe start, which fits the most bombs
+        GameOptionsBuilder::default()
+            .cols(size.x)
+            .rows(size.y)
+            .bombs(bomb_count)
+            .safe_start(false)
+            .build()
+            .map_err(|err| InvalidPreset(err.0))?;
+
+        Ok(Preset::Custom {
+            name: name.to_string(),
+            size,
+            bomb_count,
+        })
     }
 
-    fn bomb_count(&self) -> u32 {
+    /// Returns the description of a built-in preset
+    fn builtin(&self) -> Option<&'static dyn PresetInfo> {
         match self {
-            Preset::Beginner => 10,
-            Preset::Intermediate => 40,
-            Preset::Expert => 99,
+            Preset::Beginner => Some(&Beginner),
+            Preset::Intermediate => Some(&Intermediate),
+            Preset::Expert => Some(&Expert),
+            Preset::Custom { .. } => None,
         }
     }
 }
 
-impl ToString for Preset {
-    fn to_string(&self) -> String {
-        match self {
-            Preset::Beginner => "Beginner".to_string(),
-            Preset::Intermediate => "Intermediate".to_string(),
-            Preset::Expert => "Expert".to_string(),
+impl PresetInfo for Preset {
+    fn name(&self) -> &str {
+        match (self, self.builtin()) {
+            (Preset::Custom { name, .. }, _) => name,
+            (_, Some(info)) => info.name(),
+            _ => unreachable!("every preset but a custom preset is built in"),
+        }
+    }
+
+    fn size(&self) -> UVec2 {
+        match (self, self.builtin()) {
+            (Preset::Custom { size, .. }, _) => *size,
+            (_, Some(info)) => info.size(),
+            _ => unreachable!("every preset but a custom preset is built in"),
+        }
+    }
+
+    fn bomb_count(&self) -> u32 {
+        match (self, self.builtin()) {
+            (Preset::Custom { bomb_count, .. }, _) => *bomb_count,
+            (_, Some(info)) => info.bomb_count(),
+            _ => unreachable!("every preset but a custom preset is built in"),
         }
     }
 }
 
+impl std::fmt::Display for Preset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl std::str::FromStr for Preset {
+    type Err = UnknownPreset;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Preset::values(None)
+            .find(|preset| preset.to_string().eq_ignore_ascii_case(s))
+            .ok_or_else(|| UnknownPreset(s.to_string()))
+    }
+}
+
+impl TryFrom<&str> for Preset {
+    type Error = UnknownPreset;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+/// Error returned when parsing a preset name that does not exist
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnknownPreset(pub String);
+
+impl std::fmt::Display for UnknownPreset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "unknown preset \"{}\"", self.0)
+    }
+}
+
+impl std::error::Error for UnknownPreset {}
+
+/// Error returned when creating a custom preset that could not be played
+#[derive(Debug, Clone, PartialEq)]
+pub struct InvalidPreset(pub String);
+
+impl std::fmt::Display for InvalidPreset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for InvalidPreset {}
+
 #[derive(Component)]
 struct OnOptionsScreen;
 
+/// Part of the options screen the settings column scrolls in when the
+/// window is too small to show all settings
+#[derive(Component)]
+struct ScrollView;
+
+/// Column scrolled within the `ScrollView`, the offset in pixels
+/// from its top to the top of the view
+#[derive(Component, Default, PartialEq)]
+struct ScrollContent(f32);
+
+/// Part of the scroll bar showing which part of the settings is visible
+#[derive(Component)]
+struct ScrollBarThumb;
+
 #[derive(Component)]
 enum SettingsTextField {
     Rows,
     Columns,
     BombCount,
+    /// Percentage of tiles that are bombs, derived from the other fields
+    Density,
+    Seed,
+    /// Tile padding in tenths of a pixel
+    TilePadding,
+    BoardPadding,
+    SafeStartOpening,
+    Lives,
+    NeighborMode,
+    /// Why the game cannot be started, empty when the options are valid
+    Validation,
 }
 
+/// Density in percent above which the density is shown as a warning
+const DENSITY_WARNING: f32 = 25.;
+
+/// Density in percent above which the density is shown as dangerous
+const DENSITY_DANGER: f32 = 40.;
+
 #[derive(PartialEq, Component)]
 enum SettingsButtonAction {
     ChangeRows(bool),
     ChangeColumns(bool),
     ChangeBombCount(bool),
+    ChangeTilePadding(bool),
+    ChangeBoardPadding(bool),
+    ChangeSafeStartOpening(bool),
+    ChangeLives(bool),
     Preset(Preset),
+    SaveAsPreset,
+    /// Deletes the custom preset at the given index of the `PresetLibrary`
+    DeletePreset(usize),
     SafeStartToggle,
+    SeedToggle,
+    WrapCursorToggle,
+    CompetitiveToggle,
+    RevealCascadeToggle,
+    LogicalStartToggle,
+    WrapAroundToggle,
+    HexagonalToggle,
+    CycleNeighborMode,
+    RushModeToggle,
+    VersusToggle,
+    EnterShareCode,
+    LoadShareCode,
     StartGame,
     Back,
 }
@@ -102,25 +614,98 @@ enum SettingsButtonAction {
 #[derive(Component)]
 struct SelectedPreset;
 
+/// Row holding the preset buttons, rebuilt when the `PresetLibrary` changes
+#[derive(Component)]
+struct PresetsRow;
+
+#[derive(Component)]
+struct PresetNameField;
+
+/// Name typed for a new custom preset, exists while the name is being entered
+#[derive(Resource, Default)]
+struct PresetNamePrompt(String);
+
+#[derive(Component)]
+struct ShareCodeField;
+
+/// Number of trailing characters of a share code shown while typing
+const SHARE_CODE_VISIBLE: usize = 16;
+
+/// Share code being typed, exists while the code is being entered
+#[derive(Resource, Default)]
+struct ShareCodePrompt {
+    code: String,
+    /// Why the last attempt to load the code failed
+    error: Option<String>,
+}
+
 pub struct GameOptionsPlugin;
 
 impl Plugin for GameOptionsPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(GameState::Options), Self::setup_options)
+        app.add_systems(Startup, Self::load_config)
+            .add_systems(Last, Self::save_config.run_if(on_event::<AppExit>()))
+            .add_systems(OnEnter(GameState::Options), Self::setup_options)
             .add_systems(OnExit(GameState::Options), despawn_all::<OnOptionsScreen>)
             .add_systems(
                 Update,
                 (
                     Self::preset_button_color,
                     Self::button_actions,
-                    Self::display_options,
+                    Self::seed_input.run_if(
+                        not(resource_exists::<PresetNamePrompt>)
+                            .and_then(not(resource_exists::<ShareCodePrompt>)),
+                    ),
+                    Self::preset_name_input.run_if(resource_exists::<PresetNamePrompt>),
+                    Self::share_code_input.run_if(resource_exists::<ShareCodePrompt>),
+                    Self::display_preset_name,
+                    Self::display_share_code,
+                    Self::rebuild_presets.run_if(resource_changed::<PresetLibrary>),
+                    Self::display_options.run_if(resource_changed::<GameOptions>),
+                )
+                    // The settings controls are also shown in the main menu
+                    .run_if(any_with_component::<SettingsButtonAction>),
+            )
+            .add_systems(
+                Update,
+                Self::scroll_options.run_if(in_state(GameState::Options)),
+            )
+            .add_systems(
+                Update,
+                // Drop an unfinished preset name or share code when the settings are closed
+                (
+                    remove_resource::<PresetNamePrompt>.run_if(resource_exists::<PresetNamePrompt>),
+                    remove_resource::<ShareCodePrompt>.run_if(resource_exists::<ShareCodePrompt>),
                 )
-                    .run_if(in_state(GameState::Options)),
+                    .run_if(not(any_with_component::<SettingsButtonAction>)),
             );
     }
 }
 
 impl GameOptionsPlugin {
+    /// Falls back to the default options when the configuration file is missing,
+    /// cannot be parsed or describes a board that cannot be played
+    fn load_config(mut commands: Commands) {
+        let game_options = fs::read_to_string(config_path())
+            .ok()
+            .and_then(|contents| toml::from_str::<GameOptions>(&contents).ok())
+            .and_then(|game_options| {
+                GameOptionsBuilder::from(game_options)
+                    .build()
+                    .map_err(|err| warn!("Ignoring the configured options: {err}"))
+                    .ok()
+            })
+            .unwrap_or_default();
+
+        commands.insert_resource::<GameOptions>(game_options);
+    }
+
+    fn save_config(game_options: Res<GameOptions>) {
+        if let Err(err) = game_options.save() {
+            warn!("Could not save game options: {err}");
+        }
+    }
+
     fn preset_button_color(
         mut interaction_query: Query<
             (
@@ -137,6 +722,19 @@ impl GameOptionsPlugin {
         for (interaction, mut color, action, selected) in interaction_query.iter_mut() {
             let on = match action {
                 SettingsButtonAction::SafeStartToggle => game_options.safe_start,
+                SettingsButtonAction::SeedToggle => game_options.seed.is_some(),
+                SettingsButtonAction::WrapCursorToggle => game_options.wrap_cursor,
+                SettingsButtonAction::CompetitiveToggle => game_options.competitive,
+                SettingsButtonAction::RevealCascadeToggle => game_options.reveal_cascade,
+                SettingsButtonAction::LogicalStartToggle => game_options.require_logical_start,
+                SettingsButtonAction::WrapAroundToggle => game_options.wrap_around,
+                SettingsButtonAction::HexagonalToggle => {
+                    game_options.grid_shape == GridShape::Hexagonal
+                }
+                SettingsButtonAction::RushModeToggle => {
+                    game_options.game_mode == GameMode::RushMode
+                }
+                SettingsButtonAction::VersusToggle => game_options.game_mode == GameMode::Versus,
                 SettingsButtonAction::Preset(_) => selected.is_some(),
                 _ => continue,
             };
@@ -149,6 +747,7 @@ impl GameOptionsPlugin {
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn button_actions(
         mut commands: Commands,
         interaction_query: Query<
@@ -157,8 +756,10 @@ impl GameOptionsPlugin {
         >,
         mut selected_query: Query<(Entity, &mut BackgroundColor), With<SelectedPreset>>,
         mut game_options: ResMut<GameOptions>,
+        mut preset_library: ResMut<PresetLibrary>,
         mut app_state: ResMut<NextState<AppState>>,
         mut game_state: ResMut<NextState<GameState>>,
+        mut share_code_prompt: Option<ResMut<ShareCodePrompt>>,
         ui_assets: Res<UiAssets>,
     ) {
         for (interaction, action, entity) in interaction_query.iter() {
@@ -167,8 +768,12 @@ impl GameOptionsPlugin {
             }
             match action {
                 SettingsButtonAction::StartGame => {
+                    if game_options.validation_error().is_some() {
+                        continue;
+                    }
+
                     commands.remove_resource::<Board>();
-                    game_state.set(GameState::Playing);
+                    game_state.set(GameState::Generating);
                 }
                 SettingsButtonAction::Back => {
                     app_state.set(AppState::Menu);
@@ -182,10 +787,13 @@ impl GameOptionsPlugin {
                         *selected_color = ui_assets.background_alt.into();
                     }
 
+                    game_options.preset = None;
+                    game_options.share_code = None;
                     if *increase {
                         game_options.size.y = game_options.size.y.saturating_add(1);
                     } else {
-                        game_options.size.y = game_options.size.y.saturating_sub(1);
+                        game_options.size.y =
+                            game_options.size.y.saturating_sub(1).max(MIN_BOARD_SIZE);
                     }
                 }
                 SettingsButtonAction::ChangeColumns(increase) => {
@@ -196,10 +804,13 @@ impl GameOptionsPlugin {
                         *selected_color = ui_assets.background_alt.into();
                     }
 
+                    game_options.preset = None;
+                    game_options.share_code = None;
                     if *increase {
                         game_options.size.x = game_options.size.x.saturating_add(1);
                     } else {
-                        game_options.size.x = game_options.size.x.saturating_sub(1);
+                        game_options.size.x =
+                            game_options.size.x.saturating_sub(1).max(MIN_BOARD_SIZE);
                     }
                 }
                 SettingsButtonAction::ChangeBombCount(increase) => {
@@ -210,12 +821,52 @@ impl GameOptionsPlugin {
                         *selected_color = ui_assets.background_alt.into();
                     }
 
+                    game_options.preset = None;
+                    game_options.share_code = None;
                     if *increase {
                         game_options.bomb_count = game_options.bomb_count.saturating_add(1);
                     } else {
-                        game_options.bomb_count = game_options.bomb_count.saturating_sub(1);
+                        game_options.bomb_count = game_options
+                            .bomb_count
+                            .saturating_sub(1)
+                            .max(MIN_BOMB_COUNT);
                     }
                 }
+                SettingsButtonAction::ChangeTilePadding(increase) => {
+                    let step = if *increase {
+                        TILE_PADDING_STEP
+                    } else {
+                        -TILE_PADDING_STEP
+                    };
+                    game_options.tile_padding =
+                        (game_options.tile_padding + step).clamp(0., MAX_TILE_PADDING);
+                }
+                SettingsButtonAction::ChangeBoardPadding(increase) => {
+                    let step = if *increase {
+                        BOARD_PADDING_STEP
+                    } else {
+                        -BOARD_PADDING_STEP
+                    };
+                    game_options.board_padding =
+                        (game_options.board_padding + step).clamp(0., MAX_BOARD_PADDING);
+                }
+                SettingsButtonAction::ChangeSafeStartOpening(increase) => {
+                    game_options.safe_start_min_opening = if *increase {
+                        (game_options.safe_start_min_opening + SAFE_START_OPENING_STEP)
+                            .min(MAX_SAFE_START_OPENING)
+                    } else {
+                        game_options
+                            .safe_start_min_opening
+                            .saturating_sub(SAFE_START_OPENING_STEP)
+                    };
+                }
+                SettingsButtonAction::ChangeLives(increase) => {
+                    game_options.lives = if *increase {
+                        game_options.lives.saturating_add(1).min(MAX_LIVES)
+                    } else {
+                        game_options.lives.saturating_sub(1).max(1)
+                    };
+                }
                 SettingsButtonAction::Preset(preset) => {
                     if let Ok((selected_entity, mut selected_color)) =
                         selected_query.get_single_mut()
@@ -232,14 +883,111 @@ impl GameOptionsPlugin {
 
                     game_options.size = preset.size();
                     game_options.bomb_count = preset.bomb_count();
+                    game_options.preset = Some(preset.clone());
+                    game_options.share_code = None;
 
                     return;
                 }
+                SettingsButtonAction::SaveAsPreset => {
+                    commands.remove_resource::<ShareCodePrompt>();
+                    commands.insert_resource(PresetNamePrompt::default());
+                }
+                SettingsButtonAction::EnterShareCode => {
+                    commands.remove_resource::<PresetNamePrompt>();
+                    commands.insert_resource(ShareCodePrompt::default());
+                }
+                SettingsButtonAction::LoadShareCode => {
+                    if let Some(prompt) = share_code_prompt.as_deref_mut() {
+                        load_share_code(&mut commands, prompt, &mut game_options);
+                    }
+                }
+                SettingsButtonAction::DeletePreset(index) => {
+                    if *index < preset_library.0.len() {
+                        preset_library.0.remove(*index);
+
+                        if let Err(err) = preset_library.save() {
+                            warn!("Could not save presets: {err}");
+                        }
+                    }
+                }
                 SettingsButtonAction::SafeStartToggle => {
                     game_options.safe_start = !game_options.safe_start;
                 }
+                SettingsButtonAction::WrapAroundToggle => {
+                    game_options.wrap_around = !game_options.wrap_around;
+                }
+                SettingsButtonAction::HexagonalToggle => {
+                    game_options.grid_shape = match game_options.grid_shape {
+                        GridShape::Square => GridShape::Hexagonal,
+                        GridShape::Hexagonal => GridShape::Square,
+                    };
+                }
+                SettingsButtonAction::CycleNeighborMode => {
+                    game_options.neighbor_mode = game_options.neighbor_mode.next();
+                }
+                SettingsButtonAction::RushModeToggle => {
+                    game_options.game_mode = match game_options.game_mode {
+                        GameMode::RushMode => GameMode::Classic,
+                        GameMode::Classic | GameMode::Versus => GameMode::RushMode,
+                    };
+                }
+                SettingsButtonAction::VersusToggle => {
+                    game_options.game_mode = match game_options.game_mode {
+                        GameMode::Versus => GameMode::Classic,
+                        GameMode::Classic | GameMode::RushMode => GameMode::Versus,
+                    };
+                }
+                SettingsButtonAction::LogicalStartToggle => {
+                    game_options.require_logical_start = !game_options.require_logical_start;
+                }
+                SettingsButtonAction::CompetitiveToggle => {
+                    game_options.competitive = !game_options.competitive;
+                }
+                SettingsButtonAction::RevealCascadeToggle => {
+                    game_options.reveal_cascade = !game_options.reveal_cascade;
+                }
+                SettingsButtonAction::WrapCursorToggle => {
+                    game_options.wrap_cursor = !game_options.wrap_cursor;
+                }
+                SettingsButtonAction::SeedToggle => {
+                    game_options.seed = match game_options.seed {
+                        Some(_) => None,
+                        None => Some(thread_rng().gen()),
+                    };
+                }
+            }
+        }
+    }
+
+    /// Edits a fixed seed by typing digits, backspace removes the last digit
+    fn seed_input(
+        mut received_character_evr: EventReader<ReceivedCharacter>,
+        keyboard_input: Res<ButtonInput<KeyCode>>,
+        mut game_options: ResMut<GameOptions>,
+    ) {
+        let Some(mut seed) = game_options.seed else {
+            received_character_evr.clear();
+            return;
+        };
+
+        for event in received_character_evr.read() {
+            for digit in event.char.chars().filter_map(|c| c.to_digit(10)) {
+                if let Some(new_seed) = seed
+                    .checked_mul(10)
+                    .and_then(|seed| seed.checked_add(digit as u64))
+                {
+                    seed = new_seed;
+                }
             }
         }
+
+        if keyboard_input.just_pressed(KeyCode::Backspace) {
+            seed /= 10;
+        }
+
+        if game_options.seed != Some(seed) {
+            game_options.seed = Some(seed);
+        }
     }
 
     fn display_options(
@@ -254,32 +1002,241 @@ impl GameOptionsPlugin {
                 SettingsTextField::Rows => game_options.size.y.to_string(),
                 SettingsTextField::Columns => game_options.size.x.to_string(),
                 SettingsTextField::BombCount => game_options.bomb_count.to_string(),
+                SettingsTextField::Density => density_text(&game_options),
+                SettingsTextField::Seed => seed_text(game_options.seed),
+                SettingsTextField::TilePadding => tenths(game_options.tile_padding).to_string(),
+                SettingsTextField::BoardPadding => game_options.board_padding.to_string(),
+                SettingsTextField::SafeStartOpening => {
+                    game_options.safe_start_min_opening.to_string()
+                }
+                SettingsTextField::Lives => game_options.lives.to_string(),
+                SettingsTextField::NeighborMode => game_options.neighbor_mode.to_string(),
+                SettingsTextField::Validation => {
+                    game_options.validation_error().unwrap_or_default()
+                }
+            };
+
+            if let SettingsTextField::Density = field {
+                text.sections[0].style.color = density_color(game_options.density(), &ui_assets);
+            }
+        }
+
+        // The start button is disabled while the options are invalid
+        for (button_action, mut color, _) in buttons_query.iter_mut() {
+            if let SettingsButtonAction::StartGame = button_action {
+                *color = if game_options.validation_error().is_some() {
+                    ui_assets.background_alt.into()
+                } else {
+                    ui_assets.accent_alt.into()
+                };
             }
         }
 
-        for preset in Preset::values() {
+        for (button_action, mut color, entity) in buttons_query.iter_mut() {
+            let SettingsButtonAction::Preset(preset) = button_action else {
+                continue;
+            };
+
             if preset.size() != game_options.size || preset.bomb_count() != game_options.bomb_count
             {
                 continue;
             }
-            for (button_action, mut color, entity) in buttons_query.iter_mut() {
-                if *button_action != SettingsButtonAction::Preset(preset) {
-                    continue;
+
+            commands.entity(entity).insert(SelectedPreset);
+            *color = ui_assets.accent.into();
+            return;
+        }
+    }
+
+    /// Types the name of a new custom preset, enter saves the
+    /// current board configuration under that name and escape cancels
+    fn preset_name_input(
+        mut commands: Commands,
+        mut received_character_evr: EventReader<ReceivedCharacter>,
+        keyboard_input: Res<ButtonInput<KeyCode>>,
+        mut prompt: ResMut<PresetNamePrompt>,
+        mut preset_library: ResMut<PresetLibrary>,
+        game_options: Res<GameOptions>,
+    ) {
+        for event in received_character_evr.read() {
+            prompt
+                .0
+                .extend(event.char.chars().filter(|c| !c.is_control()));
+        }
+
+        if keyboard_input.just_pressed(KeyCode::Backspace) {
+            prompt.0.pop();
+        }
+
+        if keyboard_input.just_pressed(KeyCode::Escape) {
+            commands.remove_resource::<PresetNamePrompt>();
+        } else if keyboard_input.just_pressed(KeyCode::Enter) {
+            let preset = match Preset::custom(&prompt.0, game_options.size, game_options.bomb_count)
+            {
+                Ok(preset) => preset,
+                Err(err) => {
+                    warn!("Could not save preset: {err}");
+                    return;
                 }
+            };
 
-                commands.entity(entity).insert(SelectedPreset);
-                *color = ui_assets.accent.into();
-                return;
+            preset_library.0.push(preset);
+            if let Err(err) = preset_library.save() {
+                warn!("Could not save presets: {err}");
             }
+
+            commands.remove_resource::<PresetNamePrompt>();
         }
     }
 
-    fn setup_options(
+    /// Types a share code, enter or the load button replaces the
+    /// board configuration with the shared board and escape cancels
+    fn share_code_input(
         mut commands: Commands,
-        game_options: Option<Res<GameOptions>>,
-        ui_assets: Res<UiAssets>,
+        mut received_character_evr: EventReader<ReceivedCharacter>,
+        keyboard_input: Res<ButtonInput<KeyCode>>,
+        mut prompt: ResMut<ShareCodePrompt>,
+        mut game_options: ResMut<GameOptions>,
     ) {
-        let game_options = match game_options {
+        for event in received_character_evr.read() {
+            prompt
+                .code
+                .extend(event.char.chars().filter(|c| c.is_ascii_hexdigit()));
+            prompt.error = None;
+        }
+
+        if keyboard_input.just_pressed(KeyCode::Backspace) {
+            prompt.code.pop();
+            prompt.error = None;
+        }
+
+        if keyboard_input.just_pressed(KeyCode::Escape) {
+            commands.remove_resource::<ShareCodePrompt>();
+        } else if keyboard_input.just_pressed(KeyCode::Enter) {
+            load_share_code(&mut commands, &mut prompt, &mut game_options);
+        }
+    }
+
+    fn display_share_code(
+        prompt: Option<Res<ShareCodePrompt>>,
+        game_options: Res<GameOptions>,
+        mut fields_query: Query<&mut Text, With<ShareCodeField>>,
+    ) {
+        let value = match (prompt, &game_options.share_code) {
+            (Some(prompt), _) => match &prompt.error {
+                Some(error) => format!("Invalid code: {error}"),
+                None => {
+                    // Codes of large boards are too long to show completely
+                    let start = prompt.code.len().saturating_sub(SHARE_CODE_VISIBLE);
+                    format!("Code: {}_", &prompt.code[start..])
+                }
+            },
+            (None, Some(_)) => "Shared board loaded".to_string(),
+            (None, None) => String::new(),
+        };
+
+        for mut text in fields_query.iter_mut() {
+            if text.sections[0].value != value {
+                text.sections[0].value = value.clone();
+            }
+        }
+    }
+
+    fn display_preset_name(
+        prompt: Option<Res<PresetNamePrompt>>,
+        mut fields_query: Query<&mut Text, With<PresetNameField>>,
+    ) {
+        let value = match prompt {
+            Some(prompt) => format!("Name: {}_", prompt.0),
+            None => String::new(),
+        };
+
+        for mut text in fields_query.iter_mut() {
+            if text.sections[0].value != value {
+                text.sections[0].value = value.clone();
+            }
+        }
+    }
+
+    fn rebuild_presets(
+        mut commands: Commands,
+        rows_query: Query<Entity, With<PresetsRow>>,
+        game_options: Res<GameOptions>,
+        preset_library: Res<PresetLibrary>,
+        ui_assets: Res<UiAssets>,
+    ) {
+        for row in rows_query.iter() {
+            commands.entity(row).despawn_descendants();
+            spawn_preset_buttons(
+                &mut commands,
+                row,
+                &game_options,
+                &preset_library,
+                &ui_assets,
+            );
+        }
+    }
+
+    /// Scrolls the settings with the mouse wheel when they do not fit the window,
+    /// and sizes the scroll bar thumb to the visible part of the settings
+    #[allow(clippy::type_complexity)]
+    fn scroll_options(
+        mut mouse_wheel_evr: EventReader<MouseWheel>,
+        views: Query<&Node, With<ScrollView>>,
+        mut contents: Query<(&Node, &mut Style, &mut ScrollContent)>,
+        mut thumbs: Query<
+            (&mut Style, &mut Visibility),
+            (With<ScrollBarThumb>, Without<ScrollContent>),
+        >,
+    ) {
+        let (Ok(view), Ok((content, mut content_style, mut scroll))) =
+            (views.get_single(), contents.get_single_mut())
+        else {
+            return;
+        };
+
+        let lines = mouse_wheel_evr
+            .read()
+            .map(|event| match event.unit {
+                MouseScrollUnit::Line => event.y,
+                MouseScrollUnit::Pixel => event.y / SCROLL_LINE_HEIGHT,
+            })
+            .sum::<f32>();
+
+        // The layout can change size with the window, keep the offset within the content
+        let view_height = view.size().y;
+        let content_height = content.size().y;
+        let max_offset = (content_height - view_height).max(0.);
+        let offset = (scroll.0 - lines * SCROLL_LINE_HEIGHT).clamp(0., max_offset);
+        scroll.set_if_neq(ScrollContent(offset));
+        if content_style.top != Val::Px(-offset) {
+            content_style.top = Val::Px(-offset);
+        }
+
+        // Only touch the thumb when it moves, so the layout is not recomputed every frame
+        for (mut style, mut visibility) in thumbs.iter_mut() {
+            if max_offset == 0. {
+                visibility.set_if_neq(Visibility::Hidden);
+                continue;
+            }
+
+            visibility.set_if_neq(Visibility::Inherited);
+            let height = Val::Percent(view_height / content_height * 100.);
+            let top = Val::Percent(offset / content_height * 100.);
+            if style.height != height || style.top != top {
+                style.height = height;
+                style.top = top;
+            }
+        }
+    }
+
+    fn setup_options(
+        mut commands: Commands,
+        game_options: Option<Res<GameOptions>>,
+        preset_library: Res<PresetLibrary>,
+        ui_assets: Res<UiAssets>,
+    ) {
+        let game_options = match game_options {
             Some(o) => o.clone(),
             None => {
                 commands.insert_resource(GameOptions::default());
@@ -296,18 +1253,9 @@ impl GameOptionsPlugin {
             ..Default::default()
         };
 
-        let flex_row = NodeBundle {
-            style: Style {
-                flex_direction: FlexDirection::Row,
-                align_items: AlignItems::Center,
-                ..Default::default()
-            },
-            ..Default::default()
-        };
-
         let button_style = Style {
             width: Val::Px(250.),
-            height: Val::Px(65.),
+            padding: UiRect::vertical(Val::Px(10.)),
             margin: UiRect::all(Val::Px(20.)),
             justify_content: JustifyContent::Center,
             align_items: AlignItems::Center,
@@ -337,26 +1285,89 @@ impl GameOptionsPlugin {
             ))
             .id();
 
-        let settings_column = commands.spawn(flex_column.clone()).id();
+        // The settings are clipped to the window and scrolled with the mouse wheel
+        let scroll_view = commands
+            .spawn((
+                NodeBundle {
+                    style: Style {
+                        max_height: Val::Percent(100.),
+                        align_items: AlignItems::FlexStart,
+                        overflow: Overflow::clip_y(),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                ScrollView,
+            ))
+            .id();
+        let settings_column = commands
+            .spawn((
+                NodeBundle {
+                    style: Style {
+                        margin: UiRect::horizontal(Val::Px(SCROLL_BAR_WIDTH * 2.)),
+                        ..flex_column.style.clone()
+                    },
+                    ..flex_column.clone()
+                },
+                ScrollContent::default(),
+            ))
+            .id();
+        let scroll_bar = commands
+            .spawn(NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    right: Val::Px(0.),
+                    width: Val::Px(SCROLL_BAR_WIDTH),
+                    height: Val::Percent(100.),
+                    ..Default::default()
+                },
+                background_color: ui_assets.background_alt.into(),
+                ..Default::default()
+            })
+            .with_children(|parent| {
+                parent.spawn((
+                    NodeBundle {
+                        style: Style {
+                            position_type: PositionType::Absolute,
+                            width: Val::Percent(100.),
+                            ..Default::default()
+                        },
+                        background_color: ui_assets.accent.into(),
+                        ..Default::default()
+                    },
+                    ScrollBarThumb,
+                ));
+            })
+            .id();
         let title = commands
             .spawn(TextBundle::from_section(
                 "Game options",
                 ui_assets.style_title(),
             ))
             .id();
-        let heading_presets = commands
-            .spawn(TextBundle::from_section("Presets:", ui_assets.style_h1()))
+        let board_settings =
+            spawn_board_settings(&mut commands, &game_options, &preset_library, &ui_assets);
+        let validation_message = commands
+            .spawn((
+                TextBundle::from_section(
+                    game_options.validation_error().unwrap_or_default(),
+                    TextStyle {
+                        color: Color::RED,
+                        ..ui_assets.style_text_accent_alt()
+                    },
+                ),
+                SettingsTextField::Validation,
+            ))
             .id();
-        let presets_row = commands.spawn(flex_row.clone()).id();
-        let rows_row = commands.spawn(flex_row.clone()).id();
-        let columns_row = commands.spawn(flex_row.clone()).id();
-        let bomb_count_row = commands.spawn(flex_row.clone()).id();
-        let safe_start_row = commands.spawn(flex_row.clone()).id();
         let start_game_button = commands
             .spawn((
                 ButtonBundle {
                     style: button_style.clone(),
-                    background_color: ui_assets.accent_alt.into(),
+                    background_color: if game_options.validation_error().is_some() {
+                        ui_assets.background_alt.into()
+                    } else {
+                        ui_assets.accent_alt.into()
+                    },
                     ..Default::default()
                 },
                 SettingsButtonAction::StartGame,
@@ -382,194 +1393,806 @@ impl GameOptionsPlugin {
             })
             .id();
 
-        commands.entity(body).push_children(&[settings_column]);
+        commands.entity(body).push_children(&[scroll_view]);
+        commands
+            .entity(scroll_view)
+            .push_children(&[settings_column, scroll_bar]);
 
         commands.entity(settings_column).push_children(&[
             title,
-            heading_presets,
-            presets_row,
-            rows_row,
-            columns_row,
-            bomb_count_row,
-            safe_start_row,
+            board_settings,
+            validation_message,
             start_game_button,
             back_button,
         ]);
+    }
+}
 
-        for preset in Preset::values() {
-            let selected = game_options.size == preset.size()
-                && game_options.bomb_count == preset.bomb_count();
-
-            let background_color = if selected {
-                ui_assets.accent.into()
-            } else {
-                ui_assets.background_alt.into()
-            };
+/// Spawns the preset buttons and the board size, bomb count and safe start
+/// controls in a column, returning the column entity. The controls update
+/// the `GameOptions` resource wherever the column is shown
+pub fn spawn_board_settings(
+    commands: &mut Commands,
+    game_options: &GameOptions,
+    preset_library: &PresetLibrary,
+    ui_assets: &UiAssets,
+) -> Entity {
+    let flex_column = NodeBundle {
+        style: Style {
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
 
-            let button = commands
-                .spawn((
-                    ButtonBundle {
-                        style: button_style.clone(),
-                        background_color,
-                        ..Default::default()
-                    },
-                    SettingsButtonAction::Preset(preset),
-                ))
-                .with_children(|parent| {
-                    parent.spawn(TextBundle::from_section(
-                        preset.to_string(),
-                        TextStyle {
-                            color: ui_assets.foreground,
-                            ..button_text_style.clone()
-                        },
-                    ));
-                })
-                .id();
+    let flex_row = NodeBundle {
+        style: Style {
+            flex_direction: FlexDirection::Row,
+            align_items: AlignItems::Center,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
 
-            if selected {
-                commands.entity(button).insert(SelectedPreset);
-            }
+    let button_style = Style {
+        width: Val::Px(250.),
+        height: Val::Px(65.),
+        margin: UiRect::all(Val::Px(20.)),
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        ..Default::default()
+    };
 
-            commands.entity(presets_row).push_children(&[button]);
-        }
+    let button_text_style = TextStyle {
+        font_size: 40.,
+        color: ui_assets.foreground,
+        font: ui_assets.font.clone(),
+    };
 
-        let arrows_column = NodeBundle {
-            style: Style {
-                flex_direction: FlexDirection::Column,
-                align_items: AlignItems::Center,
-                height: Val::Percent(100.),
+    let settings_column = commands.spawn(flex_column).id();
+    let heading_presets = commands
+        .spawn(TextBundle::from_section("Presets:", ui_assets.style_h1()))
+        .id();
+    let presets_row = commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    flex_wrap: FlexWrap::Wrap,
+                    justify_content: JustifyContent::Center,
+                    ..flex_row.style.clone()
+                },
                 ..Default::default()
             },
-            ..Default::default()
-        };
+            PresetsRow,
+        ))
+        .id();
+    let save_preset_row = commands.spawn(flex_row.clone()).id();
+    let rows_row = commands.spawn(flex_row.clone()).id();
+    let columns_row = commands.spawn(flex_row.clone()).id();
+    let bomb_count_row = commands.spawn(flex_row.clone()).id();
+    let tile_padding_row = commands.spawn(flex_row.clone()).id();
+    let board_padding_row = commands.spawn(flex_row.clone()).id();
+    let safe_start_row = commands.spawn(flex_row.clone()).id();
+    let safe_start_opening_row = commands.spawn(flex_row.clone()).id();
+    let seed_row = commands.spawn(flex_row.clone()).id();
+    let wrap_cursor_row = commands.spawn(flex_row.clone()).id();
+    let competitive_row = commands.spawn(flex_row.clone()).id();
+    let reveal_cascade_row = commands.spawn(flex_row.clone()).id();
+    let logical_start_row = commands.spawn(flex_row.clone()).id();
+    let wrap_around_row = commands.spawn(flex_row.clone()).id();
+    let hexagonal_row = commands.spawn(flex_row.clone()).id();
+    let neighbor_mode_row = commands.spawn(flex_row.clone()).id();
+    let rush_mode_row = commands.spawn(flex_row.clone()).id();
+    let versus_row = commands.spawn(flex_row.clone()).id();
+    let lives_row = commands.spawn(flex_row.clone()).id();
+    let share_code_row = commands.spawn(flex_row.clone()).id();
 
-        let arrow_button = ButtonBundle {
-            style: Style {
-                width: Val::Px(20.),
-                height: Val::Px(20.),
-                justify_content: JustifyContent::Center,
-                align_items: AlignItems::Center,
+    commands.entity(settings_column).push_children(&[
+        heading_presets,
+        presets_row,
+        save_preset_row,
+        rows_row,
+        columns_row,
+        bomb_count_row,
+        tile_padding_row,
+        board_padding_row,
+        safe_start_row,
+        safe_start_opening_row,
+        seed_row,
+        wrap_cursor_row,
+        competitive_row,
+        reveal_cascade_row,
+        logical_start_row,
+        wrap_around_row,
+        hexagonal_row,
+        neighbor_mode_row,
+        rush_mode_row,
+        versus_row,
+        lives_row,
+        share_code_row,
+    ]);
+
+    spawn_preset_buttons(
+        commands,
+        presets_row,
+        game_options,
+        preset_library,
+        ui_assets,
+    );
+
+    let save_preset_button = commands
+        .spawn((
+            ButtonBundle {
+                style: button_style.clone(),
+                background_color: ui_assets.background_alt.into(),
                 ..Default::default()
             },
-            background_color: ui_assets.background.into(),
+            SettingsButtonAction::SaveAsPreset,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                "Save as Preset",
+                button_text_style.clone(),
+            ));
+        })
+        .id();
+    let preset_name_field = commands
+        .spawn((
+            TextBundle::from_section("", ui_assets.style_h1_accent()),
+            PresetNameField,
+        ))
+        .id();
+
+    commands
+        .entity(save_preset_row)
+        .push_children(&[save_preset_button, preset_name_field]);
+
+    let arrows_column = NodeBundle {
+        style: Style {
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            height: Val::Percent(100.),
             ..Default::default()
-        };
+        },
+        ..Default::default()
+    };
 
-        let mut load_setting =
-            |row_entity: Entity,
-             text: &str,
-             value: u32,
-             field: SettingsTextField,
-             increase_button: SettingsButtonAction,
-             decrease_button: SettingsButtonAction| {
-                let text_entity = commands
-                    .spawn(
-                        TextBundle::from_section(text, ui_assets.style_h1()).with_style(Style {
-                            width: Val::Px(250.),
-                            ..Default::default()
-                        }),
-                    )
-                    .id();
-                let field_entity = commands
-                    .spawn((
-                        TextBundle::from_section(value.to_string(), ui_assets.style_h1_accent())
-                            .with_style(Style {
-                                width: Val::Px(60.),
-                                ..Default::default()
-                            }),
-                        field,
-                    ))
-                    .id();
-                let buttons_column_entity = commands.spawn(arrows_column.clone()).id();
-                let arrow_up_button = commands
-                    .spawn((arrow_button.clone(), increase_button))
-                    .with_children(|parent| {
-                        parent.spawn(TextBundle::from_section(
-                            "▲",
-                            ui_assets.style_text_accent_alt(),
-                        ));
-                    })
-                    .id();
-                let arrow_down_button = commands
-                    .spawn((arrow_button.clone(), decrease_button))
-                    .with_children(|parent| {
-                        parent.spawn(TextBundle::from_section(
-                            "▼",
-                            ui_assets.style_text_accent_alt(),
-                        ));
-                    })
-                    .id();
-
-                commands.entity(row_entity).push_children(&[
-                    text_entity,
-                    field_entity,
-                    buttons_column_entity,
-                ]);
-
-                commands
-                    .entity(buttons_column_entity)
-                    .push_children(&[arrow_up_button, arrow_down_button]);
-            };
+    let arrow_button = ButtonBundle {
+        style: Style {
+            width: Val::Px(20.),
+            height: Val::Px(20.),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..Default::default()
+        },
+        background_color: ui_assets.background.into(),
+        ..Default::default()
+    };
 
-        load_setting(
-            rows_row,
-            "Rows:",
-            game_options.size.y,
-            SettingsTextField::Rows,
-            SettingsButtonAction::ChangeRows(true),
-            SettingsButtonAction::ChangeRows(false),
-        );
-
-        load_setting(
-            columns_row,
-            "Columns:",
-            game_options.size.x,
-            SettingsTextField::Columns,
-            SettingsButtonAction::ChangeColumns(true),
-            SettingsButtonAction::ChangeColumns(false),
-        );
-
-        load_setting(
-            bomb_count_row,
-            "Bomb count:",
-            game_options.bomb_count,
-            SettingsTextField::BombCount,
-            SettingsButtonAction::ChangeBombCount(true),
-            SettingsButtonAction::ChangeBombCount(false),
-        );
-
-        let safe_start_heading = commands
+    let mut load_setting = |row_entity: Entity,
+                            text: &str,
+                            value: u32,
+                            field: SettingsTextField,
+                            increase_button: SettingsButtonAction,
+                            decrease_button: SettingsButtonAction| {
+        let text_entity = commands
             .spawn(
-                TextBundle::from_section("Safe start:", ui_assets.style_h1()).with_style(Style {
-                    margin: UiRect::right(Val::Px(20.)),
+                TextBundle::from_section(text, ui_assets.style_h1()).with_style(Style {
+                    width: Val::Px(250.),
                     ..Default::default()
                 }),
             )
             .id();
+        let field_entity = commands
+            .spawn((
+                TextBundle::from_section(value.to_string(), ui_assets.style_h1_accent())
+                    .with_style(Style {
+                        width: Val::Px(60.),
+                        ..Default::default()
+                    }),
+                field,
+            ))
+            .id();
+        let buttons_column_entity = commands.spawn(arrows_column.clone()).id();
+        let arrow_up_button = commands
+            .spawn((arrow_button.clone(), increase_button))
+            .with_children(|parent| {
+                parent.spawn(TextBundle::from_section(
+                    "▲",
+                    ui_assets.style_text_accent_alt(),
+                ));
+            })
+            .id();
+        let arrow_down_button = commands
+            .spawn((arrow_button.clone(), decrease_button))
+            .with_children(|parent| {
+                parent.spawn(TextBundle::from_section(
+                    "▼",
+                    ui_assets.style_text_accent_alt(),
+                ));
+            })
+            .id();
+
+        commands.entity(row_entity).push_children(&[
+            text_entity,
+            field_entity,
+            buttons_column_entity,
+        ]);
+
+        commands
+            .entity(buttons_column_entity)
+            .push_children(&[arrow_up_button, arrow_down_button]);
+    };
+
+    load_setting(
+        rows_row,
+        "Rows:",
+        game_options.size.y,
+        SettingsTextField::Rows,
+        SettingsButtonAction::ChangeRows(true),
+        SettingsButtonAction::ChangeRows(false),
+    );
+
+    load_setting(
+        columns_row,
+        "Columns:",
+        game_options.size.x,
+        SettingsTextField::Columns,
+        SettingsButtonAction::ChangeColumns(true),
+        SettingsButtonAction::ChangeColumns(false),
+    );
+
+    load_setting(
+        bomb_count_row,
+        "Bomb count:",
+        game_options.bomb_count,
+        SettingsTextField::BombCount,
+        SettingsButtonAction::ChangeBombCount(true),
+        SettingsButtonAction::ChangeBombCount(false),
+    );
+
+    load_setting(
+        tile_padding_row,
+        "Tile padding:",
+        tenths(game_options.tile_padding),
+        SettingsTextField::TilePadding,
+        SettingsButtonAction::ChangeTilePadding(true),
+        SettingsButtonAction::ChangeTilePadding(false),
+    );
+
+    load_setting(
+        board_padding_row,
+        "Board padding:",
+        game_options.board_padding as u32,
+        SettingsTextField::BoardPadding,
+        SettingsButtonAction::ChangeBoardPadding(true),
+        SettingsButtonAction::ChangeBoardPadding(false),
+    );
+
+    load_setting(
+        safe_start_opening_row,
+        "Min opening:",
+        game_options.safe_start_min_opening,
+        SettingsTextField::SafeStartOpening,
+        SettingsButtonAction::ChangeSafeStartOpening(true),
+        SettingsButtonAction::ChangeSafeStartOpening(false),
+    );
+
+    load_setting(
+        lives_row,
+        "Lives:",
+        game_options.lives.into(),
+        SettingsTextField::Lives,
+        SettingsButtonAction::ChangeLives(true),
+        SettingsButtonAction::ChangeLives(false),
+    );
+
+    let density_field = commands
+        .spawn((
+            TextBundle::from_section(
+                density_text(game_options),
+                TextStyle {
+                    color: density_color(game_options.density(), ui_assets),
+                    ..ui_assets.style_h1()
+                },
+            )
+            .with_style(Style {
+                margin: UiRect::left(Val::Px(20.)),
+                ..Default::default()
+            }),
+            SettingsTextField::Density,
+        ))
+        .id();
+
+    commands
+        .entity(bomb_count_row)
+        .push_children(&[density_field]);
+
+    let (safe_start_heading, safe_start_button) = spawn_toggle(
+        commands,
+        ui_assets,
+        "Safe start:",
+        game_options.safe_start,
+        SettingsButtonAction::SafeStartToggle,
+    );
+
+    commands
+        .entity(safe_start_row)
+        .push_children(&[safe_start_heading, safe_start_button]);
+
+    let (seed_heading, seed_button) = spawn_toggle(
+        commands,
+        ui_assets,
+        "Fixed seed:",
+        game_options.seed.is_some(),
+        SettingsButtonAction::SeedToggle,
+    );
+
+    let seed_field = commands
+        .spawn((
+            TextBundle::from_section(seed_text(game_options.seed), ui_assets.style_h1_accent())
+                .with_style(Style {
+                    margin: UiRect::left(Val::Px(20.)),
+                    ..Default::default()
+                }),
+            SettingsTextField::Seed,
+        ))
+        .id();
+
+    commands
+        .entity(seed_row)
+        .push_children(&[seed_heading, seed_button, seed_field]);
+
+    let (wrap_cursor_heading, wrap_cursor_button) = spawn_toggle(
+        commands,
+        ui_assets,
+        "Wrap cursor:",
+        game_options.wrap_cursor,
+        SettingsButtonAction::WrapCursorToggle,
+    );
+
+    commands
+        .entity(wrap_cursor_row)
+        .push_children(&[wrap_cursor_heading, wrap_cursor_button]);
+
+    let (competitive_heading, competitive_button) = spawn_toggle(
+        commands,
+        ui_assets,
+        "Competitive:",
+        game_options.competitive,
+        SettingsButtonAction::CompetitiveToggle,
+    );
+
+    commands
+        .entity(competitive_row)
+        .push_children(&[competitive_heading, competitive_button]);
+
+    let (reveal_cascade_heading, reveal_cascade_button) = spawn_toggle(
+        commands,
+        ui_assets,
+        "Mine cascade:",
+        game_options.reveal_cascade,
+        SettingsButtonAction::RevealCascadeToggle,
+    );
+
+    commands
+        .entity(reveal_cascade_row)
+        .push_children(&[reveal_cascade_heading, reveal_cascade_button]);
+
+    let (logical_start_heading, logical_start_button) = spawn_toggle(
+        commands,
+        ui_assets,
+        "Logical start:",
+        game_options.require_logical_start,
+        SettingsButtonAction::LogicalStartToggle,
+    );
+
+    commands
+        .entity(logical_start_row)
+        .push_children(&[logical_start_heading, logical_start_button]);
 
-        let safe_start_button = commands
+    let (wrap_around_heading, wrap_around_button) = spawn_toggle(
+        commands,
+        ui_assets,
+        "Wrap around:",
+        game_options.wrap_around,
+        SettingsButtonAction::WrapAroundToggle,
+    );
+
+    commands
+        .entity(wrap_around_row)
+        .push_children(&[wrap_around_heading, wrap_around_button]);
+
+    let (hexagonal_heading, hexagonal_button) = spawn_toggle(
+        commands,
+        ui_assets,
+        "Hexagonal:",
+        game_options.grid_shape == GridShape::Hexagonal,
+        SettingsButtonAction::HexagonalToggle,
+    );
+
+    commands
+        .entity(hexagonal_row)
+        .push_children(&[hexagonal_heading, hexagonal_button]);
+
+    let neighbor_mode_heading = commands
+        .spawn(
+            TextBundle::from_section("Neighbors:", ui_assets.style_h1()).with_style(Style {
+                margin: UiRect::right(Val::Px(20.)),
+                ..Default::default()
+            }),
+        )
+        .id();
+    let neighbor_mode_button = commands
+        .spawn((
+            ButtonBundle {
+                style: button_style.clone(),
+                background_color: ui_assets.background_alt.into(),
+                ..Default::default()
+            },
+            SettingsButtonAction::CycleNeighborMode,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                TextBundle::from_section(
+                    game_options.neighbor_mode.to_string(),
+                    button_text_style.clone(),
+                ),
+                SettingsTextField::NeighborMode,
+            ));
+        })
+        .id();
+
+    commands
+        .entity(neighbor_mode_row)
+        .push_children(&[neighbor_mode_heading, neighbor_mode_button]);
+
+    let (rush_mode_heading, rush_mode_button) = spawn_toggle(
+        commands,
+        ui_assets,
+        "Rush mode:",
+        game_options.game_mode == GameMode::RushMode,
+        SettingsButtonAction::RushModeToggle,
+    );
+
+    commands
+        .entity(rush_mode_row)
+        .push_children(&[rush_mode_heading, rush_mode_button]);
+
+    let (versus_heading, versus_button) = spawn_toggle(
+        commands,
+        ui_assets,
+        "Versus CPU:",
+        game_options.game_mode == GameMode::Versus,
+        SettingsButtonAction::VersusToggle,
+    );
+
+    commands
+        .entity(versus_row)
+        .push_children(&[versus_heading, versus_button]);
+
+    let share_code_button = commands
+        .spawn((
+            ButtonBundle {
+                style: button_style.clone(),
+                background_color: ui_assets.background_alt.into(),
+                ..Default::default()
+            },
+            SettingsButtonAction::EnterShareCode,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                "Share Code",
+                button_text_style.clone(),
+            ));
+        })
+        .id();
+    let share_code_field = commands
+        .spawn((
+            TextBundle::from_section("", ui_assets.style_h1_accent()),
+            ShareCodeField,
+        ))
+        .id();
+    let load_share_code_button = commands
+        .spawn((
+            ButtonBundle {
+                style: Style {
+                    width: Val::Px(120.),
+                    ..button_style
+                },
+                background_color: ui_assets.background_alt.into(),
+                ..Default::default()
+            },
+            SettingsButtonAction::LoadShareCode,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section("Load", button_text_style));
+        })
+        .id();
+
+    commands.entity(share_code_row).push_children(&[
+        share_code_button,
+        share_code_field,
+        load_share_code_button,
+    ]);
+
+    settings_column
+}
+
+/// Spawns a button for every built-in and custom preset in the given row,
+/// custom presets get a button to delete them next to them
+fn spawn_preset_buttons(
+    commands: &mut Commands,
+    row: Entity,
+    game_options: &GameOptions,
+    preset_library: &PresetLibrary,
+    ui_assets: &UiAssets,
+) {
+    let button_style = Style {
+        width: Val::Px(250.),
+        height: Val::Px(65.),
+        margin: UiRect::all(Val::Px(20.)),
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        ..Default::default()
+    };
+
+    let button_text_style = TextStyle {
+        font_size: 40.,
+        color: ui_assets.foreground,
+        font: ui_assets.font.clone(),
+    };
+
+    // Custom presets follow the built-in ones, their index in the library
+    // is needed to delete them
+    let builtin_count = Preset::values(None).count();
+    let presets = Preset::values(Some(preset_library))
+        .enumerate()
+        .map(|(index, preset)| (index.checked_sub(builtin_count), preset));
+
+    for (custom_index, preset) in presets {
+        let selected =
+            game_options.size == preset.size() && game_options.bomb_count == preset.bomb_count();
+
+        let background_color = if selected {
+            ui_assets.accent.into()
+        } else {
+            ui_assets.background_alt.into()
+        };
+
+        let button = commands
             .spawn((
                 ButtonBundle {
-                    style: Style {
-                        width: Val::Px(50.),
-                        height: Val::Px(50.),
-                        border: UiRect::all(Val::Px(10.)),
-                        ..Default::default()
-                    },
-                    border_color: ui_assets.background_alt.into(),
-                    background_color: if game_options.safe_start {
-                        ui_assets.accent.into()
-                    } else {
-                        ui_assets.background_alt.into()
-                    },
+                    style: button_style.clone(),
+                    background_color,
                     ..Default::default()
                 },
-                SettingsButtonAction::SafeStartToggle,
+                SettingsButtonAction::Preset(preset.clone()),
             ))
+            .with_children(|parent| {
+                parent.spawn(TextBundle::from_section(
+                    preset.to_string(),
+                    button_text_style.clone(),
+                ));
+            })
             .id();
 
-        commands
-            .entity(safe_start_row)
-            .push_children(&[safe_start_heading, safe_start_button]);
+        if selected {
+            commands.entity(button).insert(SelectedPreset);
+        }
+
+        commands.entity(row).push_children(&[button]);
+
+        if let Some(index) = custom_index {
+            let delete_button = commands
+                .spawn((
+                    ButtonBundle {
+                        style: Style {
+                            width: Val::Px(40.),
+                            height: Val::Px(40.),
+                            margin: UiRect::right(Val::Px(20.)),
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            ..Default::default()
+                        },
+                        background_color: ui_assets.background_alt.into(),
+                        ..Default::default()
+                    },
+                    SettingsButtonAction::DeletePreset(index),
+                ))
+                .with_children(|parent| {
+                    parent.spawn(TextBundle::from_section("×", ui_assets.style_h1()));
+                })
+                .id();
+
+            commands.entity(row).push_children(&[delete_button]);
+        }
+    }
+}
+
+/// Spawns the heading and button of an on/off setting
+fn spawn_toggle(
+    commands: &mut Commands,
+    ui_assets: &UiAssets,
+    label: &str,
+    on: bool,
+    action: SettingsButtonAction,
+) -> (Entity, Entity) {
+    let heading = commands
+        .spawn(
+            TextBundle::from_section(label, ui_assets.style_h1()).with_style(Style {
+                margin: UiRect::right(Val::Px(20.)),
+                ..Default::default()
+            }),
+        )
+        .id();
+
+    let button = commands
+        .spawn((
+            ButtonBundle {
+                style: Style {
+                    width: Val::Px(50.),
+                    height: Val::Px(50.),
+                    border: UiRect::all(Val::Px(10.)),
+                    ..Default::default()
+                },
+                border_color: ui_assets.background_alt.into(),
+                background_color: if on {
+                    ui_assets.accent.into()
+                } else {
+                    ui_assets.background_alt.into()
+                },
+                ..Default::default()
+            },
+            action,
+        ))
+        .id();
+
+    (heading, button)
+}
+
+/// Replaces the board configuration with the board of the typed share code,
+/// or shows why the code could not be loaded
+fn load_share_code(
+    commands: &mut Commands,
+    prompt: &mut ShareCodePrompt,
+    game_options: &mut GameOptions,
+) {
+    match TileMap::from_share_code(&prompt.code) {
+        Ok(tile_map) => {
+            game_options.size = tile_map.size();
+            game_options.bomb_count = tile_map.total_bombs();
+            game_options.preset = None;
+            game_options.share_code = Some(prompt.code.clone());
+            commands.remove_resource::<ShareCodePrompt>();
+        }
+        Err(err) => prompt.error = Some(err.to_string()),
+    }
+}
+
+fn density_text(game_options: &GameOptions) -> String {
+    format!("({:.1}%)", game_options.density())
+}
+
+fn density_color(density: f32, ui_assets: &UiAssets) -> Color {
+    if density > DENSITY_DANGER {
+        Color::RED
+    } else if density > DENSITY_WARNING {
+        Color::YELLOW
+    } else {
+        ui_assets.foreground
+    }
+}
+
+/// Returns a length in pixels as a whole number of tenths of a pixel
+fn tenths(pixels: f32) -> u32 {
+    (pixels * 10.).round() as u32
+}
+
+fn seed_text(seed: Option<u64>) -> String {
+    seed.map_or_else(|| "Random".to_string(), |seed| seed.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn builder_sets_options() {
+        let game_options = GameOptionsBuilder::default()
+            .cols(12)
+            .rows(8)
+            .bombs(20)
+            .safe_start(false)
+            .tile_size(TileSize::Fixed(30.))
+            .padding(4.)
+            .seed(42)
+            .build()
+            .unwrap();
+
+        assert_eq!(game_options.size, UVec2::new(12, 8));
+        assert_eq!(game_options.bomb_count, 20);
+        assert!(!game_options.safe_start);
+        assert!(game_options.tile_size == TileSize::Fixed(30.));
+        assert_eq!(game_options.tile_padding, 4.);
+        assert_eq!(game_options.seed, Some(42));
+    }
+
+    #[test]
+    fn builder_rejects_overflowing_size() {
+        let result = GameOptionsBuilder::default().cols(u32::MAX).rows(2).build();
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn builder_rejects_full_board() {
+        let result = GameOptionsBuilder::default()
+            .cols(4)
+            .rows(4)
+            .bombs(16)
+            .safe_start(false)
+            .build();
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn preset_is_saved_by_name() {
+        let game_options = GameOptions {
+            preset: Some(Preset::Expert),
+            ..default()
+        };
+        let contents = toml::to_string_pretty(&game_options).unwrap();
+
+        assert!(contents.contains("preset = \"Expert\""));
+        let loaded = toml::from_str::<GameOptions>(&contents).unwrap();
+        assert!(loaded.preset == Some(Preset::Expert));
+    }
+
+    #[test]
+    fn unknown_preset_loads_as_none() {
+        let game_options = toml::from_str::<GameOptions>("preset = \"Impossible\"").unwrap();
+
+        assert!(game_options.preset.is_none());
+    }
+
+    proptest! {
+        #[test]
+        fn valid_options_place_all_bombs(
+            cols in MIN_BOARD_SIZE..40,
+            rows in MIN_BOARD_SIZE..40,
+            bombs in MIN_BOMB_COUNT..1600,
+            safe_start: bool,
+            seed: u64,
+        ) {
+            let Ok(game_options) = GameOptionsBuilder::default()
+                .cols(cols)
+                .rows(rows)
+                .bombs(bombs)
+                .safe_start(safe_start)
+                .seed(seed)
+                .build()
+            else {
+                return Ok(());
+            };
+
+            let mut tile_map = TileMap::empty(game_options.size);
+            tile_map.set_bombs_seeded(game_options.bomb_count, seed).unwrap();
+            prop_assert_eq!(tile_map.count_mines(), game_options.bomb_count);
+        }
+
+        #[test]
+        fn builder_never_panics(cols: u32, rows: u32, bombs: u32, safe_start: bool) {
+            let _ = GameOptionsBuilder::default()
+                .cols(cols)
+                .rows(rows)
+                .bombs(bombs)
+                .safe_start(safe_start)
+                .build();
+        }
     }
 }
@@ -0,0 +1,100 @@
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
+use rand::{thread_rng, Rng as _};
+
+use crate::display::DisplaySettings;
+
+/// Maximum number of particles spawned by a single burst
+const MAX_BURST_PARTICLES: usize = 30;
+
+/// Lifetime of a particle in seconds
+const PARTICLE_LIFETIME: f32 = 0.4;
+
+/// Rate at which particles slow down, per second
+const PARTICLE_DRAG: f32 = 6.;
+
+/// Size of a particle relative to the tile size
+const PARTICLE_SCALE: f32 = 0.15;
+
+/// z-index of particles relative to the tile they burst from
+const PARTICLE_Z: f32 = 5.;
+
+/// A sprite flying away from a burst, fading out over its lifetime
+#[derive(Component)]
+pub struct Particle {
+    pub velocity: Vec2,
+    pub lifetime: Timer,
+}
+
+pub struct ParticlePlugin;
+
+impl Plugin for ParticlePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, Self::update_particles);
+    }
+}
+
+impl ParticlePlugin {
+    fn update_particles(
+        mut commands: Commands,
+        time: Res<Time>,
+        mut particles: Query<(Entity, &mut Particle, &mut Transform, &mut Sprite)>,
+    ) {
+        let delta = time.delta_seconds();
+
+        for (entity, mut particle, mut transform, mut sprite) in particles.iter_mut() {
+            particle.lifetime.tick(time.delta());
+
+            transform.translation += (particle.velocity * delta).extend(0.);
+            particle.velocity *= (-PARTICLE_DRAG * delta).exp();
+            sprite.color.set_a(1. - particle.lifetime.fraction());
+
+            if particle.lifetime.finished() {
+                commands.entity(entity).despawn_recursive();
+            }
+        }
+    }
+}
+
+/// Spawns particles flying out in random directions from the center of a tile
+/// Spawns no particles while animations are disabled
+pub fn spawn_burst(
+    commands: &mut Commands,
+    tile_entity: Entity,
+    color: Color,
+    tile_size: f32,
+    display_settings: &DisplaySettings,
+) {
+    let Some(lifetime) = display_settings.animation_duration(PARTICLE_LIFETIME) else {
+        return;
+    };
+    let mut rng = thread_rng();
+
+    let particles = (0..MAX_BURST_PARTICLES)
+        .map(|_| {
+            let direction = Vec2::from_angle(rng.gen_range(0. ..TAU));
+            let speed = rng.gen_range(2. ..5.) * tile_size;
+
+            commands
+                .spawn((
+                    SpriteBundle {
+                        sprite: Sprite {
+                            color,
+                            custom_size: Some(Vec2::splat(tile_size * PARTICLE_SCALE)),
+                            ..Default::default()
+                        },
+                        transform: Transform::from_xyz(0., 0., PARTICLE_Z),
+                        ..Default::default()
+                    },
+                    Particle {
+                        velocity: direction * speed,
+                        lifetime: Timer::from_seconds(lifetime, TimerMode::Once),
+                    },
+                ))
+                .id()
+        })
+        .collect::<Vec<_>>();
+
+    commands.entity(tile_entity).push_children(&particles);
+}
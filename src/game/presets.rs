@@ -0,0 +1,47 @@
+use std::fs;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::util::data_file;
+
+use super::options::Preset;
+
+/// File name of the custom presets file in the data directory
+const PRESETS_FILE: &str = "minesweeper_presets.json";
+
+/// Board configurations saved by the player, only holds `Preset::Custom`,
+/// created through `Preset::custom`
+#[derive(Resource, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct PresetLibrary(pub Vec<Preset>);
+
+impl PresetLibrary {
+    pub fn save(&self) -> std::io::Result<()> {
+        let path = data_file(PRESETS_FILE);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        fs::write(path, serde_json::to_string_pretty(self)?)
+    }
+}
+
+pub struct PresetsPlugin;
+
+impl Plugin for PresetsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, Self::load_presets);
+    }
+}
+
+impl PresetsPlugin {
+    fn load_presets(mut commands: Commands) {
+        let library = fs::read_to_string(data_file(PRESETS_FILE))
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default();
+
+        commands.insert_resource::<PresetLibrary>(library);
+    }
+}
@@ -0,0 +1,88 @@
+use bevy::prelude::*;
+
+use crate::util::lerp_color;
+
+use super::{board::Board, GameState, FLAG_Z};
+
+/// Opacity of the probability quads
+const OVERLAY_ALPHA: f32 = 0.5;
+
+/// Whether the mine probability of the covered tiles is shown
+#[derive(Resource, Default)]
+struct ProbabilityOverlay(bool);
+
+#[derive(Component)]
+struct ProbabilityQuad;
+
+/// Debug overlay coloring covered tiles from green to red by how likely they are
+/// to be a bomb, toggled with D
+pub struct ProbabilityOverlayPlugin;
+
+impl Plugin for ProbabilityOverlayPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ProbabilityOverlay>().add_systems(
+            Update,
+            (
+                Self::toggle_overlay,
+                Self::draw_overlay.run_if(
+                    resource_changed::<ProbabilityOverlay>.or_else(resource_changed::<Board>),
+                ),
+            )
+                .chain()
+                .run_if(in_state(GameState::Playing)),
+        );
+    }
+}
+
+impl ProbabilityOverlayPlugin {
+    fn toggle_overlay(
+        keyboard_input: Res<ButtonInput<KeyCode>>,
+        mut overlay: ResMut<ProbabilityOverlay>,
+    ) {
+        if keyboard_input.just_pressed(KeyCode::KeyD) {
+            overlay.0 = !overlay.0;
+        }
+    }
+
+    fn draw_overlay(
+        mut commands: Commands,
+        overlay: Res<ProbabilityOverlay>,
+        board: Res<Board>,
+        quads: Query<Entity, With<ProbabilityQuad>>,
+    ) {
+        for quad in quads.iter() {
+            commands.entity(quad).despawn_recursive();
+        }
+
+        if !overlay.0 {
+            return;
+        }
+
+        let probabilities = board.tile_map.compute_mine_probabilities();
+
+        for (position, tile) in board.tile_map.iter_with_pos() {
+            let (Some(tile_entity), Some(_)) = (tile.entity, tile.cover) else {
+                continue;
+            };
+
+            let probability = probabilities[position.y as usize][position.x as usize];
+            let quad = commands
+                .spawn((
+                    SpriteBundle {
+                        sprite: Sprite {
+                            color: lerp_color(Color::GREEN, Color::RED, probability)
+                                .with_a(OVERLAY_ALPHA),
+                            custom_size: Some(Vec2::splat(board.tile_size)),
+                            ..Default::default()
+                        },
+                        transform: Transform::from_xyz(0., 0., FLAG_Z + 1.),
+                        ..Default::default()
+                    },
+                    ProbabilityQuad,
+                ))
+                .id();
+
+            commands.entity(tile_entity).push_children(&[quad]);
+        }
+    }
+}
@@ -0,0 +1,190 @@
+use bevy::prelude::*;
+
+use crate::style::prelude::*;
+
+use super::{
+    board::Board, camera::CameraController, BoardInitialized, GameState, OnGameScreen, FLAG_Z,
+    TILE_Z,
+};
+
+/// Above the flags of the tiles, which are placed relative to their tile
+const SELECTION_Z: f32 = TILE_Z + FLAG_Z + 1.;
+
+/// Whether dragging with the left mouse button selects a region of tiles
+/// instead of revealing, toggled with R
+#[derive(Resource, Default, PartialEq)]
+pub struct RegionSelectMode(pub bool);
+
+/// Corners of the selected region in tile coordinates, both inclusive
+#[derive(Resource, Default)]
+struct RegionSelection {
+    start: Option<UVec2>,
+    end: Option<UVec2>,
+}
+
+impl RegionSelection {
+    fn rect(&self) -> Option<IRect> {
+        Some(IRect::from_corners(
+            self.start?.as_ivec2(),
+            self.end?.as_ivec2(),
+        ))
+    }
+}
+
+#[derive(Component)]
+struct SelectionQuad;
+
+#[derive(Component)]
+struct SelectionText;
+
+pub struct RegionSelectPlugin;
+
+impl Plugin for RegionSelectPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RegionSelectMode>()
+            .init_resource::<RegionSelection>()
+            .add_systems(
+                Update,
+                (
+                    Self::setup_selection.run_if(on_event::<BoardInitialized>()),
+                    Self::toggle_mode,
+                    Self::select_region.run_if(resource_equals(RegionSelectMode(true))),
+                    Self::update_selection.run_if(
+                        resource_changed::<RegionSelection>.or_else(resource_changed::<Board>),
+                    ),
+                )
+                    .chain()
+                    .run_if(in_state(GameState::Playing)),
+            );
+    }
+}
+
+impl RegionSelectPlugin {
+    fn setup_selection(
+        mut commands: Commands,
+        mut selection: ResMut<RegionSelection>,
+        ui_assets: Res<UiAssets>,
+    ) {
+        *selection = RegionSelection::default();
+
+        commands.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: Color::rgba(0.2, 0.4, 1., 0.3),
+                    ..Default::default()
+                },
+                visibility: Visibility::Hidden,
+                ..Default::default()
+            },
+            SelectionQuad,
+            OnGameScreen,
+        ));
+
+        commands.spawn((
+            TextBundle::from_section(
+                "",
+                TextStyle {
+                    font_size: 25.,
+                    color: ui_assets.foreground,
+                    font: ui_assets.font.clone(),
+                },
+            )
+            .with_style(Style {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(10.),
+                left: Val::Px(10.),
+                ..Default::default()
+            }),
+            SelectionText,
+            OnGameScreen,
+        ));
+    }
+
+    fn toggle_mode(
+        keys: Res<ButtonInput<KeyCode>>,
+        mut region_select_mode: ResMut<RegionSelectMode>,
+        mut selection: ResMut<RegionSelection>,
+    ) {
+        if keys.just_pressed(KeyCode::KeyR) {
+            region_select_mode.0 = !region_select_mode.0;
+            *selection = RegionSelection::default();
+        }
+    }
+
+    /// Starts a selection at the tile the left button is pressed on
+    /// and stretches it to the hovered tile while the button is held
+    fn select_region(
+        window: Query<&Window>,
+        board: Res<Board>,
+        camera_controller: Res<CameraController>,
+        mouse_buttons: Res<ButtonInput<MouseButton>>,
+        mut selection: ResMut<RegionSelection>,
+    ) {
+        if !mouse_buttons.pressed(MouseButton::Left) {
+            return;
+        }
+
+        let window = window.single();
+        let Some(hovered_tile) = window
+            .cursor_position()
+            .and_then(|cursor| board.mouse_to_tile(window, cursor, &camera_controller))
+        else {
+            return;
+        };
+
+        if mouse_buttons.just_pressed(MouseButton::Left) {
+            *selection = RegionSelection {
+                start: Some(hovered_tile),
+                end: Some(hovered_tile),
+            };
+        } else if selection.start.is_some() && selection.end != Some(hovered_tile) {
+            selection.end = Some(hovered_tile);
+        }
+    }
+
+    fn update_selection(
+        region_select_mode: Res<RegionSelectMode>,
+        selection: Res<RegionSelection>,
+        board: Res<Board>,
+        mut quads: Query<(&mut Sprite, &mut Transform, &mut Visibility), With<SelectionQuad>>,
+        mut texts: Query<&mut Text, With<SelectionText>>,
+    ) {
+        let rect = selection.rect();
+
+        for (mut sprite, mut transform, mut visibility) in quads.iter_mut() {
+            let Some(rect) = rect else {
+                *visibility = Visibility::Hidden;
+                continue;
+            };
+
+            // Tile centers grow upwards while rows grow downwards,
+            // so the corners are sorted after converting them
+            let first = board.tile_center(rect.min.as_uvec2());
+            let last = board.tile_center(rect.max.as_uvec2());
+            let half_tile = Vec2::splat(board.tile_size / 2.);
+            let min = first.min(last) - half_tile;
+            let max = first.max(last) + half_tile;
+
+            sprite.custom_size = Some(max - min);
+            transform.translation = ((min + max) / 2.).extend(SELECTION_Z);
+            *visibility = Visibility::Visible;
+        }
+
+        let text = match rect {
+            Some(rect) => {
+                let stats = board.tile_map.region_stats(rect);
+                format!(
+                    "Flagged: {}  Possible mines: {}",
+                    stats.flagged,
+                    stats.max_mines()
+                )
+            }
+            None if region_select_mode.0 => "Drag to select a region".to_string(),
+            None => String::new(),
+        };
+
+        for mut text_component in texts.iter_mut() {
+            text_component.sections[0].value = text.clone();
+        }
+    }
+}
@@ -0,0 +1,399 @@
+use std::collections::VecDeque;
+
+use bevy::{prelude::*, utils::HashSet};
+
+use crate::{
+    display::DisplaySettings,
+    style::prelude::*,
+    util::{despawn_all, remove_resource},
+    AppState,
+};
+
+use super::{
+    board,
+    options::GameOptions,
+    tile_texture,
+    tilemap::{TileMap, TileType},
+    Flag, FlagAnimation, GamePlugin, Position, Question, RevealAnimating, Tile, BACKGROUND_Z,
+    TILE_Z,
+};
+
+/// Action taken during a game, along with the time in seconds since the game started
+#[derive(Clone, Copy)]
+pub enum ReplayEvent {
+    Reveal(UVec2, f32),
+    Flag(UVec2, f32),
+}
+
+impl ReplayEvent {
+    fn time(&self) -> f32 {
+        match self {
+            ReplayEvent::Reveal(_, time) | ReplayEvent::Flag(_, time) => *time,
+        }
+    }
+}
+
+/// Actions of the current or last game, to be watched once it has finished.
+/// Undone reveals are not recorded, the replay shows them as they happened
+#[derive(Resource, Default)]
+pub struct Replay {
+    pub events: Vec<ReplayEvent>,
+    /// Share code of the board the game was played on, set once the game has finished
+    pub share_code: Option<String>,
+}
+
+/// Board the replay is shown on
+#[derive(Resource)]
+struct ReplayBoard {
+    tile_map: TileMap,
+    tile_size: f32,
+    /// Seconds since the replay started
+    elapsed: f32,
+    /// Index of the first event that has not been shown yet
+    next_event: usize,
+}
+
+#[derive(Component)]
+struct OnReplayScreen;
+
+#[derive(Component)]
+struct ReplayBackButton;
+
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Replay>()
+            .add_systems(OnEnter(AppState::Replay), Self::setup_replay)
+            .add_systems(
+                OnExit(AppState::Replay),
+                (
+                    despawn_all::<OnReplayScreen>,
+                    remove_resource::<ReplayBoard>,
+                ),
+            )
+            .add_systems(
+                Update,
+                (
+                    Self::play_replay.run_if(resource_exists::<ReplayBoard>),
+                    Self::back_button,
+                )
+                    .run_if(in_state(AppState::Replay)),
+            );
+    }
+}
+
+impl ReplayPlugin {
+    fn setup_replay(
+        mut commands: Commands,
+        replay: Res<Replay>,
+        window: Query<&Window>,
+        game_options: Res<GameOptions>,
+        game_assets: Res<GameAssets>,
+        ui_assets: Res<UiAssets>,
+        mut app_state: ResMut<NextState<AppState>>,
+    ) {
+        let Some(mut tile_map) = replay
+            .share_code
+            .as_deref()
+            .and_then(|code| TileMap::from_share_code(code).ok())
+            .map(|tile_map| {
+                tile_map
+                    .with_wrap_around(game_options.wrap_around)
+                    .with_shape(game_options.grid_shape)
+                    .with_neighbor_mode(game_options.neighbor_mode)
+            })
+        else {
+            app_state.set(AppState::Menu);
+            return;
+        };
+
+        let size = tile_map.size();
+        let tile_size = GamePlugin::fit_tile_size(window.single(), &game_options, size);
+        let shape = tile_map.shape();
+        let board_size = board::board_size(size, tile_size, game_options.tile_padding, shape);
+        let board_position = Vec3::new(-board_size.x / 2., -board_size.y / 2., BACKGROUND_Z);
+        let texture = tile_texture(shape, &game_assets);
+
+        let board_entity = commands
+            .spawn((
+                SpatialBundle {
+                    transform: Transform::from_translation(board_position),
+                    ..Default::default()
+                },
+                OnReplayScreen,
+            ))
+            .with_children(|parent| {
+                parent.spawn(SpriteBundle {
+                    sprite: Sprite {
+                        custom_size: Some(board_size),
+                        ..Default::default()
+                    },
+                    texture: game_assets.board_gradient.clone(),
+                    transform: Transform::from_xyz(
+                        board_size.x / 2.,
+                        board_size.y / 2.,
+                        BACKGROUND_Z,
+                    ),
+                    ..Default::default()
+                });
+            })
+            .id();
+
+        for (position, tile) in tile_map.iter_mut_with_pos() {
+            let offset = board::tile_offset(
+                position.as_ivec2(),
+                size,
+                tile_size,
+                game_options.tile_padding,
+                shape,
+            );
+
+            let tile_entity = commands
+                .spawn((
+                    SpriteBundle {
+                        sprite: Sprite {
+                            color: game_assets.tile_uncovered,
+                            custom_size: Some(Vec2::splat(tile_size)),
+                            ..Default::default()
+                        },
+                        texture: texture.clone(),
+                        transform: Transform::from_translation(offset.extend(TILE_Z)),
+                        ..Default::default()
+                    },
+                    Position(position.as_ivec2()),
+                    Tile(tile.tile_type),
+                ))
+                .id();
+
+            let cover_entity = GamePlugin::spawn_cover(
+                &mut commands,
+                position,
+                tile_size,
+                texture.clone(),
+                game_assets.tile_covered,
+            );
+            let mut children = vec![cover_entity];
+
+            if let Some(content) = GamePlugin::spawn_tile_content(
+                &mut commands,
+                tile.tile_type,
+                tile_size,
+                texture.clone(),
+                &game_assets,
+            ) {
+                children.push(content);
+            }
+
+            commands.entity(tile_entity).push_children(&children);
+            commands.entity(board_entity).push_children(&[tile_entity]);
+
+            tile.entity = Some(tile_entity);
+            tile.cover = Some(cover_entity);
+        }
+
+        commands
+            .spawn((
+                NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        top: Val::Px(10.),
+                        width: Val::Percent(100.),
+                        justify_content: JustifyContent::Center,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                OnReplayScreen,
+            ))
+            .with_children(|parent| {
+                parent
+                    .spawn((
+                        ButtonBundle {
+                            style: Style {
+                                width: Val::Px(200.),
+                                height: Val::Px(40.),
+                                justify_content: JustifyContent::Center,
+                                align_items: AlignItems::Center,
+                                ..Default::default()
+                            },
+                            background_color: ui_assets.background_alt.into(),
+                            ..Default::default()
+                        },
+                        ReplayBackButton,
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn(TextBundle::from_section(
+                            "Menu",
+                            TextStyle {
+                                font_size: 25.,
+                                color: ui_assets.foreground,
+                                font: ui_assets.font.clone(),
+                            },
+                        ));
+                    });
+            });
+
+        commands.insert_resource(ReplayBoard {
+            tile_map,
+            tile_size,
+            elapsed: 0.,
+            next_event: 0,
+        });
+    }
+
+    /// Shows the recorded actions once the time they were taken at has passed
+    fn play_replay(
+        mut commands: Commands,
+        time: Res<Time>,
+        replay: Res<Replay>,
+        mut replay_board: ResMut<ReplayBoard>,
+        game_assets: Res<GameAssets>,
+        display_settings: Res<DisplaySettings>,
+    ) {
+        replay_board.elapsed += time.delta_seconds();
+
+        while let Some(event) = replay.events.get(replay_board.next_event) {
+            if event.time() > replay_board.elapsed {
+                break;
+            }
+            replay_board.next_event += 1;
+
+            match *event {
+                ReplayEvent::Reveal(position, _) => {
+                    Self::reveal(
+                        &mut commands,
+                        &mut replay_board,
+                        position,
+                        &display_settings,
+                    );
+                }
+                ReplayEvent::Flag(position, _) => {
+                    Self::flag(
+                        &mut commands,
+                        &mut replay_board,
+                        position,
+                        &game_assets,
+                        &display_settings,
+                    );
+                }
+            }
+        }
+    }
+
+    /// Uncovers a tile like `GamePlugin::handle_reveal_event`,
+    /// flooding through empty tiles and chording revealed numbers
+    fn reveal(
+        commands: &mut Commands,
+        replay_board: &mut ReplayBoard,
+        position: UVec2,
+        display_settings: &DisplaySettings,
+    ) {
+        let tile_map = &mut replay_board.tile_map;
+
+        let mut queue = VecDeque::new();
+        match tile_map.get_tile(position) {
+            Some(tile) if tile.cover.is_some() => queue.push_back((position, 0)),
+            Some(_) if tile_map.can_chord(position) => {
+                queue.extend(
+                    tile_map
+                        .get_neighbors(position)
+                        .map(|neighbor| (neighbor, 0)),
+                );
+            }
+            _ => return,
+        }
+
+        let mut revealed = HashSet::new();
+        while let Some((position, depth)) = queue.pop_front() {
+            if !revealed.insert(position) {
+                continue;
+            }
+
+            let Some(tile) = tile_map.get_tile_mut(position) else {
+                continue;
+            };
+            if tile.flag.is_some() {
+                continue;
+            }
+            let Some(cover_entity) = tile.cover.take() else {
+                continue;
+            };
+
+            RevealAnimating::start(commands, cover_entity, depth, display_settings);
+            if let Some(question_entity) = tile.question.take() {
+                commands.entity(question_entity).despawn_recursive();
+            }
+
+            if tile.tile_type == TileType::Empty {
+                queue.extend(
+                    tile_map
+                        .get_neighbors(position)
+                        .map(|neighbor| (neighbor, depth + 1)),
+                );
+            }
+        }
+    }
+
+    /// Cycles a covered tile between covered, flagged and questioned
+    /// like `GamePlugin::handle_flag_event`
+    fn flag(
+        commands: &mut Commands,
+        replay_board: &mut ReplayBoard,
+        position: UVec2,
+        game_assets: &GameAssets,
+        display_settings: &DisplaySettings,
+    ) {
+        let tile_size = replay_board.tile_size;
+        let texture = tile_texture(replay_board.tile_map.shape(), game_assets);
+
+        let Some(tile) = replay_board.tile_map.get_tile_mut(position) else {
+            return;
+        };
+        let (Some(tile_entity), Some(_)) = (tile.entity, tile.cover) else {
+            return;
+        };
+
+        if let Some(flag_entity) = tile.flag.take() {
+            FlagAnimation::start(commands, flag_entity, true, display_settings);
+
+            let question_entity = GamePlugin::spawn_marker(
+                commands,
+                position,
+                game_assets.tile_questioned,
+                tile_size,
+                texture,
+                Question,
+            );
+            commands
+                .entity(tile_entity)
+                .push_children(&[question_entity]);
+            tile.question = Some(question_entity);
+        } else if let Some(question_entity) = tile.question.take() {
+            commands.entity(question_entity).despawn_recursive();
+        } else {
+            let flag_entity = GamePlugin::spawn_marker(
+                commands,
+                position,
+                game_assets.tile_flagged,
+                tile_size,
+                texture,
+                Flag,
+            );
+            FlagAnimation::start(commands, flag_entity, false, display_settings);
+            commands.entity(tile_entity).push_children(&[flag_entity]);
+            tile.flag = Some(flag_entity);
+        }
+    }
+
+    fn back_button(
+        interactions: Query<&Interaction, (Changed<Interaction>, With<ReplayBackButton>)>,
+        mut app_state: ResMut<NextState<AppState>>,
+    ) {
+        for interaction in interactions.iter() {
+            if *interaction == Interaction::Pressed {
+                app_state.set(AppState::Menu);
+            }
+        }
+    }
+}
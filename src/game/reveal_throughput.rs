@@ -0,0 +1,49 @@
+use bevy::{
+    diagnostic::{Diagnostic, DiagnosticPath, Diagnostics, RegisterDiagnostic},
+    prelude::*,
+};
+
+/// Tiles revealed per frame, shown with the other diagnostics
+pub const REVEAL_THROUGHPUT: DiagnosticPath = DiagnosticPath::const_new("game/reveal_throughput");
+
+/// Reveals in a single frame above which a warning is logged
+const REVEAL_WARNING_THRESHOLD: u32 = 500;
+
+/// Counts the tiles `handle_reveal_event` uncovers, used to profile large boards
+#[derive(Resource, Default)]
+pub struct RevealThroughput {
+    /// Tiles revealed so far in the current frame
+    pub count: u32,
+    /// Most tiles revealed in a single frame
+    peak: u32,
+}
+
+/// Debug diagnostic measuring how many tiles are revealed per frame
+pub struct RevealThroughputPlugin;
+
+impl Plugin for RevealThroughputPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RevealThroughput>()
+            .register_diagnostic(Diagnostic::new(REVEAL_THROUGHPUT).with_suffix(" reveals"))
+            .add_systems(Last, Self::print_reveal_throughput);
+    }
+}
+
+impl RevealThroughputPlugin {
+    /// Records the reveals of the frame and starts counting the next one
+    fn print_reveal_throughput(
+        mut reveal_throughput: ResMut<RevealThroughput>,
+        mut diagnostics: Diagnostics,
+    ) {
+        let count = std::mem::take(&mut reveal_throughput.count);
+        diagnostics.add_measurement(&REVEAL_THROUGHPUT, || count as f64);
+
+        if count > reveal_throughput.peak {
+            reveal_throughput.peak = count;
+            info!("Peak reveal throughput: {count} tiles in a frame");
+        }
+        if count > REVEAL_WARNING_THRESHOLD {
+            warn!("Revealed {count} tiles in a single frame");
+        }
+    }
+}
@@ -0,0 +1,171 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::style::prelude::*;
+
+use super::{
+    board::Board,
+    hud::{CountdownTimer, GameTimer},
+    options::GameOptions,
+    tile_texture, tilemap, BoardInitialized, DeadMine, Flag, GamePlugin, GameState, Question,
+};
+
+/// Game left through the menu button of the pause screen,
+/// offered to be continued when starting a new game
+#[derive(Resource)]
+pub struct SavedGame {
+    /// Options the game was started with, they are restored along with the game
+    options: GameOptions,
+    /// Share code holding the bomb positions
+    share_code: String,
+    revealed: Vec<UVec2>,
+    flagged: Vec<UVec2>,
+    questioned: Vec<UVec2>,
+    timer: Duration,
+    /// Time already counted down in rush mode
+    countdown: Option<Duration>,
+}
+
+impl SavedGame {
+    /// Captures a game in progress, `None` before the bombs have been placed
+    /// or once the game has finished
+    pub(super) fn from_board(
+        board: &Board,
+        game_options: &GameOptions,
+        game_timer: &GameTimer,
+        countdown_timer: Option<&CountdownTimer>,
+    ) -> Option<Self> {
+        if !board.tile_map.is_generated() || board.is_lost() || board.is_won() {
+            return None;
+        }
+
+        let positions = |predicate: fn(&tilemap::Tile) -> bool| {
+            board
+                .tile_map
+                .iter_with_pos()
+                .filter(|(_, tile)| predicate(tile))
+                .map(|(position, _)| position)
+                .collect()
+        };
+
+        Some(Self {
+            options: GameOptions {
+                share_code: None,
+                ..game_options.clone()
+            },
+            share_code: board.tile_map.to_ascii_share(),
+            revealed: positions(|tile| tile.cover.is_none()),
+            flagged: positions(|tile| tile.flag.is_some()),
+            questioned: positions(|tile| tile.question.is_some()),
+            timer: game_timer.0,
+            countdown: countdown_timer.map(|countdown_timer| countdown_timer.0.elapsed()),
+        })
+    }
+
+    /// Options to play the saved game with
+    pub(super) fn options(&self) -> &GameOptions {
+        &self.options
+    }
+
+    pub(super) fn share_code(&self) -> &str {
+        &self.share_code
+    }
+}
+
+pub struct SavedGamePlugin;
+
+impl Plugin for SavedGamePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            Self::restore_saved_game.run_if(
+                in_state(GameState::Playing)
+                    .and_then(on_event::<BoardInitialized>())
+                    .and_then(resource_exists::<SavedGame>),
+            ),
+        );
+    }
+}
+
+impl SavedGamePlugin {
+    /// Reveals and marks the tiles of the freshly spawned board like they were
+    /// when the game was saved, the bombs were placed from its share code
+    #[allow(clippy::too_many_arguments)]
+    fn restore_saved_game(
+        mut commands: Commands,
+        saved_game: Res<SavedGame>,
+        mut board: ResMut<Board>,
+        mut game_timer: ResMut<GameTimer>,
+        countdown_timer: Option<ResMut<CountdownTimer>>,
+        game_assets: Res<GameAssets>,
+    ) {
+        let tile_size = board.tile_size;
+        let texture = tile_texture(board.tile_map.shape(), &game_assets);
+
+        for &position in &saved_game.revealed {
+            let Some(tile) = board.tile_map.get_tile_mut(position) else {
+                continue;
+            };
+            if let Some(cover_entity) = tile.cover.take() {
+                commands.entity(cover_entity).despawn_recursive();
+            }
+            if let (true, Some(tile_entity)) = (tile.is_bomb(), tile.entity) {
+                commands.entity(tile_entity).insert(DeadMine);
+            }
+        }
+
+        let markers = saved_game
+            .flagged
+            .iter()
+            .map(|&position| (position, true))
+            .chain(
+                saved_game
+                    .questioned
+                    .iter()
+                    .map(|&position| (position, false)),
+            );
+        for (position, flagged) in markers {
+            let Some(tile) = board.tile_map.get_tile_mut(position) else {
+                continue;
+            };
+            let Some(tile_entity) = tile.entity else {
+                continue;
+            };
+
+            if flagged {
+                let flag_entity = GamePlugin::spawn_marker(
+                    &mut commands,
+                    position,
+                    game_assets.tile_flagged,
+                    tile_size,
+                    texture.clone(),
+                    Flag,
+                );
+                commands.entity(tile_entity).push_children(&[flag_entity]);
+                tile.flag = Some(flag_entity);
+            } else {
+                let question_entity = GamePlugin::spawn_marker(
+                    &mut commands,
+                    position,
+                    game_assets.tile_questioned,
+                    tile_size,
+                    texture.clone(),
+                    Question,
+                );
+                commands
+                    .entity(tile_entity)
+                    .push_children(&[question_entity]);
+                tile.question = Some(question_entity);
+            }
+        }
+
+        game_timer.0 = saved_game.timer;
+        if let (Some(mut countdown_timer), Some(elapsed)) = (countdown_timer, saved_game.countdown)
+        {
+            countdown_timer.0.set_elapsed(elapsed);
+        }
+
+        commands.remove_resource::<SavedGame>();
+    }
+}
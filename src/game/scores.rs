@@ -0,0 +1,104 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::util::data_file;
+
+use super::{hud::GameTimer, options::GameOptions, spectate::Spectated, GameResult, GameState};
+
+/// Number of entries kept per category
+pub const MAX_ENTRIES: usize = 5;
+
+/// File name of the high scores file in the data directory
+const SCORES_FILE: &str = "minesweeper_scores.json";
+
+/// A single won game
+#[derive(Clone, Serialize, Deserialize)]
+pub struct HighScoreEntry {
+    /// Time taken to win the game in seconds
+    pub seconds: f32,
+    /// Unix timestamp of when the game was won
+    pub timestamp: u64,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Fastest wins, keyed by the category of the game options
+#[derive(Resource, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct HighScores(pub BTreeMap<String, Vec<HighScoreEntry>>);
+
+impl HighScores {
+    /// Adds an entry to a category, keeping the `MAX_ENTRIES` fastest
+    pub fn insert(&mut self, category: String, entry: HighScoreEntry) {
+        let entries = self.0.entry(category).or_default();
+        entries.push(entry);
+        entries.sort_by(|a, b| a.seconds.total_cmp(&b.seconds));
+        entries.truncate(MAX_ENTRIES);
+    }
+}
+
+pub struct ScoresPlugin;
+
+impl Plugin for ScoresPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, Self::load_scores)
+            .add_systems(OnEnter(GameState::Finished), Self::record_score);
+    }
+}
+
+impl ScoresPlugin {
+    fn load_scores(mut commands: Commands) {
+        let scores = fs::read_to_string(data_file(SCORES_FILE))
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default();
+
+        commands.insert_resource::<HighScores>(scores);
+    }
+
+    fn record_score(
+        game_result: Res<GameResult>,
+        game_timer: Res<GameTimer>,
+        game_options: Res<GameOptions>,
+        spectated: Option<Res<Spectated>>,
+        mut scores: ResMut<HighScores>,
+    ) {
+        if !game_result.0 || spectated.is_some() {
+            return;
+        }
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+
+        scores.insert(
+            game_options.category(),
+            HighScoreEntry {
+                seconds: game_timer.0.as_secs_f32(),
+                timestamp,
+                width: game_options.size.x,
+                height: game_options.size.y,
+            },
+        );
+
+        if let Err(err) = save_scores(&scores) {
+            warn!("Could not save high scores: {err}");
+        }
+    }
+}
+
+fn save_scores(scores: &HighScores) -> std::io::Result<()> {
+    let path = data_file(SCORES_FILE);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    fs::write(path, serde_json::to_string_pretty(scores)?)
+}
@@ -0,0 +1,72 @@
+use std::{
+    fs,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use bevy::{prelude::*, render::view::screenshot::ScreenshotManager, window::PrimaryWindow};
+
+use crate::util::screenshot_dir;
+
+use super::{board::Board, GameResult, GameState};
+
+/// Screenshot of the finished game, taken the frame after the game finished
+/// so the finished overlay has been rendered
+#[derive(Resource)]
+struct PendingScreenshot;
+
+/// Saves a screenshot of every finished game to the screenshot directory
+pub struct ScreenshotPlugin;
+
+impl Plugin for ScreenshotPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::Finished), Self::request_screenshot)
+            .add_systems(
+                Update,
+                Self::save_screenshot.run_if(
+                    resource_exists::<PendingScreenshot>.and_then(in_state(GameState::Finished)),
+                ),
+            );
+    }
+}
+
+impl ScreenshotPlugin {
+    fn request_screenshot(mut commands: Commands) {
+        commands.insert_resource(PendingScreenshot);
+    }
+
+    fn save_screenshot(
+        mut commands: Commands,
+        game_result: Res<GameResult>,
+        board: Res<Board>,
+        window: Query<Entity, With<PrimaryWindow>>,
+        mut screenshot_manager: ResMut<ScreenshotManager>,
+    ) {
+        commands.remove_resource::<PendingScreenshot>();
+
+        let Ok(window) = window.get_single() else {
+            return;
+        };
+
+        let dir = screenshot_dir();
+        if let Err(err) = fs::create_dir_all(&dir) {
+            warn!("Could not create the screenshot directory: {err}");
+            return;
+        }
+
+        let size = board.tile_map.size();
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let file_name = format!(
+            "minesweeper_{}_{}x{}_{timestamp}.png",
+            if game_result.0 { "win" } else { "lose" },
+            size.x,
+            size.y,
+        );
+
+        if let Err(err) = screenshot_manager.save_screenshot_to_disk(window, dir.join(file_name)) {
+            warn!("Could not take a screenshot: {err}");
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use bevy::{ecs::system::SystemParam, prelude::*};
+
+/// Sound effects played during a game
+#[derive(Resource)]
+pub struct SoundAssets {
+    pub reveal: Handle<AudioSource>,
+    pub flag_place: Handle<AudioSource>,
+    pub explosion: Handle<AudioSource>,
+    pub win: Handle<AudioSource>,
+}
+
+/// Whether sound effects are played, toggled from the settings menu
+#[derive(Resource)]
+pub struct SoundEnabled(pub bool);
+
+impl Default for SoundEnabled {
+    fn default() -> Self {
+        Self(true)
+    }
+}
+
+#[derive(Clone, Copy)]
+pub enum Sound {
+    Reveal,
+    FlagPlace,
+    Explosion,
+    Win,
+}
+
+/// Plays sound effects unless sound is disabled
+#[derive(SystemParam)]
+pub struct Sounds<'w> {
+    assets: Res<'w, SoundAssets>,
+    enabled: Res<'w, SoundEnabled>,
+}
+
+impl Sounds<'_> {
+    pub fn play(&self, commands: &mut Commands, sound: Sound) {
+        if !self.enabled.0 {
+            return;
+        }
+
+        let source = match sound {
+            Sound::Reveal => &self.assets.reveal,
+            Sound::FlagPlace => &self.assets.flag_place,
+            Sound::Explosion => &self.assets.explosion,
+            Sound::Win => &self.assets.win,
+        };
+
+        commands.spawn(AudioBundle {
+            source: source.clone(),
+            settings: PlaybackSettings::DESPAWN,
+        });
+    }
+}
+
+pub struct SoundPlugin;
+
+impl Plugin for SoundPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SoundEnabled>()
+            .add_systems(Startup, Self::load_sounds);
+    }
+}
+
+impl SoundPlugin {
+    fn load_sounds(mut commands: Commands, asset_server: Res<AssetServer>) {
+        commands.insert_resource(SoundAssets {
+            reveal: asset_server.load("sounds/reveal.wav"),
+            flag_place: asset_server.load("sounds/flag_place.wav"),
+            explosion: asset_server.load("sounds/explosion.wav"),
+            win: asset_server.load("sounds/win.wav"),
+        });
+    }
+}
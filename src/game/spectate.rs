@@ -0,0 +1,132 @@
+use bevy::prelude::*;
+use rand::{seq::IteratorRandom as _, thread_rng};
+
+use crate::style::prelude::*;
+
+use super::{board::Board, hint::TileHint, GameState, Position, SafeStartCover, TileRevealed};
+
+/// Seconds the solver highlights a tile before revealing it, and waits after
+/// revealing it before picking the next one
+const SPECTATE_STEP: f32 = 0.5;
+
+/// Marks a game the solver has played in, its result is not recorded
+#[derive(Resource)]
+pub(super) struct Spectated;
+
+/// Tile the solver is about to reveal and the time until its next step
+#[derive(Resource)]
+struct SpectateSolver {
+    target: Option<UVec2>,
+    timer: Timer,
+}
+
+impl Default for SpectateSolver {
+    fn default() -> Self {
+        Self {
+            target: None,
+            timer: Timer::from_seconds(SPECTATE_STEP, TimerMode::Repeating),
+        }
+    }
+}
+
+/// Lets the hint solver finish the game while the player watches, making one
+/// move per second. It reveals the tiles the revealed numbers prove to be safe
+/// and guesses when there are none. Any key hands the game back to the player
+pub struct SpectatePlugin;
+
+impl Plugin for SpectatePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::Generating), Self::clear_spectated)
+            .add_systems(OnEnter(GameState::Spectating), Self::start_spectating)
+            .add_systems(OnExit(GameState::Spectating), Self::stop_spectating)
+            .add_systems(
+                Update,
+                (Self::play_solver, Self::handle_spectate_input)
+                    .chain()
+                    .run_if(in_state(GameState::Spectating)),
+            );
+    }
+}
+
+impl SpectatePlugin {
+    fn clear_spectated(mut commands: Commands) {
+        commands.remove_resource::<Spectated>();
+    }
+
+    fn start_spectating(mut commands: Commands) {
+        commands.insert_resource(Spectated);
+        commands.insert_resource(SpectateSolver::default());
+    }
+
+    /// Removes the highlight of a tile the solver did not get to reveal
+    fn stop_spectating(
+        mut commands: Commands,
+        mut hinted: Query<(Entity, &mut Sprite, Has<SafeStartCover>), With<TileHint>>,
+        game_assets: Res<GameAssets>,
+    ) {
+        commands.remove_resource::<SpectateSolver>();
+
+        for (entity, mut sprite, safe_start) in hinted.iter_mut() {
+            commands.entity(entity).remove::<TileHint>();
+            sprite.color = if safe_start {
+                game_assets.tile_uncovered
+            } else {
+                game_assets.tile_covered
+            };
+        }
+    }
+
+    /// Alternates between highlighting the next tile and revealing it
+    fn play_solver(
+        mut commands: Commands,
+        time: Res<Time>,
+        board: Res<Board>,
+        mut solver: ResMut<SpectateSolver>,
+        hinted: Query<Entity, (With<TileHint>, With<Position>)>,
+        mut tile_revealed_evw: EventWriter<TileRevealed>,
+    ) {
+        if !solver.timer.tick(time.delta()).just_finished() {
+            return;
+        }
+
+        if let Some(position) = solver.target.take() {
+            for entity in hinted.iter() {
+                commands.entity(entity).remove::<TileHint>();
+            }
+            tile_revealed_evw.send(TileRevealed { position });
+            return;
+        }
+
+        let tile_map = &board.tile_map;
+        let target = tile_map
+            .deducible_safe_tiles()
+            .first()
+            .copied()
+            .or_else(|| {
+                tile_map
+                    .iter_with_pos()
+                    .filter(|(_, tile)| tile.cover.is_some() && tile.flag.is_none())
+                    .map(|(position, _)| position)
+                    .choose(&mut thread_rng())
+            });
+
+        let Some((position, cover)) =
+            target.and_then(|position| Some((position, tile_map.get_tile(position)?.cover?)))
+        else {
+            return;
+        };
+
+        commands.entity(cover).insert(TileHint::default());
+        solver.target = Some(position);
+    }
+
+    /// Hands the game back to the player on any key
+    fn handle_spectate_input(
+        keyboard_input: Res<ButtonInput<KeyCode>>,
+        mut game_state: ResMut<NextState<GameState>>,
+    ) {
+        if keyboard_input.get_just_pressed().next().is_some() {
+            game_state.set(GameState::Playing);
+        }
+    }
+}
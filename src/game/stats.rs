@@ -0,0 +1,81 @@
+use std::{collections::BTreeMap, fs};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::util::data_file;
+
+/// File name of the statistics file in the data directory
+const STATISTICS_FILE: &str = "minesweeper_statistics.json";
+
+/// Results of all finished games of a category
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct StatEntry {
+    pub games_played: u32,
+    pub games_won: u32,
+    /// Fastest win in seconds
+    pub best_time: Option<f32>,
+    pub current_streak: u32,
+    pub longest_streak: u32,
+}
+
+impl StatEntry {
+    /// Percentage of the played games that were won
+    pub fn win_rate(&self) -> f32 {
+        if self.games_played == 0 {
+            return 0.;
+        }
+
+        self.games_won as f32 / self.games_played as f32 * 100.
+    }
+}
+
+/// Statistics of finished games, keyed by the category of the game options
+#[derive(Resource, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Statistics(pub BTreeMap<String, StatEntry>);
+
+impl Statistics {
+    /// Records a finished game, `seconds` being the time it took
+    pub fn record(&mut self, category: String, won: bool, seconds: f32) {
+        let entry = self.0.entry(category).or_default();
+        entry.games_played += 1;
+
+        if won {
+            entry.games_won += 1;
+            entry.best_time = Some(entry.best_time.map_or(seconds, |best| best.min(seconds)));
+            entry.current_streak += 1;
+            entry.longest_streak = entry.longest_streak.max(entry.current_streak);
+        } else {
+            entry.current_streak = 0;
+        }
+    }
+
+    pub fn save(&self) -> std::io::Result<()> {
+        let path = data_file(STATISTICS_FILE);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        fs::write(path, serde_json::to_string_pretty(self)?)
+    }
+}
+
+pub struct StatsPlugin;
+
+impl Plugin for StatsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, Self::load_statistics);
+    }
+}
+
+impl StatsPlugin {
+    fn load_statistics(mut commands: Commands) {
+        let statistics = fs::read_to_string(data_file(STATISTICS_FILE))
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default();
+
+        commands.insert_resource::<Statistics>(statistics);
+    }
+}
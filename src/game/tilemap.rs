@@ -1,26 +1,186 @@
-use bevy::prelude::*;
-use rand::{seq::IteratorRandom as _, seq::SliceRandom as _, thread_rng};
+use std::collections::{HashSet, VecDeque};
+
+use bevy::{
+    prelude::*,
+    render::{
+        render_asset::RenderAssetUsages,
+        render_resource::{Extent3d, TextureDimension, TextureFormat},
+        texture::ImageSampler,
+    },
+};
+use rand::{
+    rngs::StdRng, seq::IteratorRandom as _, seq::SliceRandom as _, thread_rng, Rng,
+    SeedableRng as _,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::style::prelude::*;
 
 #[cfg(feature = "debug")]
 use colored::Colorize as _;
 
-const NEIGHBOR_OFFSETS: [(i32, i32); 8] = [
-    (-1, -1),
-    (0, -1),
-    (1, -1),
-    (-1, 0),
-    (1, 0),
-    (-1, 1),
-    (0, 1),
-    (1, 1),
+/// Version of the share code format, the first byte of every share code
+const SHARE_CODE_VERSION: u8 = 1;
+
+/// Bytes before the bomb bits of a share code: version, width and height
+const SHARE_CODE_HEADER: usize = 5;
+
+const NEIGHBOR_OFFSETS: [IVec2; 8] = [
+    IVec2::new(-1, -1),
+    IVec2::new(0, -1),
+    IVec2::new(1, -1),
+    IVec2::new(-1, 0),
+    IVec2::new(1, 0),
+    IVec2::new(-1, 1),
+    IVec2::new(0, 1),
+    IVec2::new(1, 1),
+];
+
+/// Orthogonal neighbors of a square tile
+const CARDINAL_NEIGHBOR_OFFSETS: [IVec2; 4] = [
+    IVec2::new(0, -1),
+    IVec2::new(-1, 0),
+    IVec2::new(1, 0),
+    IVec2::new(0, 1),
+];
+
+/// Diagonal neighbors of a square tile
+const DIAGONAL_NEIGHBOR_OFFSETS: [IVec2; 4] = [
+    IVec2::new(-1, -1),
+    IVec2::new(1, -1),
+    IVec2::new(-1, 1),
+    IVec2::new(1, 1),
+];
+
+/// Neighbors of a tile in an even row of a hexagonal grid,
+/// odd rows are shifted half a tile to the right
+const HEX_NEIGHBOR_OFFSETS_EVEN: [IVec2; 6] = [
+    IVec2::new(-1, -1),
+    IVec2::new(0, -1),
+    IVec2::new(-1, 0),
+    IVec2::new(1, 0),
+    IVec2::new(-1, 1),
+    IVec2::new(0, 1),
+];
+
+/// Neighbors of a tile in an odd row of a hexagonal grid
+const HEX_NEIGHBOR_OFFSETS_ODD: [IVec2; 6] = [
+    IVec2::new(0, -1),
+    IVec2::new(1, -1),
+    IVec2::new(-1, 0),
+    IVec2::new(1, 0),
+    IVec2::new(0, 1),
+    IVec2::new(1, 1),
 ];
 
+/// Shape of the tiles of a board
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum GridShape {
+    #[default]
+    Square,
+    /// Hexagonal tiles with every odd row shifted half a tile to the right
+    Hexagonal,
+}
+
+impl GridShape {
+    fn neighbor_offsets(&self, pos: UVec2, neighbor_mode: NeighborMode) -> &'static [IVec2] {
+        match self {
+            GridShape::Square => neighbor_mode.offsets(),
+            GridShape::Hexagonal if pos.y % 2 == 1 => &HEX_NEIGHBOR_OFFSETS_ODD,
+            GridShape::Hexagonal => &HEX_NEIGHBOR_OFFSETS_EVEN,
+        }
+    }
+}
+
+impl std::fmt::Display for GridShape {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            GridShape::Square => "Square",
+            GridShape::Hexagonal => "Hexagonal",
+        })
+    }
+}
+
+/// Which of the surrounding tiles count as neighbors of a square tile,
+/// hexagonal tiles always use all six of their neighbors
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum NeighborMode {
+    /// Only the 4 orthogonal neighbors
+    Cardinal,
+    /// Only the 4 diagonal neighbors
+    Diagonal,
+    /// All 8 surrounding tiles
+    #[default]
+    All,
+}
+
+impl NeighborMode {
+    fn offsets(&self) -> &'static [IVec2] {
+        match self {
+            NeighborMode::Cardinal => &CARDINAL_NEIGHBOR_OFFSETS,
+            NeighborMode::Diagonal => &DIAGONAL_NEIGHBOR_OFFSETS,
+            NeighborMode::All => &NEIGHBOR_OFFSETS,
+        }
+    }
+
+    /// Returns the next mode, wrapping around after the last
+    pub fn next(&self) -> Self {
+        match self {
+            NeighborMode::Cardinal => NeighborMode::Diagonal,
+            NeighborMode::Diagonal => NeighborMode::All,
+            NeighborMode::All => NeighborMode::Cardinal,
+        }
+    }
+}
+
+impl std::fmt::Display for NeighborMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            NeighborMode::Cardinal => "Cardinal",
+            NeighborMode::Diagonal => "Diagonal",
+            NeighborMode::All => "All",
+        })
+    }
+}
+
+/// Returns the positions neighboring `pos` on a grid of the given size and shape.
+/// When `wrap_around` is set the grid is a torus, edges connect to the opposite side.
+/// Unlike `TileMap::get_neighbors` this does not borrow the tilemap
+pub fn neighbor_positions(
+    pos: UVec2,
+    size: UVec2,
+    shape: GridShape,
+    neighbor_mode: NeighborMode,
+    wrap_around: bool,
+) -> impl Iterator<Item = UVec2> {
+    let size = size.as_ivec2();
+    let mut neighbors = Vec::with_capacity(NEIGHBOR_OFFSETS.len());
+
+    for &offset in shape.neighbor_offsets(pos, neighbor_mode) {
+        let mut np = pos.as_ivec2() + offset;
+        if wrap_around {
+            np = np.rem_euclid(size);
+        } else if np.x < 0 || np.x >= size.x || np.y < 0 || np.y >= size.y {
+            continue;
+        }
+
+        // Boards smaller than 3 tiles wrap onto the same tiles multiple times
+        let np = np.as_uvec2();
+        if np != pos && !neighbors.contains(&np) {
+            neighbors.push(np);
+        }
+    }
+
+    neighbors.into_iter()
+}
+
 #[derive(Clone)]
 pub struct Tile {
     pub tile_type: TileType,
     pub entity: Option<Entity>,
     pub cover: Option<Entity>,
     pub flag: Option<Entity>,
+    pub question: Option<Entity>,
 }
 
 impl Tile {
@@ -30,6 +190,7 @@ impl Tile {
             entity: None,
             cover: None,
             flag: None,
+            question: None,
         }
     }
 
@@ -46,6 +207,18 @@ pub struct TileMap {
     /// Number of bombs in the tilemap
     bomb_count: u32,
 
+    /// Whether the bombs have been placed
+    generated: bool,
+
+    /// Whether the edges of the tilemap connect to the opposite side
+    wrap_around: bool,
+
+    /// Shape of the tiles, determines the neighbors of a tile
+    shape: GridShape,
+
+    /// Which surrounding tiles of a square tile are its neighbors
+    neighbor_mode: NeighborMode,
+
     /// Grid of tiles
     grid: Vec<Vec<Tile>>,
 }
@@ -56,30 +229,158 @@ impl TileMap {
         Self {
             size,
             bomb_count: 0,
+            generated: false,
+            wrap_around: false,
+            shape: GridShape::Square,
+            neighbor_mode: NeighborMode::All,
             grid: vec![vec![Tile::new(TileType::Empty); size.x as usize]; size.y as usize],
         }
     }
 
+    /// Create a new tilemap with the given size and bombs placed randomly
+    pub fn new(size: UVec2, bomb_count: u32) -> Result<Self, TileMapError> {
+        let mut tile_map = Self::empty(size);
+        tile_map.set_bombs(bomb_count)?;
+        Ok(tile_map)
+    }
+
+    /// Makes the edges of the tilemap connect to the opposite side
+    pub fn with_wrap_around(mut self, wrap_around: bool) -> Self {
+        self.wrap_around = wrap_around;
+        if self.generated {
+            self.recalculate_numbers();
+        }
+        self
+    }
+
+    /// Sets the shape of the tiles
+    pub fn with_shape(mut self, shape: GridShape) -> Self {
+        self.shape = shape;
+        if self.generated {
+            self.recalculate_numbers();
+        }
+        self
+    }
+
+    /// Sets which surrounding tiles of a square tile are its neighbors
+    pub fn with_neighbor_mode(mut self, neighbor_mode: NeighborMode) -> Self {
+        self.neighbor_mode = neighbor_mode;
+        if self.generated {
+            self.recalculate_numbers();
+        }
+        self
+    }
+
+    /// Encodes the bomb positions as a hexadecimal string. The first byte is the
+    /// format version, followed by the width and height and a bit per tile
+    pub fn to_ascii_share(&self) -> String {
+        let tile_count = (self.size.x * self.size.y) as usize;
+        let mut bytes = vec![0; SHARE_CODE_HEADER + tile_count.div_ceil(8)];
+
+        bytes[0] = SHARE_CODE_VERSION;
+        bytes[1..3].copy_from_slice(&(self.size.x as u16).to_be_bytes());
+        bytes[3..5].copy_from_slice(&(self.size.y as u16).to_be_bytes());
+
+        for (index, tile) in self.iter().enumerate() {
+            if tile.is_bomb() {
+                bytes[SHARE_CODE_HEADER + index / 8] |= 1 << (index % 8);
+            }
+        }
+
+        bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+    }
+
+    /// Decodes a string created by `to_ascii_share`, the tilemap is square
+    /// and does not wrap around
+    pub fn from_share_code(code: &str) -> Result<TileMap, ShareCodeError> {
+        let code = code.trim();
+        if !code.is_ascii() {
+            return Err(ShareCodeError::InvalidCharacter);
+        }
+        if !code.len().is_multiple_of(2) {
+            return Err(ShareCodeError::InvalidLength);
+        }
+
+        let bytes = (0..code.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&code[i..i + 2], 16))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| ShareCodeError::InvalidCharacter)?;
+
+        if bytes.len() < SHARE_CODE_HEADER {
+            return Err(ShareCodeError::InvalidLength);
+        }
+        if bytes[0] != SHARE_CODE_VERSION {
+            return Err(ShareCodeError::UnsupportedVersion(bytes[0]));
+        }
+
+        let width = u16::from_be_bytes([bytes[1], bytes[2]]) as u32;
+        let height = u16::from_be_bytes([bytes[3], bytes[4]]) as u32;
+        let tile_count = (width * height) as usize;
+        if tile_count == 0 || bytes.len() != SHARE_CODE_HEADER + tile_count.div_ceil(8) {
+            return Err(ShareCodeError::InvalidLength);
+        }
+
+        let mut tile_map = TileMap::empty(UVec2::new(width, height));
+        let bits = &bytes[SHARE_CODE_HEADER..];
+        for (index, tile) in tile_map.iter_mut().enumerate() {
+            if bits[index / 8] & (1 << (index % 8)) != 0 {
+                tile.tile_type = TileType::Bomb;
+            }
+        }
+
+        tile_map.bomb_count = tile_map.iter().filter(|tile| tile.is_bomb()).count() as u32;
+        tile_map.generated = true;
+        tile_map.recalculate_numbers();
+
+        Ok(tile_map)
+    }
+
     /// Set the number of bombs in the tilemap and places them randomly
-    pub fn set_bombs(&mut self, bomb_count: u32) {
-        assert!(
-            bomb_count <= self.size.x * self.size.y,
-            "Bomb count exceeds grid size"
-        );
+    pub fn set_bombs(&mut self, bomb_count: u32) -> Result<(), TileMapError> {
+        self.set_bombs_seeded(bomb_count, thread_rng().gen())
+    }
+
+    /// Set the number of bombs in the tilemap and places them randomly,
+    /// the same seed always results in the same board
+    pub fn set_bombs_seeded(&mut self, bomb_count: u32, seed: u64) -> Result<(), TileMapError> {
+        self.place_bombs(bomb_count, &mut StdRng::seed_from_u64(seed))
+    }
+
+    fn place_bombs(&mut self, bomb_count: u32, rng: &mut impl Rng) -> Result<(), TileMapError> {
+        let max = self.size.x * self.size.y;
+        if bomb_count > max {
+            return Err(TileMapError::TooManyBombs {
+                requested: bomb_count,
+                max,
+            });
+        }
 
         self.bomb_count = bomb_count;
-        let mut rng = thread_rng();
+        self.generated = true;
+
+        // Clear the bombs of a previous generation
+        for tile in self.iter_mut() {
+            tile.tile_type = TileType::Empty;
+        }
 
         let mut positions = (0..self.size.x)
             .flat_map(|x| (0..self.size.y).map(move |y| (x, y)))
             .collect::<Vec<_>>();
 
-        positions.shuffle(&mut rng);
+        positions.shuffle(rng);
 
         for pos in positions.into_iter().take(bomb_count as usize) {
-            self.get_tile_mut(pos.into()).unwrap().tile_type = TileType::Bomb;
+            self.get_tile_mut(UVec2::from(pos)).unwrap().tile_type = TileType::Bomb;
         }
 
+        self.recalculate_numbers();
+        Ok(())
+    }
+
+    /// Sets the tile type of every tile that is not a bomb
+    /// based on the bombs around it
+    fn recalculate_numbers(&mut self) {
         for y in 0..self.size.y {
             for x in 0..self.size.x {
                 let pos = UVec2::new(x, y);
@@ -95,36 +396,243 @@ impl TileMap {
         }
     }
 
-    /// Returns the tile at the given position
-    pub fn get_tile(&self, pos: UVec2) -> Option<&Tile> {
+    /// Places bombs randomly, keeping the excluded position and the tiles around it free.
+    /// Bombs placed in that region are moved elsewhere, if the board is too crowded
+    /// to keep the whole region free only the excluded position is guaranteed to be safe
+    pub fn set_bombs_avoiding(
+        &mut self,
+        bomb_count: u32,
+        excluded: UVec2,
+        seed: u64,
+    ) -> Result<(), TileMapError> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        self.place_bombs(bomb_count, &mut rng)?;
+
+        let region = self.region_around(excluded, 1);
+
+        for &position in &region {
+            if !self.get_tile(position).is_some_and(|tile| tile.is_bomb()) {
+                continue;
+            }
+
+            let free_positions = (0..self.size.x)
+                .flat_map(|x| (0..self.size.y).map(move |y| UVec2::new(x, y)))
+                .filter(|&pos| !self.get_tile(pos).unwrap().is_bomb())
+                .collect::<Vec<_>>();
+
+            let new_position = free_positions
+                .iter()
+                .filter(|pos| !region.contains(pos))
+                .choose(&mut rng)
+                .or_else(|| {
+                    // Not enough room outside the region, at least keep the
+                    // excluded position itself free
+                    if position == excluded {
+                        free_positions.iter().choose(&mut rng)
+                    } else {
+                        None
+                    }
+                });
+
+            if let Some(&new_position) = new_position {
+                self.move_bomb(position, new_position);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Moves a bomb to a different position, updating the numbers around both positions
+    fn move_bomb(&mut self, from: UVec2, to: UVec2) {
+        self.get_tile_mut(from).unwrap().tile_type = TileType::Empty;
+        self.get_tile_mut(to).unwrap().tile_type = TileType::Bomb;
+
+        self.recalculate_numbers_at(from);
+        self.recalculate_numbers_at(to);
+    }
+
+    /// Recalculates the tile type of the given position and its neighbors
+    /// based on the bombs around them. Bomb tiles are left untouched
+    pub fn recalculate_numbers_at(&mut self, pos: UVec2) {
+        let positions = std::iter::once(pos)
+            .chain(self.get_neighbors(pos))
+            .collect::<Vec<_>>();
+
+        for pos in positions {
+            let count = self.bomb_count(pos);
+            let tile = match self.get_tile_mut(pos) {
+                Some(tile) if !tile.is_bomb() => tile,
+                _ => continue,
+            };
+
+            tile.tile_type = if count > 0 {
+                TileType::Number(count)
+            } else {
+                TileType::Empty
+            };
+        }
+    }
+
+    /// Adds a border of empty tiles around the tilemap, growing it by two tiles in both
+    /// directions. The existing tiles move one tile right and down keeping their entities,
+    /// the tiles of the border are numbered by the bombs next to them
+    pub fn expand_border(&mut self) -> Result<(), TileMapError> {
+        if self.wrap_around || self.shape == GridShape::Hexagonal {
+            return Err(TileMapError::NotExpandable);
+        }
+
+        for row in &mut self.grid {
+            row.insert(0, Tile::new(TileType::Empty));
+            row.push(Tile::new(TileType::Empty));
+        }
+        self.size += UVec2::splat(2);
+        let border_row = vec![Tile::new(TileType::Empty); self.size.x as usize];
+        self.grid.insert(0, border_row.clone());
+        self.grid.push(border_row);
+
+        let last = self.size - UVec2::ONE;
+        let border = self
+            .iter_with_pos()
+            .map(|(pos, _)| pos)
+            .filter(|pos| pos.x == 0 || pos.y == 0 || pos.x == last.x || pos.y == last.y)
+            .collect::<Vec<_>>();
+        for pos in border {
+            self.recalculate_numbers_at(pos);
+        }
+
+        Ok(())
+    }
+
+    /// Returns the tile at the given position. Signed positions are accepted as well,
+    /// positions with a negative coordinate are outside the board
+    pub fn get_tile(&self, pos: impl TryInto<UVec2>) -> Option<&Tile> {
+        let pos = pos.try_into().ok()?;
         self.grid
             .get(pos.y as usize)
             .and_then(|row| row.get(pos.x as usize))
     }
 
-    /// Returns the mutable tile at the given position
-    pub fn get_tile_mut(&mut self, pos: UVec2) -> Option<&mut Tile> {
+    /// Returns the mutable tile at the given position, see `get_tile`
+    pub fn get_tile_mut(&mut self, pos: impl TryInto<UVec2>) -> Option<&mut Tile> {
+        let pos = pos.try_into().ok()?;
         self.grid
             .get_mut(pos.y as usize)
             .and_then(|row| row.get_mut(pos.x as usize))
     }
 
     /// Returns the neighboring tiles of the given position
-    pub fn get_neighbors(&self, pos: UVec2) -> impl Iterator<Item = UVec2> + '_ {
-        let x = pos.x as i32;
-        let y = pos.y as i32;
-
-        NEIGHBOR_OFFSETS.iter().filter_map(move |(dx, dy)| {
-            let nx = x + dx;
-            let ny = y + dy;
-            if nx < 0 || nx >= self.size.x as i32 || ny < 0 || ny >= self.size.y as i32 {
-                None
-            } else {
-                Some(UVec2::new(nx as u32, ny as u32))
+    pub fn get_neighbors(&self, pos: UVec2) -> impl Iterator<Item = UVec2> {
+        neighbor_positions(
+            pos,
+            self.size,
+            self.shape,
+            self.neighbor_mode,
+            self.wrap_around,
+        )
+    }
+
+    /// Returns all positions within `radius` steps of `center`, `center` first and the
+    /// closest positions before those further away. A step goes to any touching tile,
+    /// so the distance is the Chebyshev distance on square grids and the hex distance
+    /// on hexagonal grids whatever the neighbor mode. Wrap-around boards are measured
+    /// the short way around the edges, positions outside the board are left out
+    pub fn region_around(&self, center: UVec2, radius: u32) -> Vec<UVec2> {
+        self.region_where(center, radius, |_, _| true)
+    }
+
+    /// Returns the positions of `region_around` whose center is within `radius` tiles
+    /// of `center` in a straight line, giving a round region instead of a square or
+    /// hexagonal one
+    pub fn region_around_euclidean(&self, center: UVec2, radius: u32) -> Vec<UVec2> {
+        let center_offset = self.grid_offset(center);
+        let size = self.size.as_vec2();
+        let wrap_around = self.wrap_around;
+
+        self.region_where(center, radius, |pos, _| {
+            let mut offset = (self.grid_offset(pos) - center_offset).abs();
+            if wrap_around {
+                offset = offset.min(size - offset);
             }
+            offset.length() <= radius as f32
         })
     }
 
+    /// Returns the positions of `region_around` for which `filter` holds,
+    /// `filter` is given each position along with its distance to `center`
+    pub fn region_where(
+        &self,
+        center: UVec2,
+        radius: u32,
+        filter: impl Fn(UVec2, u32) -> bool,
+    ) -> Vec<UVec2> {
+        if !center.cmplt(self.size).all() {
+            return Vec::new();
+        }
+
+        let mut visited = VisitedSet::new(self.size);
+        visited.insert(center);
+        let mut region = vec![(center, 0)];
+
+        // Breadth first, every ring of positions is one step further than the last
+        let mut index = 0;
+        while let Some(&(pos, distance)) = region.get(index) {
+            index += 1;
+            if distance == radius {
+                continue;
+            }
+
+            for neighbor in neighbor_positions(
+                pos,
+                self.size,
+                self.shape,
+                NeighborMode::All,
+                self.wrap_around,
+            ) {
+                if visited.insert(neighbor) {
+                    region.push((neighbor, distance + 1));
+                }
+            }
+        }
+
+        region
+            .into_iter()
+            .filter(|&(pos, distance)| filter(pos, distance))
+            .map(|(pos, _)| pos)
+            .collect()
+    }
+
+    /// Position of a tile in tiles, shifting the odd rows of hexagonal grids
+    /// by half a tile like they are drawn
+    fn grid_offset(&self, pos: UVec2) -> Vec2 {
+        let shift = match self.shape {
+            GridShape::Hexagonal if pos.y % 2 == 1 => 0.5,
+            _ => 0.,
+        };
+        Vec2::new(pos.x as f32 + shift, pos.y as f32)
+    }
+
+    /// Counts the tiles of a region by what is known about them,
+    /// the parts of `rect` outside the board are ignored
+    pub fn region_stats(&self, rect: IRect) -> RegionStats {
+        let mut stats = RegionStats::default();
+
+        for (pos, tile) in self.iter_with_pos() {
+            if !rect.contains(pos.as_ivec2()) {
+                continue;
+            }
+
+            if tile.flag.is_some() {
+                stats.flagged += 1;
+            } else if tile.cover.is_some() {
+                stats.covered += 1;
+            } else if tile.is_bomb() {
+                stats.known_mines += 1;
+            }
+        }
+
+        stats
+    }
+
     /// Returns the bomb count at a given position
     pub fn bomb_count(&self, pos: UVec2) -> usize {
         self.get_neighbors(pos)
@@ -132,21 +640,369 @@ impl TileMap {
             .count()
     }
 
+    /// Returns the number of flagged tiles around a given position,
+    /// bombs revealed without losing the game count as flagged
+    pub fn flagged_neighbors(&self, pos: UVec2) -> usize {
+        self.get_neighbors(pos)
+            .filter(|&pos| {
+                let tile = self.get_tile(pos).unwrap();
+                tile.flag.is_some() || (tile.cover.is_none() && tile.is_bomb())
+            })
+            .count()
+    }
+
+    /// Checks if the tile at the given position is a revealed number
+    /// with as many flags around it as its number, meaning all other
+    /// neighbors can be revealed at once
+    pub fn can_chord(&self, pos: UVec2) -> bool {
+        match self.get_tile(pos) {
+            Some(Tile {
+                tile_type: TileType::Number(count),
+                cover: None,
+                ..
+            }) => *count == self.flagged_neighbors(pos),
+            _ => false,
+        }
+    }
+
+    /// Returns the covered, unflagged tiles next to a revealed number that already
+    /// has as many flags around it as its number, these can be revealed safely
+    /// assuming the flags are correct
+    pub fn deducible_safe_tiles(&self) -> Vec<UVec2> {
+        let mut safe = self
+            .iter_with_pos()
+            .map(|(pos, _)| pos)
+            .filter(|&pos| self.can_chord(pos))
+            .flat_map(|pos| self.get_neighbors(pos))
+            .filter(|&pos| {
+                let tile = self.get_tile(pos).unwrap();
+                tile.cover.is_some() && tile.flag.is_none()
+            })
+            .collect::<Vec<_>>();
+
+        safe.sort_by_key(|pos| (pos.y, pos.x));
+        safe.dedup();
+        safe
+    }
+
+    /// Checks whether, after revealing `start`, a single pass of constraint propagation
+    /// over the revealed numbers determines whether some covered tile is a bomb or safe,
+    /// meaning the game can be continued without guessing
+    pub fn is_solvable_from(&self, start: UVec2) -> bool {
+        match self.get_tile(start) {
+            Some(tile) if !tile.is_bomb() => {}
+            _ => return false,
+        }
+
+        let revealed = self
+            .floodfill_from(start)
+            .into_iter()
+            .collect::<HashSet<_>>();
+
+        let mut mines = HashSet::new();
+        let mut safe = HashSet::new();
+
+        for &pos in &revealed {
+            let TileType::Number(count) = self.get_tile(pos).unwrap().tile_type else {
+                continue;
+            };
+
+            let unknown = self
+                .get_neighbors(pos)
+                .filter(|neighbor| !revealed.contains(neighbor) && !safe.contains(neighbor))
+                .collect::<Vec<_>>();
+            let known_mines = unknown.iter().filter(|pos| mines.contains(*pos)).count();
+
+            if known_mines == count {
+                safe.extend(unknown.into_iter().filter(|pos| !mines.contains(pos)));
+            } else if unknown.len() == count {
+                mines.extend(unknown);
+            }
+        }
+
+        !mines.is_empty() || !safe.is_empty()
+    }
+
+    /// Estimates the probability of every tile being a bomb from the revealed numbers,
+    /// indexed as `[y][x]`. Revealed tiles are 0, tiles the numbers prove to be bombs or
+    /// safe are 1 or 0, the others get the highest local estimate of the numbers around
+    /// them and fall back to the density of the remaining bombs
+    #[cfg(feature = "debug")]
+    pub fn compute_mine_probabilities(&self) -> Vec<Vec<f32>> {
+        let numbers = self
+            .iter_with_pos()
+            .filter_map(|(pos, tile)| match tile.tile_type {
+                TileType::Number(count) if tile.cover.is_none() => Some((pos, count)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let is_covered = |pos: &UVec2| self.get_tile(*pos).unwrap().cover.is_some();
+
+        // Propagate the constraints until nothing new is learned
+        let mut mines = HashSet::new();
+        let mut safe = HashSet::new();
+        let mut changed = true;
+        while changed {
+            changed = false;
+
+            for &(pos, count) in &numbers {
+                let covered = self
+                    .get_neighbors(pos)
+                    .filter(is_covered)
+                    .collect::<Vec<_>>();
+                let known_mines = covered.iter().filter(|pos| mines.contains(*pos)).count();
+                let unknown = covered
+                    .into_iter()
+                    .filter(|pos| !mines.contains(pos) && !safe.contains(pos))
+                    .collect::<Vec<_>>();
+
+                if unknown.is_empty() {
+                    continue;
+                }
+
+                if known_mines == count {
+                    safe.extend(unknown);
+                    changed = true;
+                } else if known_mines + unknown.len() == count {
+                    mines.extend(unknown);
+                    changed = true;
+                }
+            }
+        }
+
+        let unknown_count = self
+            .iter_with_pos()
+            .filter(|(pos, tile)| {
+                tile.cover.is_some() && !mines.contains(pos) && !safe.contains(pos)
+            })
+            .count();
+        let density = if unknown_count == 0 {
+            0.
+        } else {
+            (self.bomb_count as f32 - mines.len() as f32).max(0.) / unknown_count as f32
+        };
+
+        let mut probabilities = vec![vec![0.; self.size.x as usize]; self.size.y as usize];
+        for (pos, tile) in self.iter_with_pos() {
+            let probability = if tile.cover.is_none() || safe.contains(&pos) {
+                0.
+            } else if mines.contains(&pos) {
+                1.
+            } else {
+                // Highest share of the missing bombs among the unknown tiles of a number
+                numbers
+                    .iter()
+                    .filter(|(number, _)| self.get_neighbors(*number).any(|n| n == pos))
+                    .map(|&(number, count)| {
+                        let covered = self.get_neighbors(number).filter(is_covered);
+                        let (known_mines, unknown) = covered.fold((0, 0), |(m, u), pos| {
+                            if mines.contains(&pos) {
+                                (m + 1, u)
+                            } else if safe.contains(&pos) {
+                                (m, u)
+                            } else {
+                                (m, u + 1)
+                            }
+                        });
+                        (count - known_mines) as f32 / unknown as f32
+                    })
+                    .reduce(f32::max)
+                    .unwrap_or(density)
+            };
+
+            probabilities[pos.y as usize][pos.x as usize] = probability.clamp(0., 1.);
+        }
+
+        probabilities
+    }
+
     /// Returns the size of the tilemap
     pub fn size(&self) -> UVec2 {
         self.size
     }
 
+    /// Returns the shape of the tiles
+    pub fn shape(&self) -> GridShape {
+        self.shape
+    }
+
+    /// Returns the number of bombs in the tilemap
+    pub fn total_bombs(&self) -> u32 {
+        self.bomb_count
+    }
+
+    /// Returns the number of tiles that are still covered, flagged tiles included
+    pub fn count_covered(&self) -> u32 {
+        self.iter().filter(|tile| tile.cover.is_some()).count() as u32
+    }
+
+    /// Returns the number of flagged tiles
+    pub fn count_flagged(&self) -> u32 {
+        self.iter().filter(|tile| tile.flag.is_some()).count() as u32
+    }
+
+    /// Returns the number of bombs, zero until the bombs have been placed
+    pub fn count_mines(&self) -> u32 {
+        self.statistics().bomb_count
+    }
+
+    /// Counts the tiles of every tile type
+    pub fn statistics(&self) -> TileMapStats {
+        let mut stats = TileMapStats::default();
+
+        for tile in self.iter() {
+            match tile.tile_type {
+                TileType::Bomb => stats.bomb_count += 1,
+                TileType::Empty => stats.empty_count += 1,
+                TileType::Number(count) => stats.number_counts[count - 1] += 1,
+            }
+            stats.total += 1;
+        }
+
+        stats
+    }
+
+    /// Returns the number of bombs that have been revealed
+    pub fn count_revealed_mines(&self) -> u32 {
+        self.iter()
+            .filter(|tile| tile.cover.is_none() && tile.is_bomb())
+            .count() as u32
+    }
+
+    /// Returns whether the bombs have been placed
+    pub fn is_generated(&self) -> bool {
+        self.generated
+    }
+
     /// Finds a random empty tile in the tilemap
     pub fn find_empty_tile(&self) -> Option<UVec2> {
         let mut rng = thread_rng();
 
-        (0..self.size.x)
-            .flat_map(|x| (0..self.size.y).map(move |y| UVec2::new(x, y)))
-            .filter(|pos| matches!(self.get_tile(*pos).unwrap().tile_type, TileType::Empty))
+        self.iter_with_pos()
+            .filter(|(_, tile)| matches!(tile.tile_type, TileType::Empty))
+            .map(|(pos, _)| pos)
             .choose(&mut rng)
     }
 
+    /// Returns the positions revealing `start` opens up in breadth first order:
+    /// the start itself and, if it is empty, the tiles flooded through its empty
+    /// neighbors, stopping at numbers. Flagged tiles are neither revealed nor flooded through
+    pub fn floodfill_from(&self, start: UVec2) -> Vec<UVec2> {
+        self.floodfill_with_depth(&[start])
+            .into_iter()
+            .map(|(pos, _)| pos)
+            .collect()
+    }
+
+    /// Flood fills from several tiles at once like `floodfill_from`, along with
+    /// the number of steps from the start tile each position was reached in
+    pub fn floodfill_with_depth(&self, starts: &[UVec2]) -> Vec<(UVec2, u32)> {
+        let mut visited = VisitedSet::new(self.size);
+        let mut queue = starts
+            .iter()
+            .map(|&start| (start, 0))
+            .collect::<VecDeque<_>>();
+        let mut filled = Vec::new();
+
+        while let Some((pos, depth)) = queue.pop_front() {
+            if !visited.insert(pos) {
+                continue;
+            }
+            let Some(tile) = self.get_tile(pos) else {
+                continue;
+            };
+            if tile.flag.is_some() {
+                continue;
+            }
+
+            filled.push((pos, depth));
+            if tile.tile_type == TileType::Empty {
+                queue.extend(
+                    self.get_neighbors(pos)
+                        .map(|neighbor| (neighbor, depth + 1)),
+                );
+            }
+        }
+
+        filled
+    }
+
+    /// Returns the number of tiles revealing `start` opens up
+    pub fn opening_size(&self, start: UVec2) -> u32 {
+        self.floodfill_from(start).len() as u32
+    }
+
+    /// Returns the number of tiles in the largest connected region of empty tiles,
+    /// the region a single click opens up at most
+    pub fn largest_empty_region(&self) -> u32 {
+        let mut visited = VisitedSet::new(self.size);
+        let mut stack = Vec::new();
+        let mut largest = 0;
+
+        for (start, tile) in self.iter_with_pos() {
+            if tile.tile_type != TileType::Empty || !visited.insert(start) {
+                continue;
+            }
+
+            let mut region = 0;
+            stack.push(start);
+            while let Some(pos) = stack.pop() {
+                region += 1;
+                stack.extend(self.get_neighbors(pos).filter(|&neighbor| {
+                    self.get_tile(neighbor)
+                        .is_some_and(|tile| tile.tile_type == TileType::Empty)
+                        && visited.insert(neighbor)
+                }));
+            }
+            largest = largest.max(region);
+        }
+
+        largest
+    }
+
+    /// Renders the tilemap with one pixel per tile, row 0 at the top.
+    /// Tiles in `flagged` are drawn as flags, tiles in `revealed` by their content
+    /// and all other tiles as covered
+    pub fn render_minimap(
+        &self,
+        revealed: &HashSet<UVec2>,
+        flagged: &HashSet<UVec2>,
+        game_assets: &GameAssets,
+    ) -> Image {
+        let data = self
+            .iter_with_pos()
+            .flat_map(|(pos, tile)| {
+                let color = if flagged.contains(&pos) {
+                    game_assets.tile_flagged
+                } else if !revealed.contains(&pos) {
+                    game_assets.tile_covered
+                } else {
+                    match tile.tile_type {
+                        TileType::Bomb => game_assets.tile_mine,
+                        TileType::Empty => game_assets.tile_uncovered,
+                        TileType::Number(count) => game_assets.count_color(count),
+                    }
+                };
+                color.as_rgba_u8()
+            })
+            .collect();
+
+        let mut image = Image::new(
+            Extent3d {
+                width: self.size.x,
+                height: self.size.y,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            data,
+            TextureFormat::Rgba8UnormSrgb,
+            RenderAssetUsages::default(),
+        );
+        // Keep the tiles sharp when the image is scaled up
+        image.sampler = ImageSampler::nearest();
+        image
+    }
+
     pub fn iter(&self) -> impl Iterator<Item = &Tile> {
         self.grid.iter().flat_map(|row| row.iter())
     }
@@ -154,8 +1010,151 @@ impl TileMap {
     pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut Tile> {
         self.grid.iter_mut().flat_map(|row| row.iter_mut())
     }
+
+    /// Iterates over the tiles row by row along with their positions
+    pub fn iter_with_pos(&self) -> impl Iterator<Item = (UVec2, &Tile)> + '_ {
+        self.grid.iter().enumerate().flat_map(|(y, row)| {
+            row.iter()
+                .enumerate()
+                .map(move |(x, tile)| (UVec2::new(x as u32, y as u32), tile))
+        })
+    }
+
+    /// Iterates mutably over the tiles row by row along with their positions
+    pub fn iter_mut_with_pos(&mut self) -> impl Iterator<Item = (UVec2, &mut Tile)> + '_ {
+        self.grid.iter_mut().enumerate().flat_map(|(y, row)| {
+            row.iter_mut()
+                .enumerate()
+                .map(move |(x, tile)| (UVec2::new(x as u32, y as u32), tile))
+        })
+    }
+}
+
+/// Set of visited positions of a flood fill, a flat grid of flags indexed by
+/// `y * width + x` which avoids hashing every visited tile
+pub struct VisitedSet {
+    width: u32,
+    visited: Vec<bool>,
+}
+
+impl VisitedSet {
+    /// Creates an empty set for a board of the given size
+    pub fn new(size: UVec2) -> Self {
+        Self {
+            width: size.x,
+            visited: vec![false; (size.x * size.y) as usize],
+        }
+    }
+
+    /// Marks a position as visited, returns whether it was not visited before.
+    /// Positions outside the board are never marked
+    pub fn insert(&mut self, pos: UVec2) -> bool {
+        if pos.x >= self.width {
+            return false;
+        }
+        match self.visited.get_mut((pos.y * self.width + pos.x) as usize) {
+            Some(visited) if !*visited => {
+                *visited = true;
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+/// What is known about the tiles of a region of the board
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct RegionStats {
+    pub flagged: u32,
+    /// Covered tiles without a flag
+    pub covered: u32,
+    /// Revealed bombs
+    pub known_mines: u32,
+}
+
+impl RegionStats {
+    /// Returns the most bombs the region can contain
+    pub fn max_mines(&self) -> u32 {
+        self.flagged + self.covered + self.known_mines
+    }
+}
+
+/// Number of tiles of every tile type on the board
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct TileMapStats {
+    pub bomb_count: u32,
+    pub empty_count: u32,
+    /// Tiles showing each bomb count, the first element counting the ones
+    pub number_counts: [u32; 8],
+    pub total: u32,
+}
+
+impl std::fmt::Display for TileMapStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} mines, {} empty tiles",
+            self.bomb_count, self.empty_count
+        )?;
+        for (index, amount) in self.number_counts.iter().enumerate() {
+            if *amount > 0 {
+                write!(f, ", {amount} {}s", index + 1)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Error returned when decoding a share code fails
+#[derive(Debug, Clone, PartialEq)]
+pub enum ShareCodeError {
+    /// The code contains characters that are not hexadecimal digits
+    InvalidCharacter,
+    /// The code was created by a newer format
+    UnsupportedVersion(u8),
+    /// The code does not hold a bit for every tile of its board size
+    InvalidLength,
+}
+
+impl std::fmt::Display for ShareCodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ShareCodeError::InvalidCharacter => f.write_str("invalid character"),
+            ShareCodeError::UnsupportedVersion(version) => {
+                write!(f, "unsupported version {version}")
+            }
+            ShareCodeError::InvalidLength => f.write_str("invalid length"),
+        }
+    }
+}
+
+impl std::error::Error for ShareCodeError {}
+
+/// Error returned when bombs cannot be placed on a tilemap
+#[derive(Debug, Clone, PartialEq)]
+pub enum TileMapError {
+    /// More bombs were requested than the tilemap has tiles
+    TooManyBombs { requested: u32, max: u32 },
+    /// The tilemap wraps around or has hexagonal tiles, adding a border
+    /// would change the neighbors and numbers of the existing tiles
+    NotExpandable,
+}
+
+impl std::fmt::Display for TileMapError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TileMapError::TooManyBombs { requested, max } => {
+                write!(f, "{requested} bombs do not fit {max} tiles")
+            }
+            TileMapError::NotExpandable => {
+                f.write_str("wrap-around and hexagonal boards cannot be expanded")
+            }
+        }
+    }
 }
 
+impl std::error::Error for TileMapError {}
+
 #[cfg(feature = "debug")]
 impl std::fmt::Debug for TileMap {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -170,6 +1169,28 @@ impl std::fmt::Debug for TileMap {
     }
 }
 
+/// Plain ASCII representation of the tilemap without color codes
+#[cfg(feature = "debug")]
+impl std::fmt::Display for TileMap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in &self.grid {
+            for tile in row {
+                let c = match (tile.flag, tile.cover, tile.tile_type) {
+                    (Some(_), _, _) => 'F',
+                    (_, Some(_), _) if tile.question.is_some() => '?',
+                    (_, Some(_), _) => '#',
+                    (_, _, TileType::Bomb) => 'B',
+                    (_, _, TileType::Empty) => ' ',
+                    (_, _, TileType::Number(n)) => char::from_digit(n as u32, 10).unwrap_or('?'),
+                };
+                write!(f, "{c}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum TileType {
     Empty,
@@ -207,3 +1228,282 @@ impl std::fmt::Debug for TileType {
         )
     }
 }
+
+#[cfg(test)]
+impl TileMap {
+    /// Builds a generated tilemap with bombs on the given positions and numbers around them
+    pub(crate) fn with_bombs(size: UVec2, bombs: &[UVec2]) -> Self {
+        let mut tile_map = Self::empty(size);
+        for &bomb in bombs {
+            tile_map.get_tile_mut(bomb).unwrap().tile_type = TileType::Bomb;
+        }
+        tile_map.bomb_count = bombs.len() as u32;
+        tile_map.generated = true;
+        tile_map.recalculate_numbers();
+        tile_map
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn bomb_count_corner() {
+        let tile_map = TileMap::with_bombs(
+            UVec2::new(3, 3),
+            &[UVec2::new(1, 0), UVec2::new(1, 1), UVec2::new(2, 2)],
+        );
+
+        assert_eq!(tile_map.bomb_count(UVec2::new(0, 0)), 2);
+    }
+
+    #[test]
+    fn bomb_count_edge() {
+        let tile_map = TileMap::with_bombs(
+            UVec2::new(3, 3),
+            &[
+                UVec2::new(0, 0),
+                UVec2::new(2, 1),
+                UVec2::new(1, 1),
+                UVec2::new(0, 2),
+            ],
+        );
+
+        assert_eq!(tile_map.bomb_count(UVec2::new(1, 0)), 3);
+    }
+
+    #[test]
+    fn bomb_count_center() {
+        let tile_map = TileMap::with_bombs(
+            UVec2::new(3, 3),
+            &[UVec2::new(0, 0), UVec2::new(2, 0), UVec2::new(1, 2)],
+        );
+
+        assert_eq!(tile_map.bomb_count(UVec2::new(1, 1)), 3);
+    }
+
+    #[test]
+    fn bomb_count_full_board() {
+        let bombs = (0..3)
+            .flat_map(|y| (0..3).map(move |x| UVec2::new(x, y)))
+            .collect::<Vec<_>>();
+        let tile_map = TileMap::with_bombs(UVec2::new(3, 3), &bombs);
+
+        assert_eq!(tile_map.bomb_count(UVec2::new(1, 1)), 8);
+        assert_eq!(tile_map.bomb_count(UVec2::new(0, 0)), 3);
+        assert_eq!(tile_map.bomb_count(UVec2::new(1, 0)), 5);
+    }
+
+    #[test]
+    fn floodfill_fills_empty_board() {
+        let tile_map = TileMap::empty(UVec2::new(5, 5));
+        let mut filled = tile_map.floodfill_from(UVec2::new(2, 2));
+
+        assert_eq!(filled.len(), 25);
+        assert_eq!(filled[0], UVec2::new(2, 2));
+
+        filled.sort_by_key(|pos| (pos.y, pos.x));
+        filled.dedup();
+        assert_eq!(filled.len(), 25);
+    }
+
+    #[test]
+    fn floodfill_stops_at_numbers() {
+        // A wall of bombs in the middle column, the column left of it shows numbers
+        let bombs = (0..5).map(|y| UVec2::new(2, y)).collect::<Vec<_>>();
+        let tile_map = TileMap::with_bombs(UVec2::new(5, 5), &bombs);
+
+        let mut filled = tile_map.floodfill_from(UVec2::new(0, 0));
+        filled.sort_by_key(|pos| (pos.x, pos.y));
+
+        let expected = (0..2)
+            .flat_map(|x| (0..5).map(move |y| UVec2::new(x, y)))
+            .collect::<Vec<_>>();
+        assert_eq!(filled, expected);
+    }
+
+    #[test]
+    fn floodfill_opens_region_around_bomb() {
+        let tile_map = TileMap::with_bombs(UVec2::new(5, 5), &[UVec2::new(4, 4)]);
+        let filled = tile_map.floodfill_from(UVec2::new(0, 0));
+
+        // Everything but the bomb, the numbers around it included
+        assert_eq!(filled.len(), 24);
+        assert!(!filled.contains(&UVec2::new(4, 4)));
+        assert!(filled.contains(&UVec2::new(3, 3)));
+    }
+
+    #[test]
+    fn floodfill_skips_flagged_tiles() {
+        let mut tile_map = TileMap::empty(UVec2::new(5, 5));
+        for y in 0..5 {
+            tile_map.get_tile_mut(UVec2::new(2, y)).unwrap().flag = Some(Entity::PLACEHOLDER);
+        }
+
+        let filled = tile_map.floodfill_from(UVec2::new(0, 0));
+
+        assert_eq!(filled.len(), 10);
+        assert!(filled.iter().all(|pos| pos.x < 2));
+    }
+
+    #[test]
+    fn floodfill_with_depth_counts_steps() {
+        let tile_map = TileMap::empty(UVec2::new(5, 5));
+        let filled = tile_map.floodfill_with_depth(&[UVec2::new(2, 2)]);
+        let depth_of = |position: UVec2| {
+            filled
+                .iter()
+                .find(|(pos, _)| *pos == position)
+                .map(|(_, depth)| *depth)
+        };
+
+        assert_eq!(filled.len(), 25);
+        assert_eq!(depth_of(UVec2::new(2, 2)), Some(0));
+        assert_eq!(depth_of(UVec2::new(1, 1)), Some(1));
+        assert_eq!(depth_of(UVec2::new(0, 0)), Some(2));
+        assert_eq!(depth_of(UVec2::new(4, 2)), Some(2));
+
+        // Depths never decrease, the fill is breadth first
+        assert!(filled.windows(2).all(|pair| pair[0].1 <= pair[1].1));
+    }
+
+    #[test]
+    fn floodfill_with_depth_from_several_starts() {
+        let tile_map = TileMap::empty(UVec2::new(5, 1));
+        let filled = tile_map.floodfill_with_depth(&[UVec2::new(0, 0), UVec2::new(4, 0)]);
+
+        assert_eq!(filled.len(), 5);
+        assert!(filled.contains(&(UVec2::new(2, 0), 2)));
+        assert!(filled.iter().all(|(_, depth)| *depth <= 2));
+    }
+
+    #[test]
+    fn statistics_of_known_board() {
+        let tile_map = TileMap::with_bombs(UVec2::new(3, 3), &[UVec2::new(0, 0), UVec2::new(2, 0)]);
+        let stats = tile_map.statistics();
+
+        assert_eq!(stats.bomb_count, 2);
+        assert_eq!(stats.empty_count, 3);
+        assert_eq!(stats.number_counts, [2, 2, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(stats.total, 9);
+        assert_eq!(
+            stats.bomb_count + stats.empty_count + stats.number_counts.iter().sum::<u32>(),
+            stats.total
+        );
+        assert_eq!(stats.to_string(), "2 mines, 3 empty tiles, 2 1s, 2 2s");
+    }
+
+    #[test]
+    fn region_around_in_corner() {
+        let tile_map = TileMap::empty(UVec2::new(5, 5));
+        let region = tile_map.region_around(UVec2::ZERO, 1);
+
+        assert_eq!(region[0], UVec2::ZERO);
+        assert_eq!(region.len(), 4);
+    }
+
+    #[test]
+    fn region_around_is_chebyshev_on_square_grids() {
+        let tile_map = TileMap::empty(UVec2::new(7, 7)).with_neighbor_mode(NeighborMode::Cardinal);
+
+        assert_eq!(tile_map.region_around(UVec2::new(3, 3), 1).len(), 9);
+        assert_eq!(tile_map.region_around(UVec2::new(3, 3), 2).len(), 25);
+        assert_eq!(tile_map.region_around(UVec2::new(3, 3), 10).len(), 49);
+    }
+
+    #[test]
+    fn region_around_wraps_around() {
+        let tile_map = TileMap::empty(UVec2::new(5, 5)).with_wrap_around(true);
+        let region = tile_map.region_around(UVec2::ZERO, 1);
+
+        assert_eq!(region.len(), 9);
+        assert!(region.contains(&UVec2::new(4, 4)));
+    }
+
+    #[test]
+    fn region_around_is_hex_distance_on_hexagonal_grids() {
+        let tile_map = TileMap::empty(UVec2::new(7, 7)).with_shape(GridShape::Hexagonal);
+        let center = UVec2::new(3, 3);
+
+        let region = tile_map.region_around(center, 1);
+        assert_eq!(region.len(), 7);
+        assert!(tile_map
+            .get_neighbors(center)
+            .all(|pos| region.contains(&pos)));
+        assert_eq!(tile_map.region_around(center, 2).len(), 19);
+    }
+
+    #[test]
+    fn region_around_euclidean_is_round() {
+        let tile_map = TileMap::empty(UVec2::new(7, 7));
+        let region = tile_map.region_around_euclidean(UVec2::new(3, 3), 2);
+
+        // The tiles touching the center and the four tiles two steps straight away
+        assert_eq!(region.len(), 13);
+        assert!(region.contains(&UVec2::new(2, 2)));
+        assert!(region.contains(&UVec2::new(3, 1)));
+        assert!(!region.contains(&UVec2::new(2, 1)));
+    }
+
+    #[test]
+    fn region_where_gives_rings() {
+        let tile_map = TileMap::empty(UVec2::new(7, 7));
+        let ring = tile_map.region_where(UVec2::new(3, 3), 2, |_, distance| distance == 2);
+
+        assert_eq!(ring.len(), 16);
+        assert!(tile_map
+            .region_where(UVec2::new(3, 3), 4, |_, distance| distance == 4)
+            .is_empty());
+    }
+
+    proptest! {
+        #[test]
+        fn set_bombs_places_requested_bombs(
+            cols in 1u32..30,
+            rows in 1u32..30,
+            bomb_count in 0u32..1000,
+            seed: u64,
+        ) {
+            let mut tile_map = TileMap::empty(UVec2::new(cols, rows));
+            let max = cols * rows;
+
+            match tile_map.set_bombs_seeded(bomb_count, seed) {
+                Ok(()) => {
+                    let stats = tile_map.statistics();
+                    prop_assert_eq!(
+                        stats.bomb_count + stats.empty_count + stats.number_counts.iter().sum::<u32>(),
+                        stats.total
+                    );
+                    prop_assert_eq!(stats.total, max);
+                    prop_assert!(bomb_count <= max);
+                    prop_assert_eq!(tile_map.count_mines(), bomb_count);
+                    prop_assert_eq!(tile_map.total_bombs(), bomb_count);
+                }
+                Err(err) => {
+                    prop_assert!(bomb_count > max);
+                    prop_assert_eq!(err, TileMapError::TooManyBombs { requested: bomb_count, max });
+                }
+            }
+        }
+
+        #[test]
+        fn set_bombs_avoiding_keeps_start_free(
+            cols in 1u32..30,
+            rows in 1u32..30,
+            bomb_ratio in 0f32..1.,
+            seed: u64,
+        ) {
+            let size = UVec2::new(cols, rows);
+            let bomb_count = ((size.x * size.y - 1) as f32 * bomb_ratio) as u32;
+            let excluded = size / 2;
+            let mut tile_map = TileMap::empty(size);
+
+            tile_map.set_bombs_avoiding(bomb_count, excluded, seed).unwrap();
+            prop_assert_eq!(tile_map.count_mines(), bomb_count);
+            prop_assert!(!tile_map.get_tile(excluded).unwrap().is_bomb());
+        }
+    }
+}
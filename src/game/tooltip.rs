@@ -0,0 +1,158 @@
+use bevy::prelude::*;
+
+use crate::style::prelude::*;
+
+use super::{
+    board::Board, camera::CameraController, tilemap::TileType, BoardInitialized, GameState,
+    OnGameScreen,
+};
+
+/// Seconds the cursor has to rest on a tile before its tooltip is shown
+const HOVER_DELAY: f32 = 0.3;
+
+/// Distance between the cursor and the tooltip in pixels
+const TOOLTIP_OFFSET: Vec2 = Vec2::new(16., 16.);
+
+/// Text next to the cursor describing the hovered tile
+#[derive(Component)]
+struct Tooltip;
+
+/// The single tooltip of the current game, hidden while no tile is described
+#[derive(Resource)]
+struct TooltipEntity(Entity);
+
+/// Tile under the cursor and how long it has been hovered
+#[derive(Resource)]
+struct HoverTimer {
+    tile: Option<UVec2>,
+    timer: Timer,
+}
+
+impl Default for HoverTimer {
+    fn default() -> Self {
+        Self {
+            tile: None,
+            timer: Timer::from_seconds(HOVER_DELAY, TimerMode::Once),
+        }
+    }
+}
+
+/// Shows the position of a hovered covered tile, or the bomb count of a hovered number
+pub struct TooltipPlugin;
+
+impl Plugin for TooltipPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<HoverTimer>()
+            .add_systems(
+                Update,
+                (
+                    Self::setup_tooltip.run_if(on_event::<BoardInitialized>()),
+                    Self::update_tooltip.run_if(resource_exists::<TooltipEntity>),
+                )
+                    .chain()
+                    .run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(
+                OnExit(GameState::Playing),
+                Self::hide_tooltip.run_if(resource_exists::<TooltipEntity>),
+            );
+    }
+}
+
+impl TooltipPlugin {
+    fn setup_tooltip(
+        mut commands: Commands,
+        mut hover_timer: ResMut<HoverTimer>,
+        ui_assets: Res<UiAssets>,
+    ) {
+        *hover_timer = HoverTimer::default();
+
+        let tooltip = commands
+            .spawn((
+                TextBundle {
+                    text: Text::from_section(
+                        "",
+                        TextStyle {
+                            font_size: 20.,
+                            color: ui_assets.foreground,
+                            font: ui_assets.font.clone(),
+                        },
+                    ),
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        padding: UiRect::all(Val::Px(4.)),
+                        ..Default::default()
+                    },
+                    background_color: ui_assets.background_alt.into(),
+                    visibility: Visibility::Hidden,
+                    z_index: ZIndex::Global(1),
+                    ..Default::default()
+                },
+                Tooltip,
+                OnGameScreen,
+            ))
+            .id();
+        commands.insert_resource(TooltipEntity(tooltip));
+    }
+
+    fn update_tooltip(
+        time: Res<Time>,
+        window: Query<&Window>,
+        board: Res<Board>,
+        camera_controller: Res<CameraController>,
+        tooltip_entity: Res<TooltipEntity>,
+        mut hover_timer: ResMut<HoverTimer>,
+        mut tooltips: Query<(&mut Text, &mut Style, &mut Visibility), With<Tooltip>>,
+    ) {
+        let Ok((mut text, mut style, mut visibility)) = tooltips.get_mut(tooltip_entity.0) else {
+            return;
+        };
+
+        let window = window.single();
+        let cursor = window.cursor_position();
+        let hovered_tile =
+            cursor.and_then(|cursor| board.mouse_to_tile(window, cursor, &camera_controller));
+
+        // Moving to another tile starts the delay over
+        if hovered_tile != hover_timer.tile {
+            hover_timer.tile = hovered_tile;
+            hover_timer.timer.reset();
+            *visibility = Visibility::Hidden;
+            return;
+        }
+
+        if !hover_timer.timer.tick(time.delta()).finished() {
+            return;
+        }
+
+        let description = hovered_tile
+            .and_then(|position| Some((position, board.tile_map.get_tile(position)?)))
+            .and_then(|(position, tile)| match tile.tile_type {
+                _ if tile.cover.is_some() => Some(format!("({}, {})", position.x, position.y)),
+                TileType::Number(count) => Some(format!("Neighbors: {count}")),
+                _ => None,
+            });
+
+        match (description, cursor) {
+            (Some(description), Some(cursor)) => {
+                text.sections[0].value = description;
+                style.left = Val::Px(cursor.x + TOOLTIP_OFFSET.x);
+                style.top = Val::Px(cursor.y + TOOLTIP_OFFSET.y);
+                *visibility = Visibility::Inherited;
+            }
+            _ => *visibility = Visibility::Hidden,
+        }
+    }
+
+    /// Hides the tooltip while the game is paused or finished
+    fn hide_tooltip(
+        tooltip_entity: Res<TooltipEntity>,
+        mut hover_timer: ResMut<HoverTimer>,
+        mut tooltips: Query<&mut Visibility, With<Tooltip>>,
+    ) {
+        hover_timer.tile = None;
+        if let Ok(mut visibility) = tooltips.get_mut(tooltip_entity.0) {
+            *visibility = Visibility::Hidden;
+        }
+    }
+}
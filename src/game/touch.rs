@@ -0,0 +1,91 @@
+use bevy::prelude::*;
+
+use super::{
+    board::Board, camera::CameraController, GameState, TileChord, TileFlagged, TileRevealed,
+};
+
+/// Seconds a touch has to be held to flag instead of reveal
+const LONG_PRESS_DURATION: f32 = 0.2;
+
+/// Touch on the board that has not been handled yet
+#[derive(Resource)]
+struct TouchState {
+    /// Elapsed time in seconds when the first finger touched the screen
+    start: f32,
+    /// Tile under the first finger
+    tile: UVec2,
+    /// Largest number of fingers on the screen at the same time during the touch
+    fingers: usize,
+}
+
+pub struct TouchPlugin;
+
+impl Plugin for TouchPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            Self::handle_touch_input.run_if(in_state(GameState::Playing)),
+        );
+    }
+}
+
+impl TouchPlugin {
+    /// Tapping reveals a tile, holding flags it and tapping with two fingers chords.
+    /// Pinching is handled by the camera, it lasts too long to count as a tap
+    #[allow(clippy::too_many_arguments)]
+    fn handle_touch_input(
+        mut commands: Commands,
+        touches: Res<Touches>,
+        time: Res<Time>,
+        window: Query<&Window>,
+        board: Res<Board>,
+        camera_controller: Res<CameraController>,
+        touch_state: Option<ResMut<TouchState>>,
+        mut tile_revealed_evw: EventWriter<TileRevealed>,
+        mut tile_flagged_evw: EventWriter<TileFlagged>,
+        mut tile_chord_evw: EventWriter<TileChord>,
+    ) {
+        let window = window.single();
+        let now = time.elapsed_seconds();
+        let fingers = touches.iter().count();
+
+        let Some(mut touch_state) = touch_state else {
+            // A new touch starts when the first finger touches a tile
+            if fingers == 1 && touches.any_just_pressed() {
+                let tile = touches
+                    .first_pressed_position()
+                    .and_then(|position| board.mouse_to_tile(window, position, &camera_controller));
+
+                if let Some(tile) = tile {
+                    commands.insert_resource(TouchState {
+                        start: now,
+                        tile,
+                        fingers,
+                    });
+                }
+            }
+            return;
+        };
+
+        touch_state.fingers = touch_state.fingers.max(fingers);
+        let held = now - touch_state.start;
+
+        if touch_state.fingers == 1 && fingers == 1 && held >= LONG_PRESS_DURATION {
+            // Flag as soon as the press is long enough instead of waiting for the release
+            tile_flagged_evw.send(TileFlagged {
+                position: touch_state.tile,
+            });
+            commands.remove_resource::<TouchState>();
+        } else if fingers == 0 {
+            if held < LONG_PRESS_DURATION {
+                let position = touch_state.tile;
+                if touch_state.fingers > 1 {
+                    tile_chord_evw.send(TileChord { position });
+                } else {
+                    tile_revealed_evw.send(TileRevealed { position });
+                }
+            }
+            commands.remove_resource::<TouchState>();
+        }
+    }
+}
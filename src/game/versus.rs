@@ -0,0 +1,299 @@
+use std::{collections::HashSet, time::Duration};
+
+use bevy::prelude::*;
+use rand::{seq::IteratorRandom as _, thread_rng};
+
+use crate::style::prelude::*;
+
+use super::{
+    board::{Board, BoardCounts},
+    hud::format_time,
+    options::{GameMode, GameOptions},
+    tilemap::{TileMap, TileMapError, TileType},
+    BoardInitialized, GameResult, GameState, OnGameScreen,
+};
+
+/// Seconds between two moves of the computer opponent
+const OPPONENT_MOVE_INTERVAL: f32 = 1.;
+
+/// Mixed into the seed of a seeded game, so the opponent gets different bombs
+/// while replaying the same seed still gives the same opponent board
+const OPPONENT_SEED_SALT: u64 = 0x9e37_79b9_7f4a_7c15;
+
+/// Width of the opponent board in pixels, the height follows the board
+const OPPONENT_BOARD_WIDTH: f32 = 200.;
+
+/// Distance between the opponent board and the corner of the window
+const OPPONENT_BOARD_MARGIN: f32 = 10.;
+
+/// Computer opponent of a versus game, clearing its own board of the same size
+/// with different bombs. It makes one move per second, flagging or revealing the
+/// tiles a revealed number proves to be bombs or safe and guessing otherwise
+#[derive(Resource)]
+pub struct Opponent {
+    tile_map: TileMap,
+    revealed: HashSet<UVec2>,
+    flagged: HashSet<UVec2>,
+    /// Time the opponent has been playing, it stops once it has finished
+    timer: Duration,
+    move_timer: Timer,
+    /// Whether the opponent has revealed a bomb, it stops playing
+    lost: bool,
+}
+
+impl Opponent {
+    fn new(game_options: &GameOptions) -> Result<Self, TileMapError> {
+        let tile_map = match game_options.seed {
+            Some(seed) => {
+                let mut tile_map = TileMap::empty(game_options.size);
+                tile_map.set_bombs_seeded(game_options.bomb_count, seed ^ OPPONENT_SEED_SALT)?;
+                tile_map
+            }
+            None => TileMap::new(game_options.size, game_options.bomb_count)?,
+        }
+        .with_wrap_around(game_options.wrap_around)
+        .with_shape(game_options.grid_shape)
+        .with_neighbor_mode(game_options.neighbor_mode);
+
+        let mut opponent = Self {
+            revealed: HashSet::new(),
+            flagged: HashSet::new(),
+            timer: Duration::ZERO,
+            move_timer: Timer::from_seconds(OPPONENT_MOVE_INTERVAL, TimerMode::Repeating),
+            lost: false,
+            tile_map,
+        };
+
+        // Start from an empty tile like a safe start, so the opponent does not lose
+        // on its first guess
+        if let Some(start) = opponent.tile_map.find_empty_tile() {
+            opponent.reveal(start);
+        }
+
+        Ok(opponent)
+    }
+
+    /// Checks if all non-bomb tiles of the opponent have been revealed
+    pub fn is_won(&self) -> bool {
+        let size = self.tile_map.size();
+        !self.lost && self.revealed.len() as u32 + self.tile_map.total_bombs() == size.x * size.y
+    }
+
+    fn is_finished(&self) -> bool {
+        self.lost || self.is_won()
+    }
+
+    /// Reveals a tile, flooding through empty tiles like a click would
+    fn reveal(&mut self, position: UVec2) {
+        let mut queue = vec![position];
+        while let Some(position) = queue.pop() {
+            if self.flagged.contains(&position) || !self.revealed.insert(position) {
+                continue;
+            }
+
+            match self.tile_map.get_tile(position).map(|tile| tile.tile_type) {
+                Some(TileType::Bomb) => self.lost = true,
+                Some(TileType::Empty) => queue.extend(self.tile_map.get_neighbors(position)),
+                _ => {}
+            }
+        }
+    }
+
+    /// Flags or reveals the tiles around the first revealed number that proves them
+    /// to be bombs or safe, and reveals a random covered tile if there is none
+    fn play_move(&mut self) {
+        let numbers = self
+            .revealed
+            .iter()
+            .filter_map(
+                |&position| match self.tile_map.get_tile(position)?.tile_type {
+                    TileType::Number(count) => Some((position, count)),
+                    _ => None,
+                },
+            )
+            .collect::<Vec<_>>();
+
+        for (position, count) in numbers {
+            let covered = self
+                .tile_map
+                .get_neighbors(position)
+                .filter(|neighbor| !self.revealed.contains(neighbor))
+                .collect::<Vec<_>>();
+            let unknown = covered
+                .iter()
+                .copied()
+                .filter(|neighbor| !self.flagged.contains(neighbor))
+                .collect::<Vec<_>>();
+
+            if unknown.is_empty() {
+                continue;
+            }
+
+            if covered.len() - unknown.len() == count {
+                for neighbor in unknown {
+                    self.reveal(neighbor);
+                }
+                return;
+            }
+            if covered.len() == count {
+                self.flagged.extend(unknown);
+                return;
+            }
+        }
+
+        let guess = self
+            .tile_map
+            .iter_with_pos()
+            .map(|(position, _)| position)
+            .filter(|position| {
+                !self.revealed.contains(position) && !self.flagged.contains(position)
+            })
+            .choose(&mut thread_rng());
+        if let Some(guess) = guess {
+            self.reveal(guess);
+        }
+    }
+
+    fn render(&self, game_assets: &GameAssets) -> Image {
+        self.tile_map
+            .render_minimap(&self.revealed, &self.flagged, game_assets)
+    }
+}
+
+#[derive(Component)]
+struct OpponentBoard;
+
+#[derive(Component)]
+struct OpponentTimerText;
+
+/// Plays a computer opponent alongside the player in versus games,
+/// its board is shown in the bottom right corner
+pub struct VersusPlugin;
+
+impl Plugin for VersusPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                Self::setup_opponent.run_if(on_event::<BoardInitialized>()),
+                (Self::play_opponent, Self::check_opponent_finished)
+                    .run_if(resource_exists::<Opponent>),
+            )
+                .chain()
+                .run_if(in_state(GameState::Playing)),
+        );
+    }
+}
+
+impl VersusPlugin {
+    fn setup_opponent(
+        mut commands: Commands,
+        game_options: Res<GameOptions>,
+        game_assets: Res<GameAssets>,
+        ui_assets: Res<UiAssets>,
+        mut images: ResMut<Assets<Image>>,
+    ) {
+        if game_options.game_mode != GameMode::Versus {
+            commands.remove_resource::<Opponent>();
+            return;
+        }
+
+        let opponent = match Opponent::new(&game_options) {
+            Ok(opponent) => opponent,
+            Err(err) => {
+                warn!("Could not create the opponent board: {err}");
+                commands.remove_resource::<Opponent>();
+                return;
+            }
+        };
+        let image = images.add(opponent.render(&game_assets));
+        let size = game_options.size.as_vec2();
+
+        commands
+            .spawn((
+                NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        bottom: Val::Px(OPPONENT_BOARD_MARGIN),
+                        right: Val::Px(OPPONENT_BOARD_MARGIN),
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                OnGameScreen,
+            ))
+            .with_children(|parent| {
+                parent.spawn((
+                    TextBundle::from_section(
+                        format!("CPU {}", format_time(Duration::ZERO)),
+                        TextStyle {
+                            font_size: 25.,
+                            color: ui_assets.foreground,
+                            font: ui_assets.font.clone(),
+                        },
+                    ),
+                    OpponentTimerText,
+                ));
+                parent.spawn((
+                    ImageBundle {
+                        image: UiImage::new(image),
+                        style: Style {
+                            width: Val::Px(OPPONENT_BOARD_WIDTH),
+                            height: Val::Px(OPPONENT_BOARD_WIDTH * size.y / size.x),
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    },
+                    OpponentBoard,
+                ));
+            });
+
+        commands.insert_resource(opponent);
+    }
+
+    /// Only runs while playing, so the opponent waits while the game is paused
+    fn play_opponent(
+        time: Res<Time>,
+        mut opponent: ResMut<Opponent>,
+        game_assets: Res<GameAssets>,
+        boards: Query<&UiImage, With<OpponentBoard>>,
+        mut timer_texts: Query<&mut Text, With<OpponentTimerText>>,
+        mut images: ResMut<Assets<Image>>,
+    ) {
+        if opponent.is_finished() {
+            return;
+        }
+
+        opponent.timer += time.delta();
+        for mut text in timer_texts.iter_mut() {
+            text.sections[0].value = format!("CPU {}", format_time(opponent.timer));
+        }
+
+        if !opponent.move_timer.tick(time.delta()).just_finished() {
+            return;
+        }
+        opponent.play_move();
+
+        for ui_image in boards.iter() {
+            if let Some(image) = images.get_mut(&ui_image.texture) {
+                image.data = opponent.render(&game_assets).data;
+            }
+        }
+    }
+
+    /// Ends the game as lost once the opponent has cleared its board first
+    fn check_opponent_finished(
+        mut commands: Commands,
+        opponent: Res<Opponent>,
+        board: Res<Board>,
+        board_counts: Res<BoardCounts>,
+        mut game_state: ResMut<NextState<GameState>>,
+    ) {
+        if opponent.is_won() && !board_counts.is_won(board.lives) {
+            commands.insert_resource(GameResult(false));
+            game_state.set(GameState::Finished);
+        }
+    }
+}
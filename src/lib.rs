@@ -0,0 +1,135 @@
+mod credits;
+mod display;
+pub mod game;
+mod menu;
+mod splash;
+mod statistics;
+pub mod style;
+mod util;
+
+use std::path::Path;
+
+use bevy::{
+    prelude::*,
+    window::{WindowResized, WindowTheme},
+};
+use style::{game_assets::hex_image, prelude::*, textures::generate_gradient_texture};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, States)]
+enum AppState {
+    #[default]
+    Splash,
+    Menu,
+    Game,
+    Statistics,
+    /// Watching the replay of the last game
+    Replay,
+    Credits,
+}
+
+/// Opens the game window and runs the game until it is closed
+pub fn run() {
+    let mut app = App::new();
+
+    app.add_plugins(DefaultPlugins.set(WindowPlugin {
+        primary_window: Some(Window {
+            title: "Minesweeper".to_string(),
+            resolution: (850., 850.).into(),
+            window_theme: Some(WindowTheme::Dark),
+            ..Default::default()
+        }),
+        ..Default::default()
+    }))
+    .init_state::<AppState>()
+    .add_systems(Startup, (setup_camera, load_assets))
+    .add_systems(
+        Update,
+        update_board_gradient
+            .run_if(on_event::<WindowResized>().or_else(resource_changed::<GameAssets>)),
+    )
+    .add_plugins((
+        splash::SplashPlugin,
+        menu::MenuPlugin,
+        game::GamePlugin,
+        display::DisplayPlugin,
+        statistics::StatisticsPlugin,
+        credits::CreditsPlugin,
+    ));
+
+    #[cfg(feature = "debug")]
+    app.add_systems(
+        Update,
+        util::log_state_transitions::<AppState>.run_if(state_changed::<AppState>),
+    );
+
+    app.run();
+}
+
+fn setup_camera(mut commands: Commands) {
+    commands.spawn(Camera2dBundle::default());
+}
+
+fn load_assets(
+    mut commands: Commands,
+    asset_server: ResMut<AssetServer>,
+    mut images: ResMut<Assets<Image>>,
+    window: Query<&Window>,
+) {
+    let color_scheme = ColorSchemeDescriptor::NordDark;
+    let game_assets = color_scheme.game_assets();
+    let board_gradient = generate_gradient_texture(
+        game_assets.board,
+        game_assets.board_alt,
+        window_size(window.single()),
+    );
+
+    commands.insert_resource(
+        color_scheme
+            .ui_assets()
+            .with_font(asset_server.load("fonts/FiraCodeNerdFont-SemiBold.ttf")),
+    );
+    commands.insert_resource(
+        game_assets
+            .with_font(asset_server.load("fonts/BigBlueTermPlusNerdFont-Regular.ttf"))
+            .with_tile_count_sprites(tile_count_sprites(&asset_server))
+            .with_hex_tile(images.add(hex_image()))
+            .with_board_gradient(images.add(board_gradient)),
+    );
+    commands.insert_resource(color_scheme);
+}
+
+/// Loads the optional images `textures/count_<n>.png` shown on numbered tiles,
+/// counts without an image are drawn as text
+fn tile_count_sprites(asset_server: &AssetServer) -> [Option<Handle<Image>>; 8] {
+    std::array::from_fn(|index| {
+        let path = format!("textures/count_{}.png", index + 1);
+        Path::new("assets")
+            .join(&path)
+            .exists()
+            .then(|| asset_server.load(path))
+    })
+}
+
+/// Regenerates the board gradient in place, so sprites using it are updated as well
+fn update_board_gradient(
+    mut window_resized_evr: EventReader<WindowResized>,
+    window: Query<&Window>,
+    game_assets: Res<GameAssets>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    window_resized_evr.clear();
+
+    images.insert(
+        game_assets.board_gradient.clone(),
+        generate_gradient_texture(
+            game_assets.board,
+            game_assets.board_alt,
+            window_size(window.single()),
+        ),
+    );
+}
+
+/// Returns the size of the window in logical pixels
+fn window_size(window: &Window) -> UVec2 {
+    Vec2::new(window.width(), window.height()).as_uvec2()
+}
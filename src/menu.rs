@@ -1,6 +1,20 @@
 use bevy::{app::AppExit, prelude::*};
 
-use crate::{style::ui_assets::UiAssets, util::despawn_all, AppState};
+use crate::{
+    display::DisplaySettings,
+    game::{
+        options::{spawn_board_settings, GameOptions},
+        presets::PresetLibrary,
+        saved_game::SavedGame,
+        scores::HighScores,
+    },
+    style::{game_assets::DEFAULT_BOARD_BORDER_THICKNESS, prelude::*},
+    util::{complementary_color, despawn_all},
+    AppState,
+};
+
+#[cfg(feature = "audio")]
+use crate::game::sound::SoundEnabled;
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, States)]
 enum MenuState {
@@ -8,6 +22,9 @@ enum MenuState {
     Settings,
     BoardSettings,
     ColorSettings,
+    HighScores,
+    /// Asks whether to continue the saved game before starting a new one
+    ContinuePrompt,
     #[default]
     Inactive,
 }
@@ -21,11 +38,43 @@ struct OnSettingsMenuScreen;
 #[derive(Component)]
 struct OnBoardSettingsMenuScreen;
 
+#[derive(Component)]
+struct OnColorSettingsMenuScreen;
+
+#[derive(Component)]
+struct OnHighScoresMenuScreen;
+
+#[derive(Component)]
+struct OnContinuePromptScreen;
+
+/// Text of the button toggling sound effects
+#[cfg(feature = "audio")]
+#[derive(Component)]
+struct SoundToggleText;
+
 #[derive(Component)]
 enum MenuButtonAction {
     NewGame,
+    ContinueSavedGame,
+    DiscardSavedGame,
+    EnterStatistics,
+    EnterCredits,
     EnterSettings,
     ExitSettings,
+    EnterBoardSettings,
+    ExitBoardSettings,
+    EnterColorSettings,
+    SelectColorScheme(ColorSchemeDescriptor),
+    ToggleFullscreen,
+    CycleAnimationSpeed,
+    /// Switches the frame around the board to the next color of the scheme,
+    /// hiding it after the last one
+    CycleBoardBorder,
+    ExitColorSettings,
+    EnterHighScores,
+    ExitHighScores,
+    #[cfg(feature = "audio")]
+    ToggleSound,
     ExitGame,
 }
 
@@ -56,18 +105,54 @@ impl Plugin for MenuPlugin {
             )
             .add_systems(
                 OnExit(MenuState::ColorSettings),
-                despawn_all::<OnBoardSettingsMenuScreen>,
+                despawn_all::<OnColorSettingsMenuScreen>,
+            )
+            .add_systems(OnEnter(MenuState::HighScores), Self::setup_high_scores_menu)
+            .add_systems(
+                OnExit(MenuState::HighScores),
+                despawn_all::<OnHighScoresMenuScreen>,
+            )
+            .add_systems(
+                OnEnter(MenuState::ContinuePrompt),
+                Self::setup_continue_prompt,
+            )
+            .add_systems(
+                OnExit(MenuState::ContinuePrompt),
+                despawn_all::<OnContinuePromptScreen>,
             )
             .add_systems(
                 Update,
-                Self::button_actions.run_if(in_state(AppState::Menu)),
+                (
+                    Self::button_actions.run_if(in_state(AppState::Menu)),
+                    // Redraw the swatches in the colors of the selected scheme
+                    (
+                        despawn_all::<OnColorSettingsMenuScreen>,
+                        Self::setup_color_settings_menu,
+                    )
+                        .chain()
+                        .run_if(
+                            in_state(MenuState::ColorSettings).and_then(
+                                resource_changed::<UiAssets>
+                                    .or_else(resource_changed::<GameAssets>)
+                                    .or_else(resource_changed::<DisplaySettings>),
+                            ),
+                        ),
+                ),
             );
+
+        #[cfg(feature = "audio")]
+        app.add_systems(
+            Update,
+            Self::display_sound_toggle
+                .run_if(in_state(MenuState::Settings).and_then(resource_changed::<SoundEnabled>)),
+        );
     }
 }
 
 impl MenuPlugin {
-    #[allow(clippy::type_complexity)]
+    #[allow(clippy::type_complexity, clippy::too_many_arguments)]
     fn button_actions(
+        mut commands: Commands,
         interactions: Query<
             (&Interaction, &MenuButtonAction),
             (Changed<Interaction>, With<Button>),
@@ -75,20 +160,75 @@ impl MenuPlugin {
         mut app_exit_evw: EventWriter<AppExit>,
         mut menu_state: ResMut<NextState<MenuState>>,
         mut app_state: ResMut<NextState<AppState>>,
+        mut display_settings: ResMut<DisplaySettings>,
+        mut game_assets: ResMut<GameAssets>,
+        saved_game: Option<Res<SavedGame>>,
+        #[cfg(feature = "audio")] mut sound_enabled: ResMut<SoundEnabled>,
     ) {
         for (interaction, menu_button_action) in interactions.iter() {
             if *interaction == Interaction::Pressed {
                 match menu_button_action {
-                    MenuButtonAction::NewGame => {
+                    MenuButtonAction::NewGame if saved_game.is_some() => {
+                        menu_state.set(MenuState::ContinuePrompt);
+                    }
+                    // The game continues the saved game while it exists
+                    MenuButtonAction::NewGame | MenuButtonAction::ContinueSavedGame => {
                         app_state.set(AppState::Game);
                         menu_state.set(MenuState::Inactive);
                     }
+                    MenuButtonAction::DiscardSavedGame => {
+                        commands.remove_resource::<SavedGame>();
+                        app_state.set(AppState::Game);
+                        menu_state.set(MenuState::Inactive);
+                    }
+                    MenuButtonAction::EnterStatistics => {
+                        app_state.set(AppState::Statistics);
+                        menu_state.set(MenuState::Inactive);
+                    }
+                    MenuButtonAction::EnterCredits => {
+                        app_state.set(AppState::Credits);
+                        menu_state.set(MenuState::Inactive);
+                    }
                     MenuButtonAction::EnterSettings => {
                         menu_state.set(MenuState::Settings);
                     }
                     MenuButtonAction::ExitSettings => {
                         menu_state.set(MenuState::Main);
                     }
+                    MenuButtonAction::EnterBoardSettings => {
+                        menu_state.set(MenuState::BoardSettings);
+                    }
+                    MenuButtonAction::ExitBoardSettings => {
+                        menu_state.set(MenuState::Settings);
+                    }
+                    MenuButtonAction::EnterColorSettings => {
+                        menu_state.set(MenuState::ColorSettings);
+                    }
+                    MenuButtonAction::SelectColorScheme(color_scheme) => {
+                        color_scheme.apply(&mut commands);
+                    }
+                    MenuButtonAction::ToggleFullscreen => {
+                        display_settings.fullscreen = !display_settings.fullscreen;
+                    }
+                    MenuButtonAction::CycleAnimationSpeed => {
+                        display_settings.animation_speed = display_settings.next_animation_speed();
+                    }
+                    MenuButtonAction::CycleBoardBorder => {
+                        cycle_board_border(&mut game_assets);
+                    }
+                    MenuButtonAction::ExitColorSettings => {
+                        menu_state.set(MenuState::Settings);
+                    }
+                    MenuButtonAction::EnterHighScores => {
+                        menu_state.set(MenuState::HighScores);
+                    }
+                    MenuButtonAction::ExitHighScores => {
+                        menu_state.set(MenuState::Settings);
+                    }
+                    #[cfg(feature = "audio")]
+                    MenuButtonAction::ToggleSound => {
+                        sound_enabled.0 = !sound_enabled.0;
+                    }
                     MenuButtonAction::ExitGame => {
                         app_exit_evw.send(AppExit);
                     }
@@ -114,7 +254,7 @@ impl MenuPlugin {
         let button_text_style = TextStyle {
             font_size: 40.,
             color: ui_assets.foreground,
-            ..Default::default()
+            font: ui_assets.font.clone(),
         };
 
         commands
@@ -150,7 +290,7 @@ impl MenuPlugin {
                                 TextStyle {
                                     color: ui_assets.foreground,
                                     font_size: 80.,
-                                    ..Default::default()
+                                    font: ui_assets.font.clone(),
                                 },
                             )
                             .with_style(Style {
@@ -177,6 +317,24 @@ impl MenuPlugin {
                                 ));
                             });
 
+                        // Statistics button
+                        parent
+                            .spawn((
+                                ButtonBundle {
+                                    style: button_style.clone(),
+                                    background_color: ui_assets.background.into(),
+                                    ..Default::default()
+                                },
+                                MenuButtonAction::EnterStatistics,
+                            ))
+                            .with_children(|parent| {
+                                // TODO: Add icon
+                                parent.spawn(TextBundle::from_section(
+                                    "Statistics",
+                                    button_text_style.clone(),
+                                ));
+                            });
+
                         // Settings button
                         parent
                             .spawn((
@@ -195,6 +353,24 @@ impl MenuPlugin {
                                 ));
                             });
 
+                        // Credits button
+                        parent
+                            .spawn((
+                                ButtonBundle {
+                                    style: button_style.clone(),
+                                    background_color: ui_assets.background.into(),
+                                    ..Default::default()
+                                },
+                                MenuButtonAction::EnterCredits,
+                            ))
+                            .with_children(|parent| {
+                                // TODO: Add icon
+                                parent.spawn(TextBundle::from_section(
+                                    "Credits",
+                                    button_text_style.clone(),
+                                ));
+                            });
+
                         // Quit game button
                         parent
                             .spawn((
@@ -216,7 +392,95 @@ impl MenuPlugin {
             });
     }
 
-    fn setup_settings_menu(mut commands: Commands, ui_assets: Res<UiAssets>) {
+    fn setup_continue_prompt(mut commands: Commands, ui_assets: Res<UiAssets>) {
+        let button_style = Style {
+            width: Val::Px(250.),
+            height: Val::Px(65.),
+            margin: UiRect::all(Val::Px(20.)),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..Default::default()
+        };
+
+        let button_text_style = TextStyle {
+            font_size: 40.,
+            color: ui_assets.foreground,
+            font: ui_assets.font.clone(),
+        };
+
+        commands
+            .spawn((
+                NodeBundle {
+                    style: Style {
+                        align_items: AlignItems::Center,
+                        justify_content: JustifyContent::Center,
+                        width: Val::Percent(100.),
+                        height: Val::Percent(100.),
+                        ..Default::default()
+                    },
+                    background_color: ui_assets.accent.into(),
+                    ..Default::default()
+                },
+                OnContinuePromptScreen,
+            ))
+            .with_children(|parent| {
+                parent
+                    .spawn(NodeBundle {
+                        style: Style {
+                            flex_direction: FlexDirection::Column,
+                            align_items: AlignItems::Center,
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    })
+                    .with_children(|parent| {
+                        parent.spawn(
+                            TextBundle::from_section("Continue saved game?", ui_assets.style_h1())
+                                .with_style(Style {
+                                    margin: UiRect::all(Val::Px(50.)),
+                                    ..Default::default()
+                                }),
+                        );
+
+                        parent
+                            .spawn(NodeBundle {
+                                style: Style {
+                                    flex_direction: FlexDirection::Row,
+                                    ..Default::default()
+                                },
+                                ..Default::default()
+                            })
+                            .with_children(|parent| {
+                                for (text, action) in [
+                                    ("Continue", MenuButtonAction::ContinueSavedGame),
+                                    ("New Game", MenuButtonAction::DiscardSavedGame),
+                                ] {
+                                    parent
+                                        .spawn((
+                                            ButtonBundle {
+                                                style: button_style.clone(),
+                                                background_color: ui_assets.background.into(),
+                                                ..Default::default()
+                                            },
+                                            action,
+                                        ))
+                                        .with_children(|parent| {
+                                            parent.spawn(TextBundle::from_section(
+                                                text,
+                                                button_text_style.clone(),
+                                            ));
+                                        });
+                                }
+                            });
+                    });
+            });
+    }
+
+    fn setup_settings_menu(
+        mut commands: Commands,
+        ui_assets: Res<UiAssets>,
+        #[cfg(feature = "audio")] sound_enabled: Res<SoundEnabled>,
+    ) {
         let button_style = Style {
             width: Val::Px(250.),
             height: Val::Px(65.),
@@ -267,6 +531,74 @@ impl MenuPlugin {
                             },
                         ));
 
+                        parent
+                            .spawn((
+                                ButtonBundle {
+                                    style: button_style.clone(),
+                                    background_color: ui_assets.background_alt.into(),
+                                    ..Default::default()
+                                },
+                                MenuButtonAction::EnterBoardSettings,
+                            ))
+                            .with_children(|parent| {
+                                parent.spawn(TextBundle::from_section(
+                                    "Board",
+                                    button_text_style.clone(),
+                                ));
+                            });
+
+                        parent
+                            .spawn((
+                                ButtonBundle {
+                                    style: button_style.clone(),
+                                    background_color: ui_assets.background_alt.into(),
+                                    ..Default::default()
+                                },
+                                MenuButtonAction::EnterColorSettings,
+                            ))
+                            .with_children(|parent| {
+                                parent.spawn(TextBundle::from_section(
+                                    "Colors",
+                                    button_text_style.clone(),
+                                ));
+                            });
+
+                        parent
+                            .spawn((
+                                ButtonBundle {
+                                    style: button_style.clone(),
+                                    background_color: ui_assets.background_alt.into(),
+                                    ..Default::default()
+                                },
+                                MenuButtonAction::EnterHighScores,
+                            ))
+                            .with_children(|parent| {
+                                parent.spawn(TextBundle::from_section(
+                                    "High Scores",
+                                    button_text_style.clone(),
+                                ));
+                            });
+
+                        #[cfg(feature = "audio")]
+                        parent
+                            .spawn((
+                                ButtonBundle {
+                                    style: button_style.clone(),
+                                    background_color: ui_assets.background_alt.into(),
+                                    ..Default::default()
+                                },
+                                MenuButtonAction::ToggleSound,
+                            ))
+                            .with_children(|parent| {
+                                parent.spawn((
+                                    TextBundle::from_section(
+                                        sound_toggle_text(sound_enabled.0),
+                                        button_text_style.clone(),
+                                    ),
+                                    SoundToggleText,
+                                ));
+                            });
+
                         parent
                             .spawn((
                                 ButtonBundle {
@@ -283,11 +615,524 @@ impl MenuPlugin {
             });
     }
 
-    fn setup_board_settings_menu() {
-        todo!()
+    #[cfg(feature = "audio")]
+    fn display_sound_toggle(
+        sound_enabled: Res<SoundEnabled>,
+        mut texts: Query<&mut Text, With<SoundToggleText>>,
+    ) {
+        for mut text in texts.iter_mut() {
+            text.sections[0].value = sound_toggle_text(sound_enabled.0).to_string();
+        }
+    }
+
+    fn setup_high_scores_menu(
+        mut commands: Commands,
+        ui_assets: Res<UiAssets>,
+        high_scores: Res<HighScores>,
+    ) {
+        let button_style = Style {
+            width: Val::Px(250.),
+            height: Val::Px(65.),
+            margin: UiRect::all(Val::Px(20.)),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..Default::default()
+        };
+
+        let button_text_style = TextStyle {
+            font_size: 40.,
+            color: ui_assets.foreground,
+            font: ui_assets.font.clone(),
+        };
+
+        let entry_text_style = TextStyle {
+            font_size: 30.,
+            color: ui_assets.foreground,
+            font: ui_assets.font.clone(),
+        };
+
+        commands
+            .spawn((
+                NodeBundle {
+                    style: Style {
+                        align_items: AlignItems::Center,
+                        justify_content: JustifyContent::Center,
+                        width: Val::Percent(100.),
+                        height: Val::Percent(100.),
+                        ..Default::default()
+                    },
+                    background_color: ui_assets.background.into(),
+                    ..Default::default()
+                },
+                OnHighScoresMenuScreen,
+            ))
+            .with_children(|parent| {
+                parent
+                    .spawn(NodeBundle {
+                        style: Style {
+                            flex_direction: FlexDirection::Column,
+                            align_items: AlignItems::Center,
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    })
+                    .with_children(|parent| {
+                        parent.spawn(TextBundle::from_section(
+                            "High Scores",
+                            ui_assets.style_title(),
+                        ));
+
+                        if high_scores.0.is_empty() {
+                            parent.spawn(TextBundle::from_section(
+                                "No games won yet",
+                                entry_text_style.clone(),
+                            ));
+                        }
+
+                        for (category, entries) in high_scores.0.iter() {
+                            parent.spawn(TextBundle::from_section(
+                                category.clone(),
+                                ui_assets.style_h1(),
+                            ));
+
+                            for (rank, entry) in entries.iter().enumerate() {
+                                parent.spawn(TextBundle::from_section(
+                                    format!("{}. {:.1}s", rank + 1, entry.seconds),
+                                    entry_text_style.clone(),
+                                ));
+                            }
+                        }
+
+                        parent
+                            .spawn((
+                                ButtonBundle {
+                                    style: button_style,
+                                    background_color: ui_assets.background_alt.into(),
+                                    ..Default::default()
+                                },
+                                MenuButtonAction::ExitHighScores,
+                            ))
+                            .with_children(|parent| {
+                                parent.spawn(TextBundle::from_section("Back", button_text_style));
+                            });
+                    });
+            });
+    }
+
+    fn setup_board_settings_menu(
+        mut commands: Commands,
+        game_options: Option<Res<GameOptions>>,
+        preset_library: Res<PresetLibrary>,
+        ui_assets: Res<UiAssets>,
+    ) {
+        let game_options = match game_options {
+            Some(o) => o.clone(),
+            None => {
+                commands.insert_resource(GameOptions::default());
+                GameOptions::default()
+            }
+        };
+
+        let button_style = Style {
+            width: Val::Px(250.),
+            height: Val::Px(65.),
+            margin: UiRect::all(Val::Px(20.)),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..Default::default()
+        };
+
+        let button_text_style = TextStyle {
+            font_size: 40.,
+            color: ui_assets.foreground,
+            font: ui_assets.font.clone(),
+        };
+
+        let body = commands
+            .spawn((
+                NodeBundle {
+                    style: Style {
+                        align_items: AlignItems::Center,
+                        justify_content: JustifyContent::Center,
+                        width: Val::Percent(100.),
+                        height: Val::Percent(100.),
+                        ..Default::default()
+                    },
+                    background_color: ui_assets.background.into(),
+                    ..Default::default()
+                },
+                OnBoardSettingsMenuScreen,
+            ))
+            .id();
+
+        let settings_column = commands
+            .spawn(NodeBundle {
+                style: Style {
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+            .id();
+        let title = commands
+            .spawn(TextBundle::from_section(
+                "Board settings",
+                ui_assets.style_title(),
+            ))
+            .id();
+        let board_settings =
+            spawn_board_settings(&mut commands, &game_options, &preset_library, &ui_assets);
+        let back_button = commands
+            .spawn((
+                ButtonBundle {
+                    style: button_style,
+                    background_color: ui_assets.background_alt.into(),
+                    ..Default::default()
+                },
+                MenuButtonAction::ExitBoardSettings,
+            ))
+            .with_children(|parent| {
+                parent.spawn(TextBundle::from_section("Back", button_text_style));
+            })
+            .id();
+
+        commands.entity(body).push_children(&[settings_column]);
+        commands
+            .entity(settings_column)
+            .push_children(&[title, board_settings, back_button]);
     }
 
-    fn setup_color_settings_menu() {
-        todo!()
+    fn setup_color_settings_menu(
+        mut commands: Commands,
+        ui_assets: Res<UiAssets>,
+        color_scheme: Res<ColorSchemeDescriptor>,
+        display_settings: Res<DisplaySettings>,
+        game_assets: Res<GameAssets>,
+    ) {
+        let button_style = Style {
+            width: Val::Px(250.),
+            height: Val::Px(65.),
+            margin: UiRect::all(Val::Px(20.)),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..Default::default()
+        };
+
+        let button_text_style = TextStyle {
+            font_size: 40.,
+            color: ui_assets.foreground,
+            font: ui_assets.font.clone(),
+        };
+
+        let preview_style = Style {
+            width: Val::Px(60.),
+            height: Val::Px(60.),
+            margin: UiRect::all(Val::Px(10.)),
+            padding: UiRect::all(Val::Px(15.)),
+            border: UiRect::all(Val::Px(5.)),
+            ..Default::default()
+        };
+
+        commands
+            .spawn((
+                NodeBundle {
+                    style: Style {
+                        align_items: AlignItems::Center,
+                        justify_content: JustifyContent::Center,
+                        width: Val::Percent(100.),
+                        height: Val::Percent(100.),
+                        ..Default::default()
+                    },
+                    background_color: ui_assets.background.into(),
+                    ..Default::default()
+                },
+                OnColorSettingsMenuScreen,
+            ))
+            .with_children(|parent| {
+                parent
+                    .spawn(NodeBundle {
+                        style: Style {
+                            flex_direction: FlexDirection::Column,
+                            align_items: AlignItems::Center,
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    })
+                    .with_children(|parent| {
+                        parent.spawn(TextBundle::from_section("Colors", ui_assets.style_title()));
+
+                        parent
+                            .spawn(NodeBundle {
+                                style: Style {
+                                    flex_direction: FlexDirection::Row,
+                                    flex_wrap: FlexWrap::Wrap,
+                                    justify_content: JustifyContent::Center,
+                                    ..Default::default()
+                                },
+                                ..Default::default()
+                            })
+                            .with_children(|parent| {
+                                for scheme in ColorSchemeDescriptor::values() {
+                                    let scheme_ui = scheme.ui_assets();
+                                    let scheme_game = scheme.game_assets();
+
+                                    let border_color = if scheme == *color_scheme {
+                                        ui_assets.accent
+                                    } else {
+                                        ui_assets.background_alt
+                                    };
+
+                                    // Swatch showing the scheme name and a preview of a
+                                    // covered tile on the scheme background
+                                    parent
+                                        .spawn((
+                                            ButtonBundle {
+                                                style: Style {
+                                                    flex_direction: FlexDirection::Column,
+                                                    align_items: AlignItems::Center,
+                                                    padding: UiRect::all(Val::Px(10.)),
+                                                    margin: UiRect::all(Val::Px(10.)),
+                                                    border: UiRect::all(Val::Px(5.)),
+                                                    ..Default::default()
+                                                },
+                                                border_color: border_color.into(),
+                                                background_color: scheme_ui.background_alt.into(),
+                                                ..Default::default()
+                                            },
+                                            MenuButtonAction::SelectColorScheme(scheme),
+                                        ))
+                                        .with_children(|parent| {
+                                            parent.spawn(TextBundle::from_section(
+                                                scheme.to_string(),
+                                                TextStyle {
+                                                    color: scheme_ui.foreground,
+                                                    ..button_text_style.clone()
+                                                },
+                                            ));
+
+                                            parent
+                                                .spawn(NodeBundle {
+                                                    style: preview_style.clone(),
+                                                    border_color: scheme_ui.accent.into(),
+                                                    background_color: scheme_ui.background.into(),
+                                                    ..Default::default()
+                                                })
+                                                .with_children(|parent| {
+                                                    parent.spawn(NodeBundle {
+                                                        style: Style {
+                                                            width: Val::Percent(100.),
+                                                            height: Val::Percent(100.),
+                                                            ..Default::default()
+                                                        },
+                                                        background_color: scheme_game
+                                                            .tile_covered
+                                                            .into(),
+                                                        ..Default::default()
+                                                    });
+                                                });
+                                        });
+                                }
+                            });
+
+                        parent
+                            .spawn(NodeBundle {
+                                style: Style {
+                                    flex_direction: FlexDirection::Row,
+                                    align_items: AlignItems::Center,
+                                    margin: UiRect::all(Val::Px(10.)),
+                                    ..Default::default()
+                                },
+                                ..Default::default()
+                            })
+                            .with_children(|parent| {
+                                parent.spawn(
+                                    TextBundle::from_section("Fullscreen:", ui_assets.style_h1())
+                                        .with_style(Style {
+                                            margin: UiRect::right(Val::Px(20.)),
+                                            ..Default::default()
+                                        }),
+                                );
+
+                                parent.spawn((
+                                    ButtonBundle {
+                                        style: Style {
+                                            width: Val::Px(50.),
+                                            height: Val::Px(50.),
+                                            border: UiRect::all(Val::Px(10.)),
+                                            ..Default::default()
+                                        },
+                                        border_color: ui_assets.background_alt.into(),
+                                        background_color: if display_settings.fullscreen {
+                                            ui_assets.accent.into()
+                                        } else {
+                                            ui_assets.background_alt.into()
+                                        },
+                                        ..Default::default()
+                                    },
+                                    MenuButtonAction::ToggleFullscreen,
+                                ));
+                            });
+
+                        parent
+                            .spawn(NodeBundle {
+                                style: Style {
+                                    flex_direction: FlexDirection::Row,
+                                    align_items: AlignItems::Center,
+                                    margin: UiRect::all(Val::Px(10.)),
+                                    ..Default::default()
+                                },
+                                ..Default::default()
+                            })
+                            .with_children(|parent| {
+                                parent.spawn(
+                                    TextBundle::from_section("Animations:", ui_assets.style_h1())
+                                        .with_style(Style {
+                                            margin: UiRect::right(Val::Px(20.)),
+                                            ..Default::default()
+                                        }),
+                                );
+
+                                parent
+                                    .spawn((
+                                        ButtonBundle {
+                                            style: button_style.clone(),
+                                            background_color: ui_assets.background_alt.into(),
+                                            ..Default::default()
+                                        },
+                                        MenuButtonAction::CycleAnimationSpeed,
+                                    ))
+                                    .with_children(|parent| {
+                                        parent.spawn(TextBundle::from_section(
+                                            animation_speed_text(display_settings.animation_speed),
+                                            button_text_style.clone(),
+                                        ));
+                                    });
+                            });
+
+                        parent
+                            .spawn(NodeBundle {
+                                style: Style {
+                                    flex_direction: FlexDirection::Row,
+                                    align_items: AlignItems::Center,
+                                    margin: UiRect::all(Val::Px(10.)),
+                                    ..Default::default()
+                                },
+                                ..Default::default()
+                            })
+                            .with_children(|parent| {
+                                parent.spawn(
+                                    TextBundle::from_section("Board border:", ui_assets.style_h1())
+                                        .with_style(Style {
+                                            margin: UiRect::right(Val::Px(20.)),
+                                            ..Default::default()
+                                        }),
+                                );
+
+                                // Swatch in the border color, empty while the border is hidden
+                                let border_shown = game_assets.board_border_thickness > 0.;
+                                parent.spawn((
+                                    ButtonBundle {
+                                        style: Style {
+                                            width: Val::Px(50.),
+                                            height: Val::Px(50.),
+                                            border: UiRect::all(Val::Px(10.)),
+                                            ..Default::default()
+                                        },
+                                        border_color: ui_assets.background_alt.into(),
+                                        background_color: if border_shown {
+                                            game_assets.board_border_color.into()
+                                        } else {
+                                            ui_assets.background_alt.into()
+                                        },
+                                        ..Default::default()
+                                    },
+                                    MenuButtonAction::CycleBoardBorder,
+                                ));
+                            });
+
+                        parent
+                            .spawn((
+                                ButtonBundle {
+                                    style: button_style,
+                                    background_color: ui_assets.background_alt.into(),
+                                    ..Default::default()
+                                },
+                                MenuButtonAction::ExitColorSettings,
+                            ))
+                            .with_children(|parent| {
+                                parent.spawn(TextBundle::from_section("Back", button_text_style));
+                            });
+                    });
+            });
+    }
+}
+
+/// Switches the board border to the next of the complementary tone of the board,
+/// the covered tile color and the cursor color, hiding it after the last one
+fn cycle_board_border(game_assets: &mut GameAssets) {
+    let colors = [
+        complementary_color(game_assets.board),
+        game_assets.tile_covered,
+        game_assets.tile_cursor,
+    ];
+
+    if game_assets.board_border_thickness <= 0. {
+        game_assets.board_border_thickness = DEFAULT_BOARD_BORDER_THICKNESS;
+        game_assets.board_border_color = colors[0];
+        return;
+    }
+
+    match colors
+        .iter()
+        .position(|&color| color == game_assets.board_border_color)
+    {
+        Some(index) if index + 1 < colors.len() => {
+            game_assets.board_border_color = colors[index + 1];
+        }
+        _ => game_assets.board_border_thickness = 0.,
+    }
+}
+
+/// Label of the animation speed button, the redrawn menu shows the new speed
+fn animation_speed_text(animation_speed: f32) -> String {
+    if animation_speed > 0. {
+        format!("{animation_speed}x")
+    } else {
+        "Off".to_string()
+    }
+}
+
+#[cfg(feature = "audio")]
+fn sound_toggle_text(enabled: bool) -> &'static str {
+    if enabled {
+        "Sound: On"
+    } else {
+        "Sound: Off"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::RunSystemOnce;
+
+    use super::*;
+    use crate::style::colors::NordDark;
+
+    #[test]
+    fn main_menu_uses_ui_font() {
+        let font = Handle::weak_from_u128(0x6d696e6573);
+        let mut app = App::new();
+        app.insert_resource(UiAssets::from_colorscheme::<NordDark>().with_font(font.clone()));
+
+        app.world.run_system_once(MenuPlugin::setup_main_menu);
+
+        let mut texts = app.world.query::<&Text>();
+        assert!(texts.iter(&app.world).next().is_some());
+        for text in texts.iter(&app.world) {
+            for section in &text.sections {
+                assert_eq!(section.style.font, font, "{:?}", section.value);
+            }
+        }
     }
 }
@@ -1,6 +1,6 @@
 use bevy::{input::keyboard::KeyboardInput, prelude::*};
 
-use crate::{style::ui_assets::UiAssets, util::despawn_all, AppState};
+use crate::{style::prelude::*, util::despawn_all, AppState};
 
 #[derive(Component)]
 struct OnSplashScreen;
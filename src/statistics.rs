@@ -0,0 +1,354 @@
+use std::fs;
+
+use bevy::prelude::*;
+
+use crate::{
+    game::stats::{StatEntry, Statistics},
+    style::prelude::*,
+    util::{despawn_all, screenshot_dir},
+    AppState,
+};
+
+#[derive(Component)]
+struct OnStatisticsScreen;
+
+/// Dialog asking to confirm resetting the statistics
+#[derive(Component)]
+struct OnResetConfirmation;
+
+#[derive(Component)]
+enum StatisticsButtonAction {
+    Reset,
+    ConfirmReset,
+    CancelReset,
+    /// Opens the directory the screenshots of finished games are saved in
+    Screenshots,
+    Back,
+}
+
+pub struct StatisticsPlugin;
+
+impl Plugin for StatisticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(AppState::Statistics), Self::setup)
+            .add_systems(
+                OnExit(AppState::Statistics),
+                (
+                    despawn_all::<OnStatisticsScreen>,
+                    despawn_all::<OnResetConfirmation>,
+                ),
+            )
+            .add_systems(
+                Update,
+                (
+                    Self::button_actions,
+                    // Redraw the table after the statistics have been reset
+                    (despawn_all::<OnStatisticsScreen>, Self::setup)
+                        .chain()
+                        .run_if(resource_changed::<Statistics>),
+                )
+                    .chain()
+                    .run_if(in_state(AppState::Statistics)),
+            );
+    }
+}
+
+impl StatisticsPlugin {
+    #[allow(clippy::type_complexity)]
+    fn button_actions(
+        mut commands: Commands,
+        interactions: Query<
+            (&Interaction, &StatisticsButtonAction),
+            (Changed<Interaction>, With<Button>),
+        >,
+        dialogs: Query<Entity, With<OnResetConfirmation>>,
+        mut statistics: ResMut<Statistics>,
+        mut app_state: ResMut<NextState<AppState>>,
+        ui_assets: Res<UiAssets>,
+    ) {
+        for (interaction, action) in interactions.iter() {
+            if *interaction != Interaction::Pressed {
+                continue;
+            }
+
+            match action {
+                StatisticsButtonAction::Reset => {
+                    if dialogs.is_empty() {
+                        Self::spawn_reset_confirmation(&mut commands, &ui_assets);
+                    }
+                }
+                StatisticsButtonAction::ConfirmReset => {
+                    *statistics = Statistics::default();
+                    if let Err(err) = statistics.save() {
+                        warn!("Could not save statistics: {err}");
+                    }
+
+                    for dialog in dialogs.iter() {
+                        commands.entity(dialog).despawn_recursive();
+                    }
+                }
+                StatisticsButtonAction::CancelReset => {
+                    for dialog in dialogs.iter() {
+                        commands.entity(dialog).despawn_recursive();
+                    }
+                }
+                StatisticsButtonAction::Screenshots => {
+                    let dir = screenshot_dir();
+                    if let Err(err) = fs::create_dir_all(&dir).and_then(|_| open::that(dir)) {
+                        warn!("Could not open the screenshot directory: {err}");
+                    }
+                }
+                StatisticsButtonAction::Back => {
+                    app_state.set(AppState::Menu);
+                }
+            }
+        }
+    }
+
+    fn setup(mut commands: Commands, ui_assets: Res<UiAssets>, statistics: Res<Statistics>) {
+        let button_style = Style {
+            width: Val::Px(250.),
+            height: Val::Px(65.),
+            margin: UiRect::all(Val::Px(20.)),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..Default::default()
+        };
+
+        let button_text_style = TextStyle {
+            font_size: 40.,
+            color: ui_assets.foreground,
+            font: ui_assets.font.clone(),
+        };
+
+        let row_text_style = TextStyle {
+            font_size: 20.,
+            color: ui_assets.foreground,
+            font: ui_assets.font.clone(),
+        };
+
+        commands
+            .spawn((
+                NodeBundle {
+                    style: Style {
+                        align_items: AlignItems::Center,
+                        justify_content: JustifyContent::Center,
+                        width: Val::Percent(100.),
+                        height: Val::Percent(100.),
+                        ..Default::default()
+                    },
+                    background_color: ui_assets.background.into(),
+                    ..Default::default()
+                },
+                OnStatisticsScreen,
+            ))
+            .with_children(|parent| {
+                parent
+                    .spawn(NodeBundle {
+                        style: Style {
+                            flex_direction: FlexDirection::Column,
+                            align_items: AlignItems::Center,
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    })
+                    .with_children(|parent| {
+                        parent.spawn(TextBundle::from_section(
+                            "Statistics",
+                            ui_assets.style_title(),
+                        ));
+
+                        if statistics.0.is_empty() {
+                            parent.spawn(TextBundle::from_section(
+                                "No games played yet",
+                                row_text_style.clone(),
+                            ));
+                        } else {
+                            parent.spawn(TextBundle::from_section(
+                                table_row(
+                                    "Category", "Played", "Won", "Win%", "Best", "Streak",
+                                    "Longest",
+                                ),
+                                TextStyle {
+                                    color: ui_assets.accent,
+                                    ..row_text_style.clone()
+                                },
+                            ));
+                        }
+
+                        for (category, entry) in statistics.0.iter() {
+                            parent.spawn(TextBundle::from_section(
+                                entry_row(category, entry),
+                                row_text_style.clone(),
+                            ));
+                        }
+
+                        parent
+                            .spawn((
+                                ButtonBundle {
+                                    style: button_style.clone(),
+                                    background_color: ui_assets.background_alt.into(),
+                                    ..Default::default()
+                                },
+                                StatisticsButtonAction::Reset,
+                            ))
+                            .with_children(|parent| {
+                                parent.spawn(TextBundle::from_section(
+                                    "Reset",
+                                    button_text_style.clone(),
+                                ));
+                            });
+
+                        parent
+                            .spawn((
+                                ButtonBundle {
+                                    style: button_style.clone(),
+                                    background_color: ui_assets.background_alt.into(),
+                                    ..Default::default()
+                                },
+                                StatisticsButtonAction::Screenshots,
+                            ))
+                            .with_children(|parent| {
+                                parent.spawn(TextBundle::from_section(
+                                    "Screenshots",
+                                    button_text_style.clone(),
+                                ));
+                            });
+
+                        parent
+                            .spawn((
+                                ButtonBundle {
+                                    style: button_style,
+                                    background_color: ui_assets.background_alt.into(),
+                                    ..Default::default()
+                                },
+                                StatisticsButtonAction::Back,
+                            ))
+                            .with_children(|parent| {
+                                parent.spawn(TextBundle::from_section("Back", button_text_style));
+                            });
+                    });
+            });
+    }
+
+    fn spawn_reset_confirmation(commands: &mut Commands, ui_assets: &UiAssets) {
+        let button_style = Style {
+            width: Val::Px(150.),
+            height: Val::Px(65.),
+            margin: UiRect::all(Val::Px(20.)),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..Default::default()
+        };
+
+        let button_text_style = TextStyle {
+            font_size: 40.,
+            color: ui_assets.foreground,
+            font: ui_assets.font.clone(),
+        };
+
+        commands
+            .spawn((
+                NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        align_items: AlignItems::Center,
+                        justify_content: JustifyContent::Center,
+                        width: Val::Percent(100.),
+                        height: Val::Percent(100.),
+                        ..Default::default()
+                    },
+                    background_color: ui_assets.background.with_a(0.9).into(),
+                    z_index: ZIndex::Global(1),
+                    ..Default::default()
+                },
+                OnResetConfirmation,
+            ))
+            .with_children(|parent| {
+                parent
+                    .spawn(NodeBundle {
+                        style: Style {
+                            flex_direction: FlexDirection::Column,
+                            align_items: AlignItems::Center,
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    })
+                    .with_children(|parent| {
+                        parent.spawn(TextBundle::from_section(
+                            "Reset all statistics?",
+                            ui_assets.style_h1(),
+                        ));
+
+                        parent
+                            .spawn(NodeBundle {
+                                style: Style {
+                                    flex_direction: FlexDirection::Row,
+                                    ..Default::default()
+                                },
+                                ..Default::default()
+                            })
+                            .with_children(|parent| {
+                                parent
+                                    .spawn((
+                                        ButtonBundle {
+                                            style: button_style.clone(),
+                                            background_color: ui_assets.accent.into(),
+                                            ..Default::default()
+                                        },
+                                        StatisticsButtonAction::ConfirmReset,
+                                    ))
+                                    .with_children(|parent| {
+                                        parent.spawn(TextBundle::from_section(
+                                            "Yes",
+                                            button_text_style.clone(),
+                                        ));
+                                    });
+
+                                parent
+                                    .spawn((
+                                        ButtonBundle {
+                                            style: button_style,
+                                            background_color: ui_assets.background_alt.into(),
+                                            ..Default::default()
+                                        },
+                                        StatisticsButtonAction::CancelReset,
+                                    ))
+                                    .with_children(|parent| {
+                                        parent.spawn(TextBundle::from_section(
+                                            "No",
+                                            button_text_style,
+                                        ));
+                                    });
+                            });
+                    });
+            });
+    }
+}
+
+/// Lays out the columns of the statistics table, the font is monospaced
+fn table_row(
+    category: &str,
+    played: &str,
+    won: &str,
+    win_rate: &str,
+    best: &str,
+    streak: &str,
+    longest: &str,
+) -> String {
+    format!("{category:<14}{played:>8}{won:>6}{win_rate:>6}{best:>8}{streak:>8}{longest:>8}")
+}
+
+fn entry_row(category: &str, entry: &StatEntry) -> String {
+    table_row(
+        category,
+        &entry.games_played.to_string(),
+        &entry.games_won.to_string(),
+        &format!("{:.0}", entry.win_rate()),
+        &entry
+            .best_time
+            .map_or_else(|| "-".to_string(), |best| format!("{best:.1}s")),
+        &entry.current_streak.to_string(),
+        &entry.longest_streak.to_string(),
+    )
+}
@@ -0,0 +1,36 @@
+use bevy::prelude::*;
+
+use super::ColorScheme;
+
+const BACKGROUND: Color = Color::rgb(0.16, 0.16, 0.21);
+const CURRENT_LINE: Color = Color::rgb(0.27, 0.28, 0.35);
+const FOREGROUND: Color = Color::rgb(0.97, 0.97, 0.95);
+const COMMENT: Color = Color::rgb(0.38, 0.45, 0.64);
+
+const CYAN: Color = Color::rgb(0.55, 0.91, 0.99);
+const GREEN: Color = Color::rgb(0.31, 0.98, 0.48);
+const ORANGE: Color = Color::rgb(1., 0.72, 0.42);
+const PINK: Color = Color::rgb(1., 0.47, 0.78);
+const PURPLE: Color = Color::rgb(0.74, 0.58, 0.98);
+const RED: Color = Color::rgb(1., 0.33, 0.33);
+const YELLOW: Color = Color::rgb(0.95, 0.98, 0.55);
+
+pub struct Dracula;
+
+impl ColorScheme for Dracula {
+    const BACKGROUND: Color = BACKGROUND;
+    const BACKGROUND_ALT: Color = CURRENT_LINE;
+    const FOREGROUND: Color = FOREGROUND;
+    const FOREGROUND_ALT: Color = COMMENT;
+    const ACCENT: Color = PURPLE;
+    const ACCENT_ALT: Color = PINK;
+
+    const TILE_COVERED: Color = COMMENT;
+    const TILE_UNCOVERED: Color = CURRENT_LINE;
+    const TILE_FLAGGED: Color = ORANGE;
+    const TILE_QUESTIONED: Color = YELLOW;
+    const TILE_MINE: Color = RED;
+    const TILE_CURSOR: Color = CYAN;
+    const TILE_HINT: Color = GREEN;
+    const TILE_COUNT: [Color; 8] = [CYAN, GREEN, RED, PURPLE, ORANGE, PINK, FOREGROUND, YELLOW];
+}
@@ -0,0 +1,39 @@
+use bevy::prelude::*;
+
+use super::ColorScheme;
+
+const BG_0: Color = Color::rgb(0.16, 0.16, 0.16);
+const BG_1: Color = Color::rgb(0.24, 0.22, 0.21);
+const BG_3: Color = Color::rgb(0.4, 0.36, 0.33);
+
+const FG_1: Color = Color::rgb(0.92, 0.86, 0.7);
+const FG_2: Color = Color::rgb(0.84, 0.77, 0.63);
+const GRAY: Color = Color::rgb(0.57, 0.51, 0.45);
+
+const RED: Color = Color::rgb(0.98, 0.29, 0.2);
+const GREEN: Color = Color::rgb(0.72, 0.73, 0.15);
+const YELLOW: Color = Color::rgb(0.98, 0.74, 0.18);
+const BLUE: Color = Color::rgb(0.51, 0.65, 0.6);
+const PURPLE: Color = Color::rgb(0.83, 0.53, 0.61);
+const AQUA: Color = Color::rgb(0.56, 0.75, 0.49);
+const ORANGE: Color = Color::rgb(1., 0.5, 0.1);
+
+pub struct GruvboxDark;
+
+impl ColorScheme for GruvboxDark {
+    const BACKGROUND: Color = BG_0;
+    const BACKGROUND_ALT: Color = BG_1;
+    const FOREGROUND: Color = FG_1;
+    const FOREGROUND_ALT: Color = FG_2;
+    const ACCENT: Color = YELLOW;
+    const ACCENT_ALT: Color = ORANGE;
+
+    const TILE_COVERED: Color = BG_3;
+    const TILE_UNCOVERED: Color = BG_1;
+    const TILE_FLAGGED: Color = ORANGE;
+    const TILE_QUESTIONED: Color = YELLOW;
+    const TILE_MINE: Color = RED;
+    const TILE_CURSOR: Color = AQUA;
+    const TILE_HINT: Color = GREEN;
+    const TILE_COUNT: [Color; 8] = [BLUE, GREEN, RED, PURPLE, ORANGE, AQUA, GRAY, YELLOW];
+}
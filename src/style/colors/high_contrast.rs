@@ -0,0 +1,49 @@
+use bevy::prelude::*;
+
+use super::ColorScheme;
+
+const BLACK: Color = Color::rgb(0., 0., 0.);
+const DARK_GRAY: Color = Color::rgb(0.2, 0.2, 0.2);
+const WHITE: Color = Color::rgb(1., 1., 1.);
+const LIGHT_GRAY: Color = Color::rgb(0.85, 0.85, 0.85);
+
+const YELLOW: Color = Color::rgb(1., 1., 0.);
+const RED: Color = Color::rgb(1., 0., 0.);
+const CYAN: Color = Color::rgb(0., 1., 1.);
+const MAGENTA: Color = Color::rgb(1., 0., 1.);
+const GREEN: Color = Color::rgb(0., 1., 0.);
+
+// Counts are drawn on white, each has a contrast ratio of at least 4.5:1 against it
+const BLUE: Color = Color::rgb(0., 0., 0.8);
+const DARK_GREEN: Color = Color::rgb(0., 0.39, 0.);
+const DARK_RED: Color = Color::rgb(0.69, 0., 0.);
+const NAVY: Color = Color::rgb(0., 0., 0.5);
+const MAROON: Color = Color::rgb(0.5, 0., 0.);
+const TEAL: Color = Color::rgb(0., 0.4, 0.4);
+const INDIGO: Color = Color::rgb(0.29, 0., 0.51);
+
+/// Scheme for players with color-vision deficiency, tiles are black and white
+/// and everything else is told apart by brightness rather than hue
+pub struct HighContrast;
+
+impl ColorScheme for HighContrast {
+    const BACKGROUND: Color = BLACK;
+    const BACKGROUND_ALT: Color = DARK_GRAY;
+    const FOREGROUND: Color = WHITE;
+    const FOREGROUND_ALT: Color = LIGHT_GRAY;
+    const ACCENT: Color = YELLOW;
+    const ACCENT_ALT: Color = CYAN;
+
+    const TILE_COVERED: Color = BLACK;
+    const TILE_UNCOVERED: Color = WHITE;
+    const TILE_FLAGGED: Color = RED;
+    const TILE_QUESTIONED: Color = CYAN;
+    const TILE_MINE: Color = YELLOW;
+    const TILE_CURSOR: Color = MAGENTA;
+    const TILE_HINT: Color = GREEN;
+    const TILE_COUNT: [Color; 8] = [
+        BLUE, DARK_GREEN, DARK_RED, NAVY, MAROON, TEAL, BLACK, INDIGO,
+    ];
+
+    const FONT_SCALE: f32 = 1.25;
+}
@@ -1,6 +1,14 @@
 use bevy::prelude::*;
 
+use super::{game_assets::GameAssets, ui_assets::UiAssets};
+
+mod dracula;
+mod gruvbox;
+mod high_contrast;
 mod nord;
+pub use dracula::Dracula;
+pub use gruvbox::GruvboxDark;
+pub use high_contrast::HighContrast;
 pub use nord::{NordDark, NordLight};
 
 pub trait ColorScheme {
@@ -14,6 +22,189 @@ pub trait ColorScheme {
     const TILE_COVERED: Color;
     const TILE_UNCOVERED: Color;
     const TILE_FLAGGED: Color;
+    const TILE_QUESTIONED: Color;
     const TILE_MINE: Color;
+    const TILE_CURSOR: Color;
+    const TILE_HINT: Color;
     const TILE_COUNT: [Color; 8];
+
+    /// Factor applied to the size of all UI text
+    const FONT_SCALE: f32 = 1.;
+
+    /// Returns the color used for a tile with `count` neighboring bombs
+    fn tile_count_color(count: usize) -> Color {
+        Self::TILE_COUNT[tile_count_index(count)]
+    }
+}
+
+/// Maps a neighboring bomb count to an index into `ColorScheme::TILE_COUNT`.
+/// Only numbered tiles have a count between 1 and 8, other counts panic in
+/// debug builds and are clamped to the closest valid count in release builds
+pub fn tile_count_index(count: usize) -> usize {
+    debug_assert!(
+        (1..=8).contains(&count),
+        "tiles show between 1 and 8 neighboring bombs, got {count}"
+    );
+
+    // Release builds fall back to the closest valid count
+    count.saturating_sub(1).min(7)
+}
+
+/// Runtime handle to one of the available color schemes,
+/// used where the scheme is not known at compile time
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Resource)]
+pub enum ColorSchemeDescriptor {
+    #[default]
+    NordDark,
+    NordLight,
+    Dracula,
+    GruvboxDark,
+    HighContrast,
+}
+
+impl ColorSchemeDescriptor {
+    pub fn values() -> impl Iterator<Item = ColorSchemeDescriptor> {
+        [
+            ColorSchemeDescriptor::NordDark,
+            ColorSchemeDescriptor::NordLight,
+            ColorSchemeDescriptor::Dracula,
+            ColorSchemeDescriptor::GruvboxDark,
+            ColorSchemeDescriptor::HighContrast,
+        ]
+        .iter()
+        .copied()
+    }
+
+    pub fn ui_assets(&self) -> UiAssets {
+        match self {
+            ColorSchemeDescriptor::NordDark => UiAssets::from_colorscheme::<NordDark>(),
+            ColorSchemeDescriptor::NordLight => UiAssets::from_colorscheme::<NordLight>(),
+            ColorSchemeDescriptor::Dracula => UiAssets::from_colorscheme::<Dracula>(),
+            ColorSchemeDescriptor::GruvboxDark => UiAssets::from_colorscheme::<GruvboxDark>(),
+            ColorSchemeDescriptor::HighContrast => UiAssets::from_colorscheme::<HighContrast>(),
+        }
+    }
+
+    pub fn game_assets(&self) -> GameAssets {
+        match self {
+            ColorSchemeDescriptor::NordDark => GameAssets::from_colorscheme::<NordDark>(),
+            ColorSchemeDescriptor::NordLight => GameAssets::from_colorscheme::<NordLight>(),
+            ColorSchemeDescriptor::Dracula => GameAssets::from_colorscheme::<Dracula>(),
+            ColorSchemeDescriptor::GruvboxDark => GameAssets::from_colorscheme::<GruvboxDark>(),
+            ColorSchemeDescriptor::HighContrast => GameAssets::from_colorscheme::<HighContrast>(),
+        }
+    }
+
+    /// Re-inserts `UiAssets` and `GameAssets` with the colors of this scheme,
+    /// keeping the fonts and images of the current resources
+    pub fn apply(&self, commands: &mut Commands) {
+        commands.insert_resource(*self);
+
+        let ui_assets = self.ui_assets();
+        let game_assets = self.game_assets();
+
+        commands.add(move |world: &mut World| {
+            let ui_font = world
+                .get_resource::<UiAssets>()
+                .map(|assets| assets.font.clone())
+                .unwrap_or_default();
+            let game_assets = match world.get_resource::<GameAssets>() {
+                Some(assets) => game_assets.with_handles_of(assets),
+                None => game_assets,
+            };
+
+            world.insert_resource(ui_assets.with_font(ui_font));
+            world.insert_resource(game_assets);
+        });
+    }
+}
+
+impl std::fmt::Display for ColorSchemeDescriptor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ColorSchemeDescriptor::NordDark => "Nord Dark",
+            ColorSchemeDescriptor::NordLight => "Nord Light",
+            ColorSchemeDescriptor::Dracula => "Dracula",
+            ColorSchemeDescriptor::GruvboxDark => "Gruvbox Dark",
+            ColorSchemeDescriptor::HighContrast => "High Contrast",
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// WCAG relative luminance of a color in sRGB
+    fn relative_luminance(color: Color) -> f32 {
+        let [r, g, b, _] = color.as_rgba_f32();
+        let linear = |channel: f32| {
+            if channel <= 0.03928 {
+                channel / 12.92
+            } else {
+                ((channel + 0.055) / 1.055).powf(2.4)
+            }
+        };
+        0.2126 * linear(r) + 0.7152 * linear(g) + 0.0722 * linear(b)
+    }
+
+    /// WCAG contrast ratio between two colors, from 1 to 21
+    fn contrast_ratio(a: Color, b: Color) -> f32 {
+        let (a, b) = (relative_luminance(a), relative_luminance(b));
+        (a.max(b) + 0.05) / (a.min(b) + 0.05)
+    }
+
+    fn check_color_scheme<T: ColorScheme>(name: &str) {
+        let colors = [
+            T::BACKGROUND,
+            T::BACKGROUND_ALT,
+            T::FOREGROUND,
+            T::FOREGROUND_ALT,
+            T::ACCENT,
+            T::ACCENT_ALT,
+            T::TILE_COVERED,
+            T::TILE_UNCOVERED,
+            T::TILE_FLAGGED,
+            T::TILE_QUESTIONED,
+            T::TILE_MINE,
+            T::TILE_CURSOR,
+            T::TILE_HINT,
+        ];
+        for color in colors.iter().chain(&T::TILE_COUNT) {
+            assert!(
+                color
+                    .as_rgba_f32()
+                    .iter()
+                    .all(|channel| (0. ..=1.).contains(channel)),
+                "{name} has a color outside the sRGB range: {color:?}"
+            );
+        }
+
+        for (index, color) in T::TILE_COUNT.iter().enumerate() {
+            assert!(
+                !T::TILE_COUNT[..index].contains(color),
+                "{name} uses {color:?} for more than one tile count"
+            );
+        }
+
+        let ratio = contrast_ratio(T::FOREGROUND, T::BACKGROUND);
+        assert!(
+            ratio >= 3.,
+            "{name} foreground contrasts {ratio:.2}:1 with the background"
+        );
+    }
+
+    #[test]
+    fn tile_count_color_starts_at_one() {
+        assert_eq!(NordDark::tile_count_color(1), NordDark::TILE_COUNT[0]);
+    }
+
+    #[test]
+    fn color_schemes_are_valid() {
+        check_color_scheme::<NordDark>("Nord Dark");
+        check_color_scheme::<NordLight>("Nord Light");
+        check_color_scheme::<Dracula>("Dracula");
+        check_color_scheme::<GruvboxDark>("Gruvbox Dark");
+        check_color_scheme::<HighContrast>("High Contrast");
+    }
 }
@@ -35,7 +35,10 @@ impl ColorScheme for NordDark {
     const TILE_COVERED: Color = NORD_3;
     const TILE_UNCOVERED: Color = NORD_4;
     const TILE_FLAGGED: Color = NORD_12;
+    const TILE_QUESTIONED: Color = NORD_13;
     const TILE_MINE: Color = NORD_11;
+    const TILE_CURSOR: Color = NORD_7;
+    const TILE_HINT: Color = NORD_14;
     const TILE_COUNT: [Color; 8] = [
         NORD_9, NORD_14, NORD_11, NORD_10, NORD_15, NORD_7, NORD_2, NORD_13,
     ];
@@ -53,7 +56,10 @@ impl ColorScheme for NordLight {
     const TILE_COVERED: Color = NORD_3;
     const TILE_UNCOVERED: Color = NORD_4;
     const TILE_FLAGGED: Color = NORD_12;
+    const TILE_QUESTIONED: Color = NORD_13;
     const TILE_MINE: Color = NORD_11;
+    const TILE_CURSOR: Color = NORD_7;
+    const TILE_HINT: Color = NORD_14;
     const TILE_COUNT: [Color; 8] = [
         NORD_9, NORD_14, NORD_11, NORD_10, NORD_15, NORD_7, NORD_2, NORD_13,
     ];
@@ -1,27 +1,110 @@
-use super::colors::ColorScheme;
-use bevy::prelude::*;
+use super::colors::{tile_count_index, ColorScheme};
+use crate::util::complementary_color;
+use bevy::{
+    prelude::*,
+    render::{
+        render_asset::RenderAssetUsages,
+        render_resource::{Extent3d, TextureDimension, TextureFormat},
+    },
+};
+
+/// Width and height in pixels of the generated hexagon image
+const HEX_IMAGE_SIZE: u32 = 64;
+
+/// Thickness in pixels of the frame around the board
+pub const DEFAULT_BOARD_BORDER_THICKNESS: f32 = 2.;
 
 #[derive(Resource)]
 pub struct GameAssets {
     pub board: Color,
+    /// Color the board background fades to
+    pub board_alt: Color,
+    /// Thickness in pixels of the frame around the board, 0 disables the frame
+    pub board_border_thickness: f32,
+    /// Color of the frame around the board, the complementary tone of `board` by default
+    pub board_border_color: Color,
     pub tile_covered: Color,
     pub tile_uncovered: Color,
     pub tile_flagged: Color,
+    pub tile_questioned: Color,
     pub tile_mine: Color,
+    pub tile_cursor: Color,
+    pub tile_hint: Color,
     pub tile_count: [Color; 8],
     pub tile_count_font: Handle<Font>,
+    /// Images shown on tiles with 1 to 8 neighboring bombs instead of the count text
+    pub tile_count_sprites: [Option<Handle<Image>>; 8],
+    /// White pointy-top hexagon used as the sprite of tiles on hexagonal boards
+    pub hex_tile: Handle<Image>,
+    /// Gradient from `board` to `board_alt` drawn behind the tiles,
+    /// regenerated when the window is resized or the colors change
+    pub board_gradient: Handle<Image>,
+    /// Layout of the tile sprite sheet, tiles are drawn in solid colors
+    /// unless both the layout and the image of the sheet are set
+    pub tile_atlas_layout: Handle<TextureAtlasLayout>,
+    pub tile_atlas_image: Handle<Image>,
+}
+
+/// Graphic of a tile in the tile sprite sheet
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TileSpriteIndex {
+    Cover,
+    Empty,
+    Flag,
+    Mine,
+    /// Bomb count between 1 and 8
+    Number(u8),
+}
+
+impl TileSpriteIndex {
+    /// Returns the index in the sprite sheet, which holds the cover, the empty tile,
+    /// the flag and the mine followed by the numbers 1 to 8
+    pub fn index(&self) -> usize {
+        match self {
+            TileSpriteIndex::Cover => 0,
+            TileSpriteIndex::Empty => 1,
+            TileSpriteIndex::Flag => 2,
+            TileSpriteIndex::Mine => 3,
+            TileSpriteIndex::Number(count) => 3 + (*count).clamp(1, 8) as usize,
+        }
+    }
 }
 
 impl GameAssets {
     pub fn from_colorscheme<T: ColorScheme>() -> Self {
         Self {
             board: T::BACKGROUND,
+            board_alt: T::BACKGROUND_ALT,
+            board_border_thickness: DEFAULT_BOARD_BORDER_THICKNESS,
+            board_border_color: complementary_color(T::BACKGROUND),
             tile_covered: T::TILE_COVERED,
             tile_uncovered: T::TILE_UNCOVERED,
             tile_flagged: T::TILE_FLAGGED,
+            tile_questioned: T::TILE_QUESTIONED,
             tile_mine: T::TILE_MINE,
-            tile_count: T::TILE_COUNT,
+            tile_cursor: T::TILE_CURSOR,
+            tile_hint: T::TILE_HINT,
+            tile_count: std::array::from_fn(|index| T::tile_count_color(index + 1)),
             tile_count_font: Default::default(),
+            tile_count_sprites: Default::default(),
+            hex_tile: Default::default(),
+            board_gradient: Default::default(),
+            tile_atlas_layout: Default::default(),
+            tile_atlas_image: Default::default(),
+        }
+    }
+
+    /// Takes the fonts, images and layouts of other assets, which are
+    /// loaded once and kept when the color scheme changes
+    pub fn with_handles_of(self, other: &GameAssets) -> Self {
+        Self {
+            tile_count_font: other.tile_count_font.clone(),
+            tile_count_sprites: other.tile_count_sprites.clone(),
+            hex_tile: other.hex_tile.clone(),
+            board_gradient: other.board_gradient.clone(),
+            tile_atlas_layout: other.tile_atlas_layout.clone(),
+            tile_atlas_image: other.tile_atlas_image.clone(),
+            ..self
         }
     }
 
@@ -30,9 +113,49 @@ impl GameAssets {
         self
     }
 
+    pub fn with_tile_count_sprites(mut self, sprites: [Option<Handle<Image>>; 8]) -> Self {
+        self.tile_count_sprites = sprites;
+        self
+    }
+
+    pub fn with_hex_tile(mut self, hex_tile: Handle<Image>) -> Self {
+        self.hex_tile = hex_tile;
+        self
+    }
+
+    pub fn with_board_gradient(mut self, board_gradient: Handle<Image>) -> Self {
+        self.board_gradient = board_gradient;
+        self
+    }
+
+    /// Returns the sprite sheet image and the atlas selecting a tile graphic,
+    /// `None` when tiles are drawn in solid colors
+    pub fn tile_atlas(&self, sprite: TileSpriteIndex) -> Option<(Handle<Image>, TextureAtlas)> {
+        if self.tile_atlas_layout == Handle::default() || self.tile_atlas_image == Handle::default()
+        {
+            return None;
+        }
+
+        Some((
+            self.tile_atlas_image.clone(),
+            TextureAtlas {
+                layout: self.tile_atlas_layout.clone(),
+                index: sprite.index(),
+            },
+        ))
+    }
+
+    /// Returns the image shown on a tile with `count` neighboring bombs,
+    /// `None` when the count is drawn as text
+    pub fn tile_count_sprite(&self, count: usize) -> Option<Handle<Image>> {
+        self.tile_count_sprites.get(count.checked_sub(1)?)?.clone()
+    }
+
+    /// Returns the color of a tile showing `count` neighboring bombs, following
+    /// the same rule as `ColorScheme::tile_count_color` the colors were taken from.
+    /// Counts outside 1 to 8 panic in debug builds and are clamped in release builds
     pub fn count_color(&self, count: usize) -> Color {
-        let count = count.saturating_sub(1).min(7);
-        self.tile_count[count]
+        self.tile_count[tile_count_index(count)]
     }
 }
 
@@ -40,10 +163,16 @@ impl Default for GameAssets {
     fn default() -> Self {
         Self {
             board: Color::WHITE,
+            board_alt: Color::GRAY,
+            board_border_thickness: DEFAULT_BOARD_BORDER_THICKNESS,
+            board_border_color: complementary_color(Color::WHITE),
             tile_covered: Color::DARK_GRAY,
             tile_uncovered: Color::GRAY,
             tile_flagged: Color::RED,
+            tile_questioned: Color::YELLOW,
             tile_mine: Color::RED,
+            tile_cursor: Color::CYAN,
+            tile_hint: Color::LIME_GREEN,
             tile_count: [
                 Color::BLUE,
                 Color::GREEN,
@@ -55,6 +184,74 @@ impl Default for GameAssets {
                 Color::DARK_GRAY,
             ],
             tile_count_font: Default::default(),
+            tile_count_sprites: Default::default(),
+            hex_tile: Default::default(),
+            board_gradient: Default::default(),
+            tile_atlas_layout: Default::default(),
+            tile_atlas_image: Default::default(),
+        }
+    }
+}
+
+/// Generates a white pointy-top hexagon filling a square image,
+/// sprites using it are tinted by their color like the default texture
+pub fn hex_image() -> Image {
+    let half = HEX_IMAGE_SIZE as f32 / 2.;
+    let data = (0..HEX_IMAGE_SIZE)
+        .flat_map(|y| (0..HEX_IMAGE_SIZE).map(move |x| (x, y)))
+        .flat_map(|(x, y)| {
+            let u = ((x as f32 + 0.5) / half - 1.).abs();
+            let v = ((y as f32 + 0.5) / half - 1.).abs();
+            let alpha = if v <= 1. - u / 2. { 255 } else { 0 };
+            [255, 255, 255, alpha]
+        })
+        .collect();
+
+    Image::new(
+        Extent3d {
+            width: HEX_IMAGE_SIZE,
+            height: HEX_IMAGE_SIZE,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::style::colors::{Dracula, GruvboxDark, HighContrast, NordDark, NordLight};
+
+    fn check_count_colors<T: ColorScheme>() {
+        let game_assets = GameAssets::from_colorscheme::<T>();
+        for count in 1..=8 {
+            assert_eq!(game_assets.count_color(count), T::TILE_COUNT[count - 1]);
         }
     }
+
+    #[test]
+    fn count_color_matches_color_scheme() {
+        check_count_colors::<NordDark>();
+        check_count_colors::<NordLight>();
+        check_count_colors::<Dracula>();
+        check_count_colors::<GruvboxDark>();
+        check_count_colors::<HighContrast>();
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "between 1 and 8")]
+    fn count_color_rejects_zero() {
+        GameAssets::default().count_color(0);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "between 1 and 8")]
+    fn count_color_rejects_nine() {
+        GameAssets::default().count_color(9);
+    }
 }
@@ -1,3 +1,10 @@
 pub mod colors;
 pub mod game_assets;
+pub mod textures;
 pub mod ui_assets;
+
+pub mod prelude {
+    pub use super::colors::ColorSchemeDescriptor;
+    pub use super::game_assets::GameAssets;
+    pub use super::ui_assets::UiAssets;
+}
@@ -0,0 +1,36 @@
+use bevy::{
+    prelude::*,
+    render::{
+        render_asset::RenderAssetUsages,
+        render_resource::{Extent3d, TextureDimension, TextureFormat},
+    },
+};
+
+use crate::util::lerp_color;
+
+/// Generates a diagonal gradient from `from` in the top left corner
+/// to `to` in the bottom right corner
+pub fn generate_gradient_texture(from: Color, to: Color, size: UVec2) -> Image {
+    let size = size.max(UVec2::ONE);
+    let max = (size - UVec2::ONE).max(UVec2::ONE).as_vec2();
+
+    let data = (0..size.y)
+        .flat_map(|y| (0..size.x).map(move |x| (x, y)))
+        .flat_map(|(x, y)| {
+            let t = (x as f32 / max.x + y as f32 / max.y) / 2.;
+            lerp_color(from, to, t).as_rgba_u8()
+        })
+        .collect();
+
+    Image::new(
+        Extent3d {
+            width: size.x,
+            height: size.y,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    )
+}
@@ -10,6 +10,8 @@ pub struct UiAssets {
     pub accent: Color,
     pub accent_alt: Color,
     pub font: Handle<Font>,
+    /// Factor applied to the size of all text, larger for high contrast
+    pub font_scale: f32,
 }
 
 impl UiAssets {
@@ -22,6 +24,7 @@ impl UiAssets {
             accent: T::ACCENT,
             accent_alt: T::ACCENT_ALT,
             font: Default::default(),
+            font_scale: T::FONT_SCALE,
         }
     }
 
@@ -32,15 +35,20 @@ impl UiAssets {
 
     pub fn style_title(&self) -> TextStyle {
         TextStyle {
-            font_size: 60.,
+            font_size: 60. * self.font_scale,
             color: self.foreground,
             font: self.font.clone(),
         }
     }
 
     pub fn style_h1(&self) -> TextStyle {
+        self.style_h1_scaled(1.)
+    }
+
+    /// Returns the heading style with its size multiplied by `scale`
+    pub fn style_h1_scaled(&self, scale: f32) -> TextStyle {
         TextStyle {
-            font_size: 40.,
+            font_size: 40. * scale * self.font_scale,
             color: self.foreground,
             font: self.font.clone(),
         }
@@ -49,14 +57,14 @@ impl UiAssets {
     pub fn style_h1_accent(&self) -> TextStyle {
         TextStyle {
             font: self.font.clone(),
-            font_size: 40.,
+            font_size: 40. * self.font_scale,
             color: self.accent,
         }
     }
 
     pub fn style_text_accent_alt(&self) -> TextStyle {
         TextStyle {
-            font_size: 20.,
+            font_size: 20. * self.font_scale,
             color: self.accent_alt,
             font: self.font.clone(),
         }
@@ -73,6 +81,7 @@ impl Default for UiAssets {
             accent: Color::RED,
             accent_alt: Color::ORANGE_RED,
             font: Default::default(),
+            font_scale: 1.,
         }
     }
 }
@@ -1,7 +1,89 @@
-use bevy::prelude::*;
+use std::path::PathBuf;
+
+use bevy::{ecs::query::QueryFilter, prelude::*};
 
 pub fn despawn_all<T: Component>(mut commands: Commands, to_despawn: Query<Entity, With<T>>) {
     for entity in to_despawn.iter() {
         commands.entity(entity).despawn_recursive()
     }
 }
+
+/// Despawns every entity with component `T` that also matches the filter `F`
+pub fn despawn_with_filter<T: Component, F: QueryFilter>(
+    mut commands: Commands,
+    to_despawn: Query<Entity, (With<T>, F)>,
+) {
+    for entity in to_despawn.iter() {
+        commands.entity(entity).despawn_recursive()
+    }
+}
+
+/// Despawns every entity with component `T` apart from `except`. Unlike the other
+/// helpers this is called from within a system, as the entity to keep is only known there
+pub fn despawn_all_except<T: Component>(
+    commands: &mut Commands,
+    except: Entity,
+    to_despawn: &Query<Entity, With<T>>,
+) {
+    for entity in to_despawn.iter().filter(|&entity| entity != except) {
+        commands.entity(entity).despawn_recursive()
+    }
+}
+
+pub fn remove_resource<R: Resource>(mut commands: Commands) {
+    commands.remove_resource::<R>();
+}
+
+/// Returns the path of a file in the platform data directory,
+/// or in the working directory if there is none
+pub fn data_file(name: &str) -> PathBuf {
+    dirs::data_dir().unwrap_or_default().join(name)
+}
+
+/// Returns the directory screenshots are saved in, a `Minesweeper` folder in the
+/// platform pictures directory or a `screenshots` folder next to the executable
+pub fn screenshot_dir() -> PathBuf {
+    dirs::picture_dir()
+        .map(|pictures| pictures.join("Minesweeper"))
+        .or_else(|| {
+            std::env::current_exe()
+                .ok()
+                .and_then(|exe| Some(exe.parent()?.join("screenshots")))
+        })
+        .unwrap_or_default()
+}
+
+/// Linearly interpolates between two colors, `t` is clamped to `[0, 1]`
+pub fn lerp_color(from: Color, to: Color, t: f32) -> Color {
+    let t = t.clamp(0., 1.);
+    let from = from.as_rgba_f32();
+    let to = to.as_rgba_f32();
+
+    Color::rgba(
+        from[0] + (to[0] - from[0]) * t,
+        from[1] + (to[1] - from[1]) * t,
+        from[2] + (to[2] - from[2]) * t,
+        from[3] + (to[3] - from[3]) * t,
+    )
+}
+
+/// Returns the complementary tone of a color: the opposite hue with inverted
+/// lightness, so it stands out against dark and light colors alike
+pub fn complementary_color(color: Color) -> Color {
+    let [hue, saturation, lightness, alpha] = color.as_hsla_f32();
+    Color::hsla((hue + 180.) % 360., saturation, 1. - lightness, alpha)
+}
+
+/// Eases from 0 to 1 overshooting past 1 before settling, `t` is clamped to `[0, 1]`.
+/// An `overshoot` of 2.5 peaks at roughly 1.2
+pub fn ease_out_back(t: f32, overshoot: f32) -> f32 {
+    let t = t.clamp(0., 1.) - 1.;
+    1. + (overshoot + 1.) * t.powi(3) + overshoot * t.powi(2)
+}
+
+/// Logs every transition of the state `S`, register with
+/// `.run_if(state_changed::<S>)`
+#[cfg(feature = "debug")]
+pub fn log_state_transitions<S: States>(state: Res<State<S>>) {
+    bevy::log::info!("State<{}> → {:?}", std::any::type_name::<S>(), state.get());
+}